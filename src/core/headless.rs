@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Loader,
+        Prefab,
+    },
+    core::{
+        ArcThreadPool,
+        bundle::SystemBundle,
+        rayon::ThreadPoolBuilder,
+        Time,
+        transform::TransformBundle,
+    },
+    ecs::prelude::*,
+    Error,
+    utils::application_root_dir,
+};
+
+use crate::game::character::{CharacterBundle, CharacterPrefabData};
+use crate::game::combat::{CombatBundle, CombatData, CombatInstance, CombatRoot, CombatState, Team};
+use crate::game::combat::enemy::AiControlledTeams;
+use crate::game::map::{CurrentState, WorldPrefabData};
+
+/// The fixed time step used when simulating a combat.
+pub const HEADLESS_DELTA: f32 = 1.0 / 60.0;
+/// No combat should take more turns than this, so one which does is considered stuck.
+pub const HEADLESS_MAX_TURNS: i32 = 500;
+/// A minute of simulated time, after which a turn which has not ended is considered stuck.
+pub const MAX_STEPS_PER_TURN: usize = 60 * 60;
+
+#[derive(Debug, Error)]
+pub enum HeadlessError {
+    #[error(display = "the combat did not end within {} turns, its final state was {:?}", _0, _1)]
+    TurnLimit(i32, Option<CombatState>),
+    #[error(display = "the combat got stuck on turn {}, its final state was {:?}", _0, _1)]
+    Stalled(i32, Option<CombatState>),
+}
+
+/// A single combat which is simulated without a window, renderer or audio output.
+/// Both teams are controlled by the AI and time is stepped manually, so this can be used to test balance on build servers.
+pub struct HeadlessCombat<'a, 'b> {
+    world: World,
+    dispatcher: Dispatcher<'a, 'b>,
+    instance: CombatInstance,
+}
+
+impl<'a, 'b> HeadlessCombat<'a, 'b> {
+    pub fn new(combat: CombatData, seed: u64) -> Result<Self, Error> {
+        let app_root = application_root_dir()?;
+        let mut world: World = World::new();
+
+        let thread_pool: ArcThreadPool = Arc::new(ThreadPoolBuilder::new().build()?);
+        world.insert(Loader::new(app_root.join("assets"), thread_pool.clone()));
+        world.insert(thread_pool.clone());
+        world.insert(Time::default());
+        // Prefabs are never instantiated, but the handles are still created by the combat systems.
+        world.insert(AssetStorage::<Prefab<CharacterPrefabData>>::new());
+        world.insert(AssetStorage::<Prefab<WorldPrefabData>>::new());
+        world.insert(CurrentState::default());
+        world.insert(AiControlledTeams(vec![Team::Friendly, Team::Enemy]));
+
+        let mut builder: DispatcherBuilder<'a, 'b> = DispatcherBuilder::new().with_pool(thread_pool);
+        TransformBundle::new().build(&mut world, &mut builder)?;
+        CombatBundle::headless().build(&mut world, &mut builder)?;
+        CharacterBundle.build(&mut world, &mut builder)?;
+
+        let mut dispatcher: Dispatcher<'a, 'b> = builder.build();
+        dispatcher.setup(&mut world);
+
+        let instance: CombatInstance = CombatInstance::create(&mut world, combat.clone(), seed);
+        instance.begin(&mut world, &combat);
+
+        Ok(Self {
+            world,
+            dispatcher,
+            instance,
+        })
+    }
+
+    /// Advances the simulation by a single frame of `delta` seconds.
+    pub fn step(&mut self, delta: f32) {
+        {
+            let mut time = self.world.write_resource::<Time>();
+            time.set_delta_seconds(delta);
+            time.increment_frame_number();
+        }
+        self.dispatcher.dispatch(&self.world);
+        self.world.maintain();
+    }
+
+    /// Runs the combat until it ends, returning the state it ended in.
+    /// A defeat is the victory of the enemy team.
+    /// Fails once more than `max_turns` turns have passed, or when a single turn does not end.
+    pub fn run(&mut self, delta: f32, max_turns: i32) -> Result<CombatState, HeadlessError> {
+        let mut turn: i32 = self.turn_count();
+        let mut turn_steps: usize = 0;
+        loop {
+            self.step(delta);
+            match self.state() {
+                Some(state @ CombatState::Victory(_)) | Some(state @ CombatState::Fled) => return Ok(state),
+                _ => {},
+            }
+            if self.turn_count() != turn {
+                turn = self.turn_count();
+                turn_steps = 0;
+            } else {
+                turn_steps += 1;
+            }
+            if turn > max_turns {
+                return Err(HeadlessError::TurnLimit(max_turns, self.state()));
+            }
+            if turn_steps > MAX_STEPS_PER_TURN {
+                return Err(HeadlessError::Stalled(turn, self.state()));
+            }
+        }
+    }
+
+    pub fn state(&self) -> Option<CombatState> {
+        self.world.read_storage::<CombatRoot>().get(self.instance.root_ent).map(|root| root.current_state)
+    }
+
+    pub fn turn_count(&self) -> i32 {
+        self.world.read_storage::<CombatRoot>().get(self.instance.root_ent).map_or(0, |root| root.turn_count)
+    }
+
+    pub fn instance(&self) -> CombatInstance {
+        self.instance
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::character::CharacterId;
+    use crate::game::combat::{CharacterSpawn, Rank, Wave};
+
+    fn combat() -> CombatData {
+        let wave: Wave = Wave::new_simple(vec![CharacterSpawn::new(CharacterId("spacebot"), Rank::Basic)]);
+        CombatData::basic("headless_test", "maps/sol/moon.ron", vec![wave])
+    }
+
+    fn run(seed: u64) -> CombatState {
+        HeadlessCombat::new(combat(), seed)
+            .expect("Failed to create the simulation!")
+            .run(HEADLESS_DELTA, HEADLESS_MAX_TURNS)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[test]
+    fn combat_reaches_victory() {
+        let state: CombatState = run(1);
+        assert!(matches!(state, CombatState::Victory(_)), "the combat ended in {:?}", state);
+    }
+
+    #[test]
+    fn combat_is_deterministic_for_a_seed() {
+        assert_eq!(run(7), run(7));
+    }
+}
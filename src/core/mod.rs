@@ -56,17 +56,13 @@ use crate::game;
 use crate::game::combat::process::Principal;
 use crate::game::ui::font::GameFonts;
 use crate::state::*;
-use rand::Rng;
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::StdRng;
 use crate::game::character::{CharacterStore, CharacterRole};
 use crate::game::combat::ability::{AbilityList, AbilityUsability};
 use crate::game::combat::ability::charge::ChargeAbility;
 use crate::game::combat::ability::spawn::SpawnAbility;
 use crate::game::combat::ability::hack::HackAbility;
-use crate::game::combat::ability::barrage::BarrageAbility;
-use crate::game::combat::ability::twin_shot::TwinShotAbility;
-use crate::game::combat::ability::focused_charge::FocusedChargeAbility;
-use crate::game::combat::ability::snipe::SnipeAbility;
-use crate::game::combat::ability::annihilate::AnnihilateAbility;
 use crate::game::map::CombatStore;
 use crate::game::combat::{CombatData, Wave};
 use crate::game::CoreGameBundle;
@@ -74,7 +70,9 @@ use crate::core::rebuild_pass::RebuildPlugin;
 
 pub mod action;
 pub mod activity;
+pub mod headless;
 pub mod rebuild_pass;
+pub mod registry;
 mod add_to_limit;
 
 /// Builds the application object for the game.
@@ -96,7 +94,7 @@ pub fn build_application<'a, 'b, 'c, S: State<AggregateData<'a, 'b>, StateEvent>
         .with_combat(
             GameDataBuilder::new()
                 // Our custom core game bundle data.
-                .with_bundle(game::combat::CombatBundle)?
+                .with_bundle(game::combat::CombatBundle::default())?
                 .with_bundle(game::character::CharacterBundle)?
         )
         .with_map(
@@ -270,9 +268,74 @@ pub fn load_texture(world: &mut World, path: impl Into<String>) -> Handle<Textur
     )
 }
 
+/// The seeded random number generator which all combat randomness goes through.
+/// The seed is stored on the `CombatRoot`, so the same seed reproduces the same fight.
+#[derive(Debug, Clone)]
+pub struct CombatRng {
+    seed: u64,
+    rng: StdRng,
+    /// The number of 32 bit words drawn from the generator so far.
+    draws: u64,
+}
+
+impl CombatRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            draws: 0,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Discards values until `draws` words have been drawn in total.
+    /// Used by replays, where the AI no longer draws the values it did while recording.
+    /// Returns false if more values have already been drawn.
+    pub fn skip_to(&mut self, draws: u64) -> bool {
+        while self.draws < draws {
+            self.next_u32();
+        }
+        self.draws == draws
+    }
+}
+
+impl Default for CombatRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl RngCore for CombatRng {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 2;
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += ((dest.len() + 3) / 4) as u64;
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draws += ((dest.len() + 3) / 4) as u64;
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
 /// Rolls a 'dice' based on the specified chance.
-pub fn roll(chance: f32) -> bool {
-    let mut rng = rand::thread_rng();
+pub fn roll(rng: &mut CombatRng, chance: f32) -> bool {
     let value: f32 = rng.gen_range(0.0, 1.0);
     if value < chance {
         true
@@ -281,7 +344,7 @@ pub fn roll(chance: f32) -> bool {
     }
 }
 
-pub fn select_rng(values: &[f32]) -> Option<usize> {
+pub fn select_rng(rng: &mut CombatRng, values: &[f32]) -> Option<usize> {
     if values.is_empty() {
         return None;
     }
@@ -292,7 +355,6 @@ pub fn select_rng(values: &[f32]) -> Option<usize> {
     if total <= 0.0 {
         return None;
     }
-    let mut rng = rand::thread_rng();
     let random: f32 = rng.gen_range(0.0, total);
     let mut current: f32 = 0.0;
     for (i, value) in values.iter().enumerate() {
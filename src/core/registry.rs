@@ -0,0 +1,40 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref INTERNED_IDS: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+/// Returns a `'static` copy of the specified id, so that the ids of characters, abilities etc. can stay `Copy`.
+/// Each distinct id is only allocated once, however many times it is loaded.
+pub fn intern(id: &str) -> &'static str {
+    let mut interned = INTERNED_IDS.lock().expect("Interned ids poisoned!");
+    if let Some(existing) = interned.get(id) {
+        existing
+    } else {
+        let leaked: &'static str = Box::leak(id.to_string().into_boxed_str());
+        interned.insert(leaked);
+        leaked
+    }
+}
+
+/// Maps stable string ids to the runtime types which implement them (e.g. the rule which decides a combat).
+/// Unlike `TypeId`s, the ids can be saved and loaded, and do not change between compiler versions.
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry {
+    types: HashMap<&'static str, TypeId>,
+}
+
+impl TypeRegistry {
+    /// Overwrites any type which was previously registered with the same id.
+    pub fn register<T: 'static>(&mut self, id: &'static str) {
+        self.types.insert(id, TypeId::of::<T>());
+    }
+
+    pub fn type_id(&self, id: &str) -> Option<TypeId> {
+        self.types.get(id).copied()
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::Path;
+
+use amethyst::{
+    assets::{
+        Format,
+        RonFormat,
+    },
+    core::SystemDesc,
+    ecs::prelude::*,
+    log::{error, warn},
+    utils::application_root_dir,
+};
+
+use crate::game::character::{CharacterData, CharacterId, CharacterRole, CharacterStore, Stats, UnassignedCharacter};
+use crate::game::combat::Team;
+use crate::game::combat::ability::Element;
+use crate::game::combat::status::StatusType;
+
+/// The directory (relative to the application root) which is searched for character definitions.
+pub const CHARACTER_DIR: &str = "assets/object/character";
+/// The file name of a character definition, which sits next to the character's prefab.
+pub const CHARACTER_FILE: &str = "character.ron";
+
+/// A character definition, as it is stored in `assets/object/character/**/character.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterDef {
+    pub id: CharacterId,
+    /// The path of the prefab, relative to the assets directory.
+    pub prefab: String,
+    pub name: String,
+    pub description: String,
+    pub max_charge: f32,
+    pub max_health: f32,
+    pub initial_charge: f32,
+    pub natural_charge: f32,
+    pub artificial_charge: f32,
+    /// The higher the modifier, the harder the character is to hack.
+    pub hack_modifier: Option<f32>,
+    pub role: CharacterRole,
+    pub allegiance: Team,
+    pub turns: i32,
+    pub crosshair_scale: f32,
+    /// The statuses which can never be inflicted on the character.
+    #[serde(default)]
+    pub immunities: Vec<StatusType>,
+    #[serde(default)]
+    pub attack: Stats,
+    #[serde(default)]
+    pub resistance: Stats,
+    /// The element of the character itself, which is used for the type-effectiveness matrix in `assets/elements.ron`.
+    #[serde(default)]
+    pub element: Option<Element>,
+    /// The proportion of damage which is blocked, from 0 to 1.
+    #[serde(default)]
+    pub armour: f32,
+    /// The proportion of the target's armour which the character's attacks ignore, from 0 to 1.
+    #[serde(default)]
+    pub penetration: f32,
+    #[serde(default = "CharacterDef::default_crit_chance")]
+    pub crit_chance: f32,
+    #[serde(default = "CharacterDef::default_crit_multiplier")]
+    pub crit_multiplier: f32,
+    /// The chance of each hit against the character being a glancing blow.
+    #[serde(default = "CharacterDef::default_glance_chance")]
+    pub glance_chance: f32,
+    #[serde(default = "CharacterDef::default_glance_multiplier")]
+    pub glance_multiplier: f32,
+    /// Determines how often the character acts in the initiative turn mode.
+    #[serde(default = "CharacterDef::default_speed")]
+    pub speed: f32,
+}
+
+impl CharacterDef {
+    fn default_crit_chance() -> f32 {
+        0.05
+    }
+
+    fn default_crit_multiplier() -> f32 {
+        1.5
+    }
+
+    fn default_glance_chance() -> f32 {
+        0.1
+    }
+
+    fn default_glance_multiplier() -> f32 {
+        0.5
+    }
+
+    fn default_speed() -> f32 {
+        1.0
+    }
+
+    /// Creates the character data for this definition.
+    /// The strings are leaked, since characters are only loaded once and `CharacterData` needs to stay `Copy`.
+    pub fn data(&self) -> CharacterData {
+        CharacterData {
+            role: self.role,
+            max_charge: self.max_charge,
+            max_health: self.max_health,
+            initial_charge: self.initial_charge,
+            natural_charge: self.natural_charge,
+            artificial_charge: self.artificial_charge,
+            turns: self.turns,
+            attack: self.attack,
+            name: Box::leak(self.name.clone().into_boxed_str()),
+            description: Box::leak(self.description.clone().into_boxed_str()),
+            resistance: self.resistance,
+            hack_modifier: self.hack_modifier,
+            allegiance: self.allegiance,
+            crosshair_scale: self.crosshair_scale,
+            immunities: Box::leak(self.immunities.clone().into_boxed_slice()),
+            element: self.element,
+            armour: self.armour,
+            penetration: self.penetration,
+            crit_chance: self.crit_chance,
+            crit_multiplier: self.crit_multiplier,
+            glance_chance: self.glance_chance,
+            glance_multiplier: self.glance_multiplier,
+            speed: self.speed,
+            base_dmg: 1.0,
+            base_accuracy: 1.0,
+            base_evade: 1.0,
+            hit_bias: 0.0,
+        }
+    }
+}
+
+/// Recursively loads every character definition in the specified directory, sorted by id.
+/// Files which fail to load are skipped.
+pub fn load_characters(dir: impl AsRef<Path>) -> Vec<CharacterDef> {
+    let mut defs: Vec<CharacterDef> = Vec::new();
+    collect_characters(dir.as_ref(), &mut defs);
+    defs.sort_by_key(|def| def.id);
+    defs
+}
+
+fn collect_characters(dir: &Path, defs: &mut Vec<CharacterDef>) {
+    match fs::read_dir(dir) {
+        Ok(entries) => {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.is_dir() {
+                    collect_characters(&path, defs);
+                } else if path.file_name().map_or(false, |name| name == CHARACTER_FILE) {
+                    match fs::read(&path) {
+                        Ok(bytes) => {
+                            match Format::<CharacterDef>::import_simple(&RonFormat, bytes) {
+                                Ok(def) => defs.push(def),
+                                Err(err) => warn!("[load_characters] Failed to parse {:?}: {:?}", path, err),
+                            }
+                        },
+                        Err(err) => warn!("[load_characters] Failed to read {:?}: {:?}", path, err),
+                    }
+                }
+            }
+        },
+        Err(err) => warn!("[load_characters] Failed to read character directory {:?}: {:?}", dir, err),
+    }
+}
+
+/// Registers the character definitions with the `CharacterStore` and assigns newly spawned characters.
+#[derive(Debug, Copy, Clone, Default, new, SystemDesc)]
+#[system_desc(name(CharacterDataSystemDesc))]
+pub struct CharacterDataSystem;
+
+impl<'s> System<'s> for CharacterDataSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, UnassignedCharacter>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        let defs: Vec<CharacterDef> = match application_root_dir() {
+            Ok(app_root) => load_characters(app_root.join(CHARACTER_DIR)),
+            Err(err) => {
+                error!("[CharacterDataSystem] Failed to find the application root: {:?}", err);
+                Vec::new()
+            },
+        };
+        for def in defs {
+            CharacterStore::register(world, def.id, def.data(), def.prefab.clone());
+        }
+    }
+
+    fn run(&mut self, (entities, mut unassigned_characters): Self::SystemData) {
+        let to_assign: Vec<Entity> = (&entities, &unassigned_characters).join().map(|(entity, _)| entity).collect();
+        for entity in to_assign {
+            unassigned_characters.remove(entity);
+        }
+    }
+}
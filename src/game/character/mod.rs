@@ -1,7 +1,7 @@
 use std::any::{
     Any, TypeId,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use amethyst::{
@@ -37,10 +37,14 @@ use amethyst::{
         GltfSceneAsset,
         GltfSceneFormat,
     },
+    log::warn,
     renderer::light::LightPrefab,
+    shrev::EventChannel,
 };
 use crate::amethyst::derive;
-use crate::core::{get_root, get_root_mut, roll};
+use crate::core::{CombatRng, get_root, get_root_mut, roll};
+use crate::core::registry::intern;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use crate::game::combat::{ability::{
     Ability,
     AbilityData,
@@ -48,123 +52,26 @@ use crate::game::combat::{ability::{
     AbilityList,
     AbilityUsability,
     Element,
+    DmgPackage,
 }, CombatRoot, status::{
+    StatusDef,
     StatusEffect,
+    StatusInflictedEvent,
+    StatusList,
+    StatusModifiers,
+    StatusTick,
     StatusType,
 }, Team, Rank};
 use crate::game::combat::ability::{UnassignedAbility, StatusInflictDesc};
+use crate::game::combat::difficulty::{DifficultyProfile, StatScaling};
+use crate::game::combat::element::ElementConfig;
+use crate::game::combat::pipeline::{DmgBreakdown, DmgContext, DmgPipeline, DmgResult, HitChances, HitOutcome, roll_variance};
 use crate::game::combat::process::Principal;
 use crate::game::combat::spawn::{SlotManager, Slots, SpawnAction, SpawnProcess, SpawnSystem};
 
-pub use {
-    spacebot::{
-        SpacebotDrone,
-        GunnerSpacebotDrone,
-        ChargeSpacebotDrone,
-        SupporterSpacebotDrone,
-        ModelXDrone,
-    },
-    master::MasterDrone,
-    spangles::SpanglesDrone,
-    sparky::SparkyDrone,
-    blitz::BlitzDrone,
-    defender::DefenderDrone,
-    guardian::GuardianDrone,
-    earth::EarthCharacter,
-};
 use space_render::{Atmosphere, Planet};
 
-macro_rules! define_character {
-    (
-        $C:ident,
-        $S:ident,
-        $SD:ident,
-        $prefab_path:literal,
-        $name:literal,
-        $description:literal,
-        $max_charge:literal,
-        $max_health:literal,
-        $initial_charge:literal,
-        $natural_charge:literal,
-        $artificial_charge:literal,
-        $hack_modifier:expr,
-        $role:expr,
-        $allegiance:expr,
-        $turns:literal,
-        $crosshair_scale:literal,
-    ) => {
-        #[derive(Debug, Copy, Clone, Default, Component)]
-        pub struct $C;
-
-        impl $C {
-            pub fn character_id() -> CharacterId {
-                std::any::TypeId::of::<Self>()
-            }
-
-            pub fn data() -> CharacterData {
-                CharacterData {
-                    role: $role,
-                    max_charge: $max_charge,
-                    max_health: $max_health,
-                    initial_charge: $initial_charge,
-                    natural_charge: $natural_charge,
-                    artificial_charge: $artificial_charge,
-                    turns: $turns,
-                    attack: Stats::default(),
-                    name: $name,
-                    description: $description,
-                    resistance: Stats::default(),
-                    hack_modifier: $hack_modifier,
-                    allegiance: $allegiance,
-                    crosshair_scale: $crosshair_scale,
-                    base_dmg: 1.0,
-                    base_accuracy: 1.0,
-                    base_evade: 1.0,
-                }
-            }
-        }
-
-         #[derive(Debug, Copy, Clone, Default, new, SystemDesc)]
-        #[system_desc(name($SD))]
-        pub struct $S;
-
-        impl<'s> System<'s> for $S {
-            type SystemData = (
-                Entities<'s>,
-                WriteStorage<'s, UnassignedCharacter>,
-                WriteStorage<'s, $C>,
-            );
-
-            fn setup(&mut self, world: &mut World) {
-                CharacterStore::register(world, $C::character_id(), $C::data(), $prefab_path);
-            }
-
-            fn run(&mut self, (entities, mut unassigned_characters, mut unique_drones): Self::SystemData) {
-                let mut to_assign: Vec<Entity> = Vec::new();
-                for (entity, unassigned) in (&entities, &unassigned_characters).join() {
-                    if unassigned.id() == TypeId::of::<$C>() {
-                        to_assign.push(entity);
-                    }
-                }
-                for entity in to_assign {
-                    unassigned_characters.remove(entity);
-                    /// Set up our drone
-                    unique_drones.insert(entity, $C::default());
-                }
-            }
-        }
-    }
-}
-
-pub mod master;
-pub mod spangles;
-pub mod sparky;
-pub mod spacebot;
-pub mod defender;
-pub mod blitz;
-pub mod guardian;
-pub mod earth;
-
+pub mod data;
 
 pub struct CharacterBundle;
 
@@ -175,78 +82,10 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CharacterBundle {
         builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> Result<(), Error> {
 
-        // Controller systems.
         builder.add(
-            master::MasterDroneSystemDesc::default()
-            .build(world),
-        "master_drone",
-        &[],
-        );
-        builder.add(
-            spangles::SpanglesDroneSystemDesc::default()
-                .build(world),
-            "spangles_drone_system",
-            &[],
-        );
-        builder.add(
-            defender::DefenderDroneSystemDesc::default()
+            data::CharacterDataSystemDesc::default()
                 .build(world),
-            "defender_drone",
-            &[],
-        );
-        builder.add(
-            blitz::BlitzDroneSystemDesc::default()
-                .build(world),
-            "blitz_drone",
-            &[],
-        );
-        builder.add(
-            sparky::SparkyDroneSystemDesc::default()
-                .build(world),
-            "sparky_drone",
-            &[],
-        );
-
-        builder.add(
-            spacebot::SpacebotDroneSystemDesc::default()
-                .build(world),
-            "spacebot_drone",
-            &[],
-        );
-        builder.add(
-            spacebot::GunnerSpacebotDroneSystemDesc::default()
-                .build(world),
-            "gunner_spacebot_drone",
-            &[],
-        );
-        builder.add(
-            spacebot::SupporterSpacebotDroneSystemDesc::default()
-                .build(world),
-            "supporter_spacebot_drone",
-            &[],
-        );
-        builder.add(
-            spacebot::ChargeSpacebotDroneSystemDesc::default()
-                .build(world),
-            "charge_spacebot_drone",
-            &[],
-        );
-        builder.add(
-            spacebot::ModelXDroneSystemDesc::default()
-                .build(world),
-            "model_x_drone",
-            &[],
-        );
-        builder.add(
-            guardian::GuardianDroneSystemDesc::default()
-                .build(world),
-            "guardian_drone",
-            &[],
-        );
-        builder.add(
-            earth::EarthCharacterSystemDesc::default()
-                .build(world),
-            "earth_character",
+            "character_data",
             &[],
         );
 
@@ -254,7 +93,27 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CharacterBundle {
     }
 }
 
-pub type CharacterId = TypeId;
+/// Identifies a character, e.g. `"gunner_spacebot"`. This is the id in the character's definition file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CharacterId(pub &'static str);
+
+impl CharacterId {
+    /// The player's drone.
+    pub const MASTER: CharacterId = CharacterId("master");
+}
+
+impl Serialize for CharacterId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CharacterId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id: String = String::deserialize(deserializer)?;
+        Ok(CharacterId(intern(&id)))
+    }
+}
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component, Serialize, Deserialize, PrefabData)]
 #[prefab(Component)]
@@ -266,19 +125,22 @@ impl WeaponSlot {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CharacterRole {
     Master,
     Slave,
     Independent, // Cannot be spawned, but is not a boss. (e.g. ModelX)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// Multipliers for each element. For attack stats they scale the damage dealt, and for resistance stats the damage received.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Stats {
     // Elemental
     pub kinetic: f32,
     pub plasma: f32,
     pub ion: f32,
+    pub radiation: f32,
     pub quantum: f32,
 }
 
@@ -288,6 +150,7 @@ impl Default for Stats {
             kinetic: 1.0,
             plasma: 1.0,
             ion: 1.0,
+            radiation: 1.0,
             quantum: 1.0,
         }
     }
@@ -299,6 +162,7 @@ impl Stats {
             Element::Kinetic => self.kinetic,
             Element::Plasma => self.plasma,
             Element::Ion => self.ion,
+            Element::Radiation => self.radiation,
             Element::Quantum => self.quantum,
         }
     }
@@ -312,43 +176,78 @@ impl Stats {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// The statuses which are currently affecting a character.
+/// Kept ordered, so that ticks are applied in the same order when a combat is replayed.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Statuses {
-    pub map: HashMap<StatusType, usize>,
-}
-
-impl Default for Statuses {
-    fn default() -> Self {
-        let statuses: Vec<StatusType> = StatusType::all();
-        let mut map: HashMap<StatusType, usize> = HashMap::with_capacity(statuses.len());
-        for status in statuses {
-            map.insert(status, 0);
-        }
-        Self { map }
-    }
+    pub map: BTreeMap<StatusType, StatusEffect>,
 }
 
 impl Statuses {
+    /// Returns the remaining turns of the status, which is 0 if the status is not active.
     pub fn get(&self, status: StatusType) -> usize {
-        *self.map.get(&status).unwrap()
+        self.map.get(&status).map_or(0, |effect| effect.turns)
     }
 
-    pub fn append(&mut self, status: StatusType, count: usize) {
-        *self.map.get_mut(&status).unwrap() += count;
+    pub fn effect(&self, status: StatusType) -> Option<&StatusEffect> {
+        self.map.get(&status)
+    }
+
+    /// Returns the remaining turns if the status had any effect.
+    pub fn inflict(&mut self, def: &StatusDef, turns: usize, strength: f32, source: Option<Entity>) -> Option<usize> {
+        if turns == 0 {
+            return None;
+        }
+        if let Some(effect) = self.map.get_mut(&def.ty) {
+            effect.stack(turns, strength, source)
+        } else {
+            self.map.insert(def.ty, StatusEffect::new(def.clone(), turns, strength, source));
+            Some(turns)
+        }
     }
 
     pub fn decrement(&mut self, status: StatusType) {
-        let status = self.map.get_mut(&status).unwrap();
-        if *status != 0 {
-            *status -= 1
+        if let Some(effect) = self.map.get_mut(&status) {
+            effect.turns = effect.turns.saturating_sub(1);
+            if effect.turns == 0 {
+                self.map.remove(&status);
+            }
         }
     }
 
     pub fn take(&mut self, status: StatusType) -> usize {
-        let status = self.map.get_mut(&status).unwrap();
-        let ret = *status;
-        *status = 0;
-        ret
+        self.map.remove(&status).map_or(0, |effect| effect.turns)
+    }
+
+    /// Counts down the statuses which tick at this point of the turn.
+    /// Returns the proportion of max health restored by each status, along with the status' source.
+    pub fn tick(&mut self, tick: StatusTick) -> Vec<(f32, Option<Entity>)> {
+        let mut deltas: Vec<(f32, Option<Entity>)> = Vec::new();
+        for effect in self.map.values_mut() {
+            if effect.def.tick == tick {
+                let delta: f32 = effect.health_per_tick();
+                if delta != 0.0 {
+                    deltas.push((delta, effect.source));
+                }
+                effect.turns = effect.turns.saturating_sub(1);
+            }
+        }
+        self.map.retain(|_, effect| effect.turns > 0);
+        deltas
+    }
+
+    /// The combined stat multipliers of every active status.
+    pub fn modifiers(&self) -> StatusModifiers {
+        self.map.values().fold(StatusModifiers::default(), |modifiers, effect| modifiers.combine(&effect.modifiers()))
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.map.values().any(|effect| effect.def.stun)
+    }
+
+    /// Returns true if an active status grants immunity to the specified status.
+    pub fn is_immune(&self, status: StatusType) -> bool {
+        self.map.values().any(|effect| effect.def.immunities.contains(&status))
     }
 }
 
@@ -373,6 +272,62 @@ pub struct CharacterData {
     pub hack_modifier: Option<f32>,
     pub allegiance: Team,
     pub crosshair_scale: f32,
+    /// The statuses which can never be inflicted on the character.
+    pub immunities: &'static [StatusType],
+    /// The element of the character itself, which is used for the type-effectiveness matrix.
+    pub element: Option<Element>,
+    /// The proportion of damage which is blocked, from 0 to 1.
+    pub armour: f32,
+    /// The proportion of the target's armour which the character's attacks ignore, from 0 to 1.
+    pub penetration: f32,
+    /// The chance of each of the character's hits being critical.
+    pub crit_chance: f32,
+    /// The damage multiplier of critical hits.
+    pub crit_multiplier: f32,
+    /// The chance of each hit against the character being a glancing blow.
+    pub glance_chance: f32,
+    /// The damage multiplier of glancing blows.
+    pub glance_multiplier: f32,
+    /// Determines how often the character acts in the initiative turn mode.
+    pub speed: f32,
+    /// Added to the hit chance of the character's attacks (e.g. by the difficulty).
+    pub hit_bias: f32,
+}
+
+#[cfg(test)]
+impl CharacterData {
+    /// A character whose stats leave every multiplier neutral, for the unit tests of the combat modules.
+    pub fn neutral(allegiance: Team, max_health: f32, speed: f32) -> Self {
+        Self {
+            name: "Test",
+            description: "",
+            resistance: Stats::default(),
+            turns: 1,
+            attack: Stats::default(),
+            role: CharacterRole::Slave,
+            max_health,
+            max_charge: 100.0,
+            initial_charge: 0.0,
+            natural_charge: 0.0,
+            artificial_charge: 0.0,
+            base_dmg: 1.0,
+            base_accuracy: 1.0,
+            base_evade: 1.0,
+            hack_modifier: None,
+            allegiance,
+            crosshair_scale: 1.0,
+            immunities: &[],
+            element: None,
+            armour: 0.0,
+            penetration: 0.0,
+            crit_chance: 0.0,
+            crit_multiplier: 1.5,
+            glance_chance: 0.0,
+            glance_multiplier: 0.5,
+            speed,
+            hit_bias: 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -391,6 +346,8 @@ pub struct Character {
 
     // The number of turns the character currently has. Usually one.
     turns: i32,
+    // Set while the character is waiting for its place in the initiative queue.
+    waiting: bool,
 }
 
 impl Character {
@@ -403,19 +360,11 @@ impl Character {
     }
 
     pub fn accuracy(&self) -> f32 {
-        let mut accuracy = self.data.base_accuracy * self.rank.accuracy_multiplier();
-        if self.has_status(StatusType::Unstable) {
-            accuracy /= 1.5;
-        }
-        if self.has_status(StatusType::Focus) {
-            accuracy *= 2.0;
-        }
-        accuracy
+        self.data.base_accuracy * self.rank.accuracy_multiplier() * self.state.statuses.modifiers().accuracy
     }
 
     pub fn evade(&self) -> f32 {
-        let mut evade = self.data.base_evade * self.rank.evade_multiplier();
-        evade
+        self.data.base_evade * self.rank.evade_multiplier() * self.state.statuses.modifiers().evade
     }
 
     pub fn crosshair_scale(&self) -> f32 {
@@ -426,6 +375,15 @@ impl Character {
         self.data.max_charge = max_charge;
     }
 
+    /// Scales the stats of the character. The current health and charge keep their proportion of the maximum.
+    pub fn scale(&mut self, scaling: &StatScaling) {
+        let health: f32 = self.relative_health();
+        let charge: f32 = self.relative_charge();
+        scaling.apply(&mut self.data);
+        self.state.health = health * self.max_health();
+        self.state.charge = charge * self.max_charge();
+    }
+
     pub fn status(&self, ty: StatusType) -> usize {
         self.state.statuses.get(ty)
     }
@@ -434,8 +392,32 @@ impl Character {
         self.status(ty) > 0
     }
 
-    pub fn append_status(&mut self, ty: StatusType, count: usize) {
-        self.state.statuses.append(ty, count);
+    pub fn status_effect(&self, ty: StatusType) -> Option<&StatusEffect> {
+        self.state.statuses.effect(ty)
+    }
+
+    pub fn status_effects(&self) -> impl Iterator<Item=&StatusEffect> {
+        self.state.statuses.map.values()
+    }
+
+    pub fn is_immune(&self, ty: StatusType) -> bool {
+        self.data.immunities.contains(&ty) || self.state.statuses.is_immune(ty)
+    }
+
+    /// Inflicts the status, following its stacking rules. Immunities are not checked.
+    /// Returns the remaining turns if the status had any effect.
+    pub fn apply_status(&mut self, def: &StatusDef, turns: usize, strength: f32, source: Option<Entity>) -> Option<usize> {
+        self.state.statuses.inflict(def, turns, strength, source)
+    }
+
+    /// Counts down the statuses which tick at this point of the turn, and applies their health changes.
+    /// Returns the change in health caused by each status, along with the status' source.
+    pub fn tick_statuses(&mut self, tick: StatusTick) -> Vec<(f32, Option<Entity>)> {
+        let max_health: f32 = self.max_health();
+        self.state.statuses.tick(tick)
+            .into_iter()
+            .map(|(delta, source)| (self.change_health(delta * max_health), source))
+            .collect()
     }
 
     pub fn decrement_status(&mut self, ty: StatusType) {
@@ -462,6 +444,7 @@ impl Character {
 
     pub fn reset(&mut self) {
         self.turns = self.data.turns;
+        self.waiting = false;
     }
 
     pub fn restore(&mut self) {
@@ -519,15 +502,34 @@ impl Character {
     }
 
     pub fn turns(&self) -> i32 {
-        if self.has_status(StatusType::Scramble) {
+        if self.waiting || self.state.statuses.is_stunned() {
             0
         } else {
             self.turns
         }
     }
 
+    /// The number of turns the character is given at the start of each of its turns.
+    pub fn base_turns(&self) -> i32 {
+        self.data.turns
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.state.statuses.is_stunned()
+    }
+
     pub fn has_turn(&self) -> bool {
-        self.turns() > 0 || self.has_status(StatusType::Overclocked)
+        !self.waiting && (self.turns() > 0 || self.has_status(StatusType::Overclocked))
+    }
+
+    /// Holds back the character's turns until it is reset, without using up any overclocked turns.
+    /// Used by the initiative turn mode while it is not the character's turn.
+    pub fn wait(&mut self) {
+        self.waiting = true;
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
     }
 
     pub fn use_turns(&mut self) {
@@ -838,10 +840,10 @@ impl Character {
                 charge: data.initial_charge * rank.charge_multiplier(),
                 statuses: Statuses::default(),
             },
-            // Same as `TypeId::of<C>()`
             id,
             // Start with 1 turn by default.
             turns: 1,
+            waiting: false,
             rank,
         }
     }
@@ -856,25 +858,60 @@ impl Character {
         self.rank
     }
 
-    pub fn dmg_output(&self, dmg: f32, element: Element) -> f32 {
-        let element_multiplier: f32 = self.data.attack.element_inflict(element);
-        let mut output: f32 = dmg * element_multiplier * self.damage_multiplier();
-        if self.has_status(StatusType::Unstable) {
-            output *= 1.5;
-        }
-        if self.has_status(StatusType::Empower) {
-            output *= 1.5;
-        }
-        output
+    pub fn attack(&self) -> Stats {
+        self.data.attack
     }
 
-    pub fn dmg_receive(&self, dmg: f32, element: Element) -> f32 {
-        let element_multiplier: f32 = self.data.attack.element_receive(element);
-        let mut output: f32 = dmg * element_multiplier;
-        if self.has_status(StatusType::Defend) {
-            output *= 0.5;
-        }
-        output
+    pub fn resistance(&self) -> Stats {
+        self.data.resistance
+    }
+
+    pub fn element(&self) -> Option<Element> {
+        self.data.element
+    }
+
+    pub fn base_dmg(&self) -> f32 {
+        self.data.base_dmg
+    }
+
+    /// Combines the character's resistance with the type-effectiveness of the element against the character.
+    /// Values above 1 are super effective.
+    pub fn element_multiplier(&self, element: Element, element_config: &ElementConfig) -> f32 {
+        self.data.resistance.element_receive(element) * element_config.effectiveness(element, self.data.element)
+    }
+
+    pub fn armour(&self) -> f32 {
+        self.data.armour.max(0.0).min(1.0)
+    }
+
+    pub fn penetration(&self) -> f32 {
+        self.data.penetration
+    }
+
+    /// The chance of an attack with the specified accuracy hitting the target.
+    pub fn hit_chance(&self, target: &Character, accuracy: f32) -> f32 {
+        let exponent = self.accuracy() / target.evade();
+        (accuracy.powf(1.0 / exponent) + self.data.hit_bias).max(0.0).min(1.0)
+    }
+
+    pub fn crit_chance(&self) -> f32 {
+        self.data.crit_chance
+    }
+
+    pub fn crit_multiplier(&self) -> f32 {
+        self.data.crit_multiplier
+    }
+
+    pub fn glance_chance(&self) -> f32 {
+        self.data.glance_chance
+    }
+
+    pub fn glance_multiplier(&self) -> f32 {
+        self.data.glance_multiplier
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.data.speed * self.state.statuses.modifiers().speed
     }
 
     pub fn add_charge(&mut self, charge: f32) -> f32 {
@@ -917,7 +954,9 @@ impl Character {
         specific: Option<&[AbilityId]>,
     ) {
         if let Some(character) = characters.get(character_ent) {
-            let ability_map: HashMap<AbilityId, AbilityData> = list.abilities_for(character);
+            let mut ability_map: Vec<(AbilityId, AbilityData)> = list.abilities_for(character).into_iter().collect();
+            // Keep the order stable so that random selections can be reproduced.
+            ability_map.sort_by_key(|(id, _)| *id);
             for (id, ability_data) in ability_map {
                 Self::insert_ability(
                     entities,
@@ -945,46 +984,74 @@ impl Character {
         unassigned_abilities.insert(ability_ent, UnassignedAbility(ability_data.id));
         parents.insert(ability_ent, Parent { entity: character_ent });
     }
+    /// Resolves the damage of the package through the pipeline and inflicts it on the target.
+    /// The source team is used by modifiers such as the difficulty.
     pub fn inflict_dmg_silent<'s>(
+        rng: &mut CombatRng,
         characters: &mut (impl GenericReadStorage<Component=Character> + GenericWriteStorage<Component=Character>),
-        source_ent: Option<Entity>, target_ent: Entity, dmg: f32, element: Element,
-    ) -> Result<f32, InflictError> {
-        let mut dmg_to_inflict: f32 = 0.0;
-        if let Some(source_ent) = source_ent {
-            if let Some(source) = characters.get(source_ent) {
-                let output: f32 = source.dmg_output(dmg, element);
-                if let Some(target) = characters.get(target_ent) {
-                    dmg_to_inflict = target.dmg_receive(output, element);
-                } else {
-                    return Err(InflictError::InvalidTargetEntity);
-                }
-            } else {
+        pipeline: &DmgPipeline,
+        element_config: &ElementConfig,
+        difficulty: &DifficultyProfile,
+        source_team: Option<Team>,
+        package: &DmgPackage,
+    ) -> Result<DmgResult, InflictError> {
+        if let Some(source_ent) = package.source {
+            if characters.get(source_ent).is_none() {
                 return Err(InflictError::InvalidSourceEntity);
             }
-        } else {
-            if let Some(target) = characters.get(target_ent) {
-                dmg_to_inflict = target.dmg_receive(dmg, element);
-            } else {
-                return Err(InflictError::InvalidTargetEntity);
-            }
         }
-        if dmg_to_inflict != 0.0 {
-            if let Some(target) = characters.get_mut(target_ent) {
-                target.change_health(-dmg_to_inflict);
+        let (chances, outcome, variance): (HitChances, HitOutcome, f32) = {
+            let target: &Character = characters.get(package.target).ok_or(InflictError::InvalidTargetEntity)?;
+            let chances: HitChances = HitChances::new(package.source.and_then(|source_ent| characters.get(source_ent)), target, &package.hit);
+            (chances, chances.roll(rng), roll_variance(rng, &package.hit))
+        };
+        let breakdown: DmgBreakdown = {
+            let target: &Character = characters.get(package.target).ok_or(InflictError::InvalidTargetEntity)?;
+            pipeline.resolve(&DmgContext {
+                package,
+                source: package.source.and_then(|source_ent| characters.get(source_ent)),
+                source_team,
+                target,
+                element_config,
+                difficulty,
+                chances,
+                outcome,
+                variance,
+            })
+        };
+        if breakdown.total != 0.0 {
+            if let Some(target) = characters.get_mut(package.target) {
+                target.change_health(-breakdown.total);
             }
         }
-        Ok(dmg_to_inflict)
+        let follow_ups: Vec<DmgPackage> = {
+            let target: &Character = characters.get(package.target).ok_or(InflictError::InvalidTargetEntity)?;
+            pipeline.post_hit(&DmgContext {
+                package,
+                source: package.source.and_then(|source_ent| characters.get(source_ent)),
+                source_team,
+                target,
+                element_config,
+                difficulty,
+                chances,
+                outcome,
+                variance,
+            }, &breakdown)
+        };
+        Ok(DmgResult {
+            breakdown,
+            follow_ups,
+        })
     }
 
     pub fn check_hit<'s>(
+        rng: &mut CombatRng,
         characters: &impl GenericReadStorage<Component=Character>,
         target_ent: Entity, source_ent: Entity, accuracy: f32,
     ) -> Result<bool, InflictError> {
         if let Some(source) = characters.get(source_ent) {
             if let Some(target) = characters.get(target_ent) {
-                let exponent = source.accuracy() / target.evade();
-                let net = accuracy.powf(1.0 / exponent);
-                Ok(roll(net))
+                Ok(roll(rng, source.hit_chance(target, accuracy)))
             } else {
                 Err(InflictError::InvalidTargetEntity)
             }
@@ -993,14 +1060,20 @@ impl Character {
         }
     }
 
+    /// Returns the remaining turns of the status if it was inflicted.
     pub fn inflict_status_silent<'s>(
+        rng: &mut CombatRng,
         characters: &mut impl GenericWriteStorage<Component=Character>,
-        target_ent: Entity, status_inflict: StatusInflictDesc,
+        status_list: &StatusList,
+        source_ent: Option<Entity>, target_ent: Entity, status_inflict: StatusInflictDesc,
     ) -> Result<Option<usize>, InflictError> {
         if let Some(target) = characters.get_mut(target_ent) {
-            if roll(status_inflict.chance) {
-                target.append_status(status_inflict.ty, status_inflict.turns);
-                return Ok(Some(status_inflict.turns));
+            if roll(rng, status_inflict.chance) && !target.is_immune(status_inflict.ty) {
+                if let Some(def) = status_list.get(status_inflict.ty) {
+                    return Ok(target.apply_status(def, status_inflict.turns, status_inflict.strength, source_ent));
+                } else {
+                    warn!("[Character::inflict_status_silent] No definition for status {:?}.", status_inflict.ty);
+                }
             }
         } else {
             return Err(InflictError::InvalidTargetEntity);
@@ -1008,6 +1081,26 @@ impl Character {
         Ok(None)
     }
 
+    /// Inflicts the status and notifies the listeners of `StatusInflictedEvent` if it had any effect.
+    pub fn inflict_status<'s>(
+        rng: &mut CombatRng,
+        characters: &mut impl GenericWriteStorage<Component=Character>,
+        status_list: &StatusList,
+        status_events: &mut EventChannel<StatusInflictedEvent>,
+        source_ent: Option<Entity>, target_ent: Entity, status_inflict: StatusInflictDesc,
+    ) -> Result<Option<usize>, InflictError> {
+        let result = Self::inflict_status_silent(rng, characters, status_list, source_ent, target_ent, status_inflict);
+        if let Ok(Some(turns)) = result {
+            status_events.single_write(StatusInflictedEvent {
+                source: source_ent,
+                target: target_ent,
+                ty: status_inflict.ty,
+                turns,
+            });
+        }
+        result
+    }
+
     pub fn get_lowest_hp(
         characters: &impl GenericReadStorage<Component=Character>,
         character_ents: &[Entity],
@@ -1044,6 +1137,10 @@ pub struct Defeated {
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct CharacterDefeatedEvent {
     pub character_ent: Entity,
+    /// The `Character` is removed as soon as the event is sent, so readers should use these instead of looking it up.
+    pub character_id: CharacterId,
+    pub name: &'static str,
+    pub team: Option<Team>,
     pub splash_dmg: f32,
     pub killer: Option<Entity>,
 }
@@ -1081,9 +1178,19 @@ impl CharacterStore {
                 res.push((*k, *character_data));
             }
         }
+        // Keep the order stable so that random selections can be reproduced.
+        res.sort_by_key(|(id, _)| *id);
         res
     }
 
+    pub fn data(&self, id: &CharacterId) -> Option<CharacterData> {
+        if let Some((data, _)) = self.characters.get(id) {
+            Some(*data)
+        } else {
+            None
+        }
+    }
+
     pub fn prefab(&self, id: &CharacterId) -> Option<Handle<Prefab<CharacterPrefabData>>> {
         if let Some((_, prefab)) = self.characters.get(id) {
             prefab.clone()
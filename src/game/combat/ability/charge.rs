@@ -40,7 +40,7 @@ use crate::{
 use crate::core::{activity::ActivityState, get_root};
 use crate::game::character::{CharacterPrefabData, CharacterStore, UnassignedCharacter, CharacterRole};
 use crate::game::combat::{Team};
-use crate::game::combat::ability::{AbilityData, AbilityPerform, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, ChargeEvent, UnassignedAbility, AbilityCharge, AbilityTarget, AbilityList, AbilityUsability};
+use crate::game::combat::ability::{AbilityData, AbilityPerform, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, ChargeEvent, UnassignedAbility, AbilityCharge, AbilityTarget, AbilityList, AbilityUsability, AbilityId};
 use crate::game::combat::spawn::SpawnAction;
 use crate::game::ui::marker::MarkerUiCompletedEvent;
 use crate::game::ui::select_character::{CharacterSelectedEvent, SelectCharacterEvent};
@@ -54,15 +54,19 @@ pub struct ChargeAbility;
 pub struct ChargePerform;
 
 impl ChargeAbility {
+    pub const ID: AbilityId = AbilityId("charge");
+
     pub fn data() -> AbilityData {
         AbilityData {
             name: "Charge",
             desc: "Charges the drone.",
-            id: TypeId::of::<Self>(),
+            id: Self::ID,
             system: TypeId::of::<ChargeAbilitySystem>(),
             charge: AbilityCharge::Static(-40.0),
             target_info: None,
-            cooldown: 4,
+            // Charging is the fallback when a drone has no charge left, so it never goes on cooldown.
+            cooldown: 0,
+            scaling: None,
         }
     }
 }
@@ -113,13 +117,14 @@ impl<'s> System<'s> for ChargeAbilitySystem {
                     AiAbilitySelection {
                         score,
                         target: AbilityTarget::Single(character_ent),
+                        charge: None,
                     }
                 );
             }
         }
 
         for (entity, mut ability, _) in (&entities, &mut abilities, unassigned_abilities.mask().clone()).join() {
-            if ability.data.id == TypeId::of::<ChargeAbility>() {
+            if ability.data.id == ChargeAbility::ID {
                 if let Some((character, _)) = get_root::<Character, _, _>(&parents, &characters, entity) {
                     ability.data.charge = AbilityCharge::Static(-character.artificial_charge());
                 }
@@ -162,9 +167,7 @@ impl<'s> System<'s> for ChargeAbilitySystem {
                         }
                     );
                     charge_performs.insert(ent, ChargePerform);
-                    performs.insert(ent, AbilityPerform{
-                        target: AbilityTarget::Multi(Vec::new()),
-                    });
+                    performs.insert(ent, AbilityPerform::new(AbilityTarget::Multi(Vec::new())));
                     continue;
                 } else {
                     panic!("[ChargeAbilitySystem] Unexpected failure to take turn.");
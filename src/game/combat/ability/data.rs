@@ -0,0 +1,460 @@
+use std::any::TypeId;
+use std::fs;
+use std::path::Path;
+
+use amethyst::{
+    assets::{
+        Format,
+        RonFormat,
+    },
+    core::{
+        Parent,
+        Time,
+    },
+    ecs::prelude::*,
+    log::{error, warn},
+    prelude::SystemDesc,
+    shrev::EventChannel,
+    utils::application_root_dir,
+};
+
+use crate::core::{
+    get_root,
+    select_rng,
+    CombatRng,
+};
+use crate::game::character::Character;
+use crate::game::combat::{CombatRoot, Team};
+use crate::game::combat::ability::{
+    Ability,
+    AbilityActionDesc,
+    AbilityCharge,
+    AbilityData,
+    AbilityId,
+    AbilityList,
+    AbilityPerform,
+    AbilityProgression,
+    AbilityTarget,
+    AbilityTargetArea,
+    AbilityTargetInfo,
+    AbilityUsability,
+    ChargeEvent,
+    ChargeScaling,
+    FireBulletEvent,
+    FireDesc,
+    FireLaserEvent,
+    FireTorpedoEvent,
+    FireType,
+    FireWaveEvent,
+    HealEvent,
+    StatusInflictDesc,
+    UnassignedAbility,
+};
+use crate::game::combat::process::Principal;
+use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::tactical::{AiAbilitySelection, AiAbilitySelectionQuery};
+use crate::game::combat::status::{StatusInflictedEvent, StatusList};
+
+/// The directory (relative to the application root) which contains the ability definitions.
+pub const ABILITY_DIR: &str = "assets/abilities";
+
+/// An ability definition, as it is stored in `assets/abilities/<id>.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbilityDef {
+    pub id: AbilityId,
+    pub name: String,
+    pub desc: String,
+    pub charge: AbilityCharge,
+    pub cooldown: i32,
+    pub target_info: AbilityTargetInfo,
+    pub usability: AbilityUsability,
+    pub actions: Vec<AbilityActionDesc>,
+    /// Only used by `AbilityCharge::Range` abilities.
+    #[serde(default)]
+    pub scaling: ChargeScaling,
+}
+
+impl AbilityDef {
+    /// Creates the ability data for this definition.
+    /// The strings are leaked, since abilities are only loaded once and `AbilityData` needs to stay `Copy`.
+    pub fn data(&self) -> AbilityData {
+        AbilityData {
+            name: Box::leak(self.name.clone().into_boxed_str()),
+            desc: Box::leak(self.desc.clone().into_boxed_str()),
+            id: self.id,
+            system: TypeId::of::<DataAbilitySystem>(),
+            charge: self.charge,
+            target_info: Some(self.target_info),
+            cooldown: self.cooldown,
+            scaling: Some(self.scaling),
+        }
+    }
+
+    /// Checks that the `DataAbilitySystem` can perform every action of this definition.
+    pub fn validate(&self) -> Result<(), AbilityDefError> {
+        for action in self.actions.iter() {
+            if let AbilityActionDesc::Fire(fire_desc, _) = action {
+                if matches!(fire_desc.ty, FireType::Missile | FireType::Wave(..)) {
+                    return Err(AbilityDefError::UnsupportedFireType(fire_desc.ty.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum AbilityDefError {
+    #[error(display = "fire type {:?} is not supported by data abilities", _0)]
+    UnsupportedFireType(FireType),
+}
+
+/// Loads every ability definition in the specified directory, sorted by id.
+/// Files which fail to load, or whose actions can't be performed, are skipped.
+pub fn load_abilities(dir: impl AsRef<Path>) -> Vec<AbilityDef> {
+    let mut defs: Vec<AbilityDef> = Vec::new();
+    match fs::read_dir(dir.as_ref()) {
+        Ok(entries) => {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.extension().map_or(false, |ext| ext == "ron") {
+                    match fs::read(&path) {
+                        Ok(bytes) => {
+                            match Format::<AbilityDef>::import_simple(&RonFormat, bytes) {
+                                Ok(def) => match def.validate() {
+                                    Ok(()) => defs.push(def),
+                                    Err(err) => warn!("[load_abilities] Skipped {:?}: {}", path, err),
+                                },
+                                Err(err) => warn!("[load_abilities] Failed to parse {:?}: {:?}", path, err),
+                            }
+                        },
+                        Err(err) => warn!("[load_abilities] Failed to read {:?}: {:?}", path, err),
+                    }
+                }
+            }
+        },
+        Err(err) => warn!("[load_abilities] Failed to read ability directory {:?}: {:?}", dir.as_ref(), err),
+    }
+    defs.sort_by_key(|def| def.id);
+    defs
+}
+
+/// Marks an ability which is performed by the `DataAbilitySystem`.
+#[derive(Debug, Copy, Clone, Default, Component)]
+pub struct DataAbility;
+
+/// Performs every ability which was loaded from a data file, using the actions in its definition.
+#[derive(Debug, Copy, Clone, Default, new, SystemDesc)]
+#[system_desc(name(DataAbilitySystemDesc))]
+pub struct DataAbilitySystem;
+
+impl<'s> System<'s> for DataAbilitySystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Principal>,
+        WriteStorage<'s, CombatRoot>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, SlotManager>,
+        WriteStorage<'s, Parent>,
+        WriteStorage<'s, Character>,
+        ReadStorage<'s, Ability>,
+        WriteStorage<'s, AbilityPerform>,
+        WriteStorage<'s, AbilityProgression>,
+        WriteStorage<'s, AiAbilitySelectionQuery>,
+        WriteStorage<'s, UnassignedAbility>,
+        WriteStorage<'s, DataAbility>,
+        Read<'s, AbilityList>,
+        Read<'s, Time>,
+        Write<'s, EventChannel<FireBulletEvent>>,
+        Write<'s, EventChannel<FireTorpedoEvent>>,
+        Write<'s, EventChannel<FireLaserEvent>>,
+        Write<'s, EventChannel<FireWaveEvent>>,
+        Write<'s, EventChannel<HealEvent>>,
+        Write<'s, EventChannel<ChargeEvent>>,
+        Write<'s, crate::core::CombatRng>,
+        Read<'s, StatusList>,
+        Write<'s, EventChannel<StatusInflictedEvent>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        let defs: Vec<AbilityDef> = match application_root_dir() {
+            Ok(app_root) => load_abilities(app_root.join(ABILITY_DIR)),
+            Err(err) => {
+                error!("[DataAbilitySystem] Failed to find the application root: {:?}", err);
+                Vec::new()
+            },
+        };
+        let mut ability_list = world.fetch_mut::<AbilityList>();
+        for def in defs {
+            ability_list.register_def(def);
+        }
+    }
+    fn run(&mut self, (entities, mut principals, combat_roots, teams, slot_managers, mut parents, mut characters, abilities, mut performs, mut progressions, mut ability_selections, mut unassigned, mut ability_components, ability_list, time, mut fire_bullet_events, mut fire_torpedo_events, mut fire_laser_events, mut fire_wave_events, mut heal_events, mut charge_events, mut rng, status_list, mut status_events): Self::SystemData) {
+        for (entity, ability, _, mut ability_selection) in (&entities, &abilities, ability_components.mask(), &mut ability_selections).join() {
+            // `AbilityDef` requires target info, so definitions without it are rejected when they are loaded.
+            let target_info: AbilityTargetInfo = match ability.data.target_info {
+                Some(target_info) => target_info,
+                None => continue,
+            };
+            if ability_selection.result.is_none() {
+                let target: AbilityTarget = {
+                    if target_info.area == AbilityTargetArea::Single {
+                        let mut chances: Vec<f32> = Vec::new();
+                        let mut targets: Vec<Entity> = Vec::new();
+                        for target in ability_selection.targets.iter() {
+                            if let Some(character) = characters.get(*target) {
+                                chances.push(2.0 - character.relative_health());
+                                targets.push(*target);
+                            }
+                        }
+                        AbilityTarget::Single(targets[select_rng(&mut rng, &chances).expect("Failed to get target for ability!")])
+                    } else if target_info.area == AbilityTargetArea::Flexible {
+                        let mut chances: Vec<f32> = Vec::new();
+                        let mut targets: Vec<Entity> = Vec::new();
+                        let mut multi: bool = false;
+                        for target in ability_selection.targets.iter() {
+                            if let Some(character) = characters.get(*target) {
+                                if (character.health() < 10.0) {
+                                    multi = true;
+                                } else {
+                                    chances.push(2.0 - character.relative_health());
+                                    targets.push(*target);
+                                }
+                            }
+                        }
+                        if multi {
+                            AbilityTarget::Multi(ability_selection.targets.clone())
+                        } else {
+                            AbilityTarget::Single(targets[select_rng(&mut rng, &chances).expect("Failed to get target for ability!")])
+                        }
+                    } else {
+                        AbilityTarget::Multi(ability_selection.targets.clone())
+                    }
+                };
+                // The higher the charge, the greater the likelihood.
+                let score: f32 = (ability.data.charge.rated_charge() * 0.3).powf(0.3);
+                ability_selection.result = Some(
+                    AiAbilitySelection {
+                        score,
+                        target,
+                        charge: None,
+                    }
+                );
+            }
+        }
+        for (entity, ability, _) in (&entities, &abilities, unassigned.mask().clone()).join() {
+            if ability.data.system == TypeId::of::<DataAbilitySystem>() {
+                ability_components.insert(entity, DataAbility::default());
+                unassigned.remove(entity);
+            }
+        }
+
+        let mut failed: Vec<Entity> = Vec::new();
+        for (entity, ability, _, perform, _) in (&entities, &abilities, &ability_components, &performs, !progressions.mask().clone()).join() {
+            if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, entity) {
+                let charge: f32 = perform.charge(&ability.data.charge);
+                if Character::try_take_turn(&mut characters, character_ent, charge) {
+                    let mut progression: AbilityProgression = AbilityProgression::new();
+                    progression.scaling = ability.data.scaling
+                        .map_or_else(ChargeScaling::default, |scaling| scaling.scaled(ability.data.charge.investment(charge)));
+                    progressions.insert(entity, progression);
+                } else {
+                    warn!("[DataAbilitySystem] `{}` could not take a turn, so it is skipped.", ability.data.id.0);
+                    failed.push(entity);
+                }
+            }
+        }
+        for entity in failed {
+            Principal::try_root_disengage(&parents, &mut principals, entity, TypeId::of::<Self>());
+            performs.remove(entity);
+        }
+
+        let mut to_remove: Vec<Entity> = Vec::new();
+        for (entity, ability, _, perform, mut progression) in (&entities, &abilities, &ability_components, &performs, &mut progressions).join() {
+            progression.time += time.delta_seconds();
+            let ability_actions: &[AbilityActionDesc] = ability_list.actions(&ability.data.id).unwrap_or(&[]);
+            if let Some((character, character_ent)) = get_root::<Character, _, _>(&parents, &characters, entity) {
+                loop {
+                    if progression.stage < ability_actions.len() {
+                        let desc: AbilityActionDesc = ability_actions[progression.stage].clone();
+                        match desc {
+                            AbilityActionDesc::Fire(fire_desc, weapon_idx) => {
+                                let fire_desc: FireDesc = progression.scaling.fire(&fire_desc);
+                                match fire_desc.ty {
+                                    FireType::Bullet => {
+                                        fire_bullet_events.single_write(
+                                            FireBulletEvent {
+                                                source: character_ent,
+                                                weapon_idx,
+                                                target: perform.target.select_uniform(progression.fire_count),
+                                                accuracy: fire_desc.accuracy,
+                                                effect: fire_desc.effect,
+                                                hit: fire_desc.hit,
+                                                power: fire_desc.power,
+                                            }
+                                        );
+                                    },
+                                    FireType::Torpedo(element) => {
+                                        fire_torpedo_events.single_write(
+                                            FireTorpedoEvent {
+                                                source: character_ent,
+                                                weapon_idx,
+                                                target: perform.target.select_uniform(progression.fire_count),
+                                                accuracy: fire_desc.accuracy,
+                                                effect: fire_desc.effect,
+                                                hit: fire_desc.hit,
+                                                power: fire_desc.power,
+                                                element,
+                                            }
+                                        );
+                                    },
+                                    FireType::Laser(element, time, should_multiply) => {
+                                        let target_ent: Entity = perform.target.select_uniform(progression.fire_count);
+                                        let scale: f32 = {
+                                            if should_multiply {
+                                                if let Some((slot_manager, _)) = get_root::<SlotManager, _, _>(&parents, &slot_managers, target_ent) {
+                                                    if let Some((team, _)) = get_root::<Team, _, _>(&parents, &teams, target_ent) {
+                                                        (slot_manager.for_team(*team).count() as f32)
+                                                    } else {
+                                                        1.0
+                                                    }
+                                                } else {
+                                                    1.0
+                                                }
+
+                                            } else {
+                                                1.0
+                                            }
+                                        };
+                                        fire_laser_events.single_write(
+                                            FireLaserEvent {
+                                                source: character_ent,
+                                                weapon_idx,
+                                                target: target_ent,
+                                                time,
+                                                accuracy: fire_desc.accuracy,
+                                                effect: fire_desc.effect,
+                                                hit: fire_desc.hit,
+                                                power: fire_desc.power,
+                                                scale,
+                                                element,
+                                            }
+                                        );
+                                    },
+                                    // Rejected by `load_abilities`.
+                                    FireType::Missile | FireType::Wave(..) => {},
+                                }
+                                progression.fire_count += 1;
+                                progression.stage += 1;
+                            },
+                            AbilityActionDesc::Wave(weapon_idx, time, dmg) => {
+                                fire_wave_events.single_write(
+                                    FireWaveEvent {
+                                        source: character_ent,
+                                        weapon_idx,
+                                        targets: perform.target.to_vec(),
+                                        time,
+                                        dmg: dmg.map(|dmg| progression.scaling.wave(&dmg)),
+                                    }
+                                );
+                                progression.stage += 1;
+                            },
+                            AbilityActionDesc::InflictStatus(inflict) => {
+                                let inflict: StatusInflictDesc = progression.scaling.status(inflict);
+                                for target_ent in perform.target.to_vec() {
+                                    Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, Some(character_ent), target_ent, inflict);
+                                }
+                                progression.stage += 1;
+                            },
+                            AbilityActionDesc::InflictStatusFlexible(inflict, targeted) => {
+                                let mut inflict: StatusInflictDesc = progression.scaling.status(inflict);
+                                match perform.target.clone() {
+                                    AbilityTarget::Single(target_ent) => {
+                                        inflict.turns = targeted;
+                                        Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, Some(character_ent), target_ent, inflict);
+                                    },
+                                    AbilityTarget::Multi(targets) => {
+                                        for target_ent in targets {
+                                            Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, Some(character_ent), target_ent, inflict);
+                                        }
+                                    },
+                                };
+                                progression.stage += 1;
+                            }
+                            AbilityActionDesc::Heal(heal_value, target_ty) => {
+                                let targets = perform.target.to_vec();
+                                for target in targets.iter() {
+                                    let mut invoke: bool = false;
+                                    if let Some(ty) = target_ty {
+                                        if let Some((team, _)) = Team::get_team(&parents, &teams, *target) {
+                                            if team.is_target_for(&ty) {
+                                                invoke = true;
+                                            }
+                                        }
+                                    } else {
+                                        invoke = true;
+                                    }
+                                    if invoke {
+                                        heal_events.single_write(
+                                            HealEvent {
+                                                owner: Some(entity),
+                                                source: Some(character_ent),
+                                                target: *target,
+                                                heal_value: heal_value * progression.scaling.power / (targets.len() as f32),
+                                            }
+                                        );
+                                    }
+                                }
+                                progression.stage += 1;
+                            },
+                            AbilityActionDesc::Charge(charge_value, target_ty) => {
+                                let targets = perform.target.to_vec();
+                                for target in targets.iter() {
+                                    let mut invoke: bool = false;
+                                    if let Some(ty) = target_ty {
+                                        if let Some((team, _)) = Team::get_team(&parents, &teams, *target) {
+                                            if team.is_target_for(&ty) {
+                                                invoke = true;
+                                            }
+                                        }
+                                    } else {
+                                        invoke = true;
+                                    }
+                                    if invoke {
+                                        charge_events.single_write(
+                                            ChargeEvent {
+                                                owner: Some(entity),
+                                                source: Some(character_ent),
+                                                target: *target,
+                                                charge_value: charge_value * progression.scaling.power / (targets.len() as f32),
+                                            }
+                                        );
+                                    }
+                                }
+                                progression.stage += 1;
+                            },
+                            AbilityActionDesc::Wait(wait_time) => {
+                                if progression.stage_time >= wait_time {
+                                    progression.stage_time = 0.0;
+                                    progression.stage += 1;
+                                } else {
+                                    progression.stage_time += time.delta_seconds();
+                                    break;
+                                }
+                            },
+                        }
+                    } else {
+                        to_remove.push(entity);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for entity in to_remove {
+            Principal::try_root_disengage(&parents, &mut principals, entity, TypeId::of::<Self>());
+            performs.remove(entity);
+            progressions.remove(entity);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use amethyst::{
+    core::Parent,
+    ecs::{
+        prelude::*,
+        storage::GenericReadStorage,
+    },
+    prelude::SystemDesc,
+    shrev::EventChannel,
+};
+use std::any::TypeId;
+
+use crate::core::{CombatRng, get_root, roll};
+use crate::game::character::{Character, CharacterRole};
+use crate::game::combat::{CombatRoot, Team};
+use crate::game::combat::ability::{Ability, AbilityCharge, AbilityData, AbilityId, AbilityInvoke, AbilityList, AbilityPerform, AbilityUsability, UnassignedAbility};
+use crate::game::combat::process::Principal;
+use crate::game::combat::spawn::{SlotManager, Slots};
+
+/// The chance of escaping from a single basic enemy.
+pub const FLEE_BASE_CHANCE: f32 = 0.9;
+pub const FLEE_MIN_CHANCE: f32 = 0.05;
+pub const FLEE_MAX_CHANCE: f32 = 0.95;
+
+/// Sent whenever a character attempts to flee from the combat.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FleeEvent {
+    pub character_ent: Entity,
+    pub chance: f32,
+    pub escaped: bool,
+}
+
+#[derive(Debug, Copy, Clone, Default, Component)]
+pub struct FleeAbility;
+
+impl FleeAbility {
+    pub const ID: AbilityId = AbilityId("flee");
+
+    pub fn data() -> AbilityData {
+        AbilityData {
+            name: "Flee",
+            desc: "Attempts to escape from the combat. The fewer and weaker the enemies, the likelier the escape. A failed attempt wastes the turn.",
+            id: Self::ID,
+            system: TypeId::of::<FleeAbilitySystem>(),
+            charge: AbilityCharge::Static(0.0),
+            target_info: None,
+            cooldown: 0,
+            scaling: None,
+        }
+    }
+}
+
+/// The chance of escaping from the characters in the slots, which falls with their number and rank.
+pub fn flee_chance(slots: &Slots, characters: &impl GenericReadStorage<Component=Character>) -> f32 {
+    let threat: f32 = slots.occupied()
+        .iter()
+        .filter_map(|entity_opt| entity_opt.and_then(|character_ent| characters.get(character_ent)))
+        .map(|character| character.rank().base_multiplier())
+        .sum();
+    if threat <= 0.0 {
+        FLEE_MAX_CHANCE
+    } else {
+        (FLEE_BASE_CHANCE / threat).max(FLEE_MIN_CHANCE).min(FLEE_MAX_CHANCE)
+    }
+}
+
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(FleeAbilitySystemDesc))]
+pub struct FleeAbilitySystem;
+
+impl<'s> System<'s> for FleeAbilitySystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Principal>,
+        WriteStorage<'s, Character>,
+        WriteStorage<'s, Ability>,
+        WriteStorage<'s, AbilityPerform>,
+        WriteStorage<'s, UnassignedAbility>,
+        WriteStorage<'s, AbilityInvoke>,
+        WriteStorage<'s, FleeAbility>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, SlotManager>,
+        ReadStorage<'s, CombatRoot>,
+        Write<'s, CombatRng>,
+        Write<'s, EventChannel<FleeEvent>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        world.fetch_mut::<AbilityList>().register(FleeAbility::data(), AbilityUsability::Role(CharacterRole::Master));
+    }
+
+    fn run(&mut self, (entities, mut principals, mut characters, mut abilities, mut performs, mut unassigned_abilities, mut ability_invokes, mut flee_abilities, parents, teams, slot_managers, roots, mut rng, mut flee_events): Self::SystemData) {
+        for (entity, ability, _) in (&entities, &abilities, unassigned_abilities.mask().clone()).join() {
+            if ability.data.id == FleeAbility::ID {
+                flee_abilities.insert(entity, FleeAbility::default());
+                unassigned_abilities.remove(entity);
+            }
+        }
+
+        // Only the player can flee, and not from every wave.
+        for (entity, mut ability, _) in (&entities, &mut abilities, flee_abilities.mask()).join() {
+            let friendly: bool = Team::get_team(&parents, &teams, entity).map_or(false, |(team, _)| team == Team::Friendly);
+            let allowed: bool = get_root::<CombatRoot, _, _>(&parents, &roots, entity).map_or(false, |(root, _)| root.can_flee());
+            ability.locked = !(friendly && allowed);
+        }
+
+        // Check if the ability has been triggered.
+        for (ent, _, _) in (&entities, performs.mask().clone() | ability_invokes.mask().clone(), flee_abilities.mask()).join() {
+            if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, ent) {
+                if Character::try_take_turn(&mut characters, character_ent, 0.0) {
+                    if let Some((team, _)) = Team::get_team(&parents, &teams, character_ent) {
+                        if let Some((slot_manager, _)) = get_root::<SlotManager, _, _>(&parents, &slot_managers, character_ent) {
+                            let chance: f32 = flee_chance(slot_manager.for_team(team.other()), &characters);
+                            let escaped: bool = roll(&mut rng, chance);
+                            flee_events.single_write(FleeEvent {
+                                character_ent,
+                                chance,
+                                escaped,
+                            });
+                        }
+                    }
+                    performs.remove(ent);
+                    ability_invokes.remove(ent);
+                } else {
+                    panic!("[FleeAbilitySystem] Unexpected failure to take turn.");
+                }
+            }
+            Principal::try_root_disengage(&parents, &mut principals, ent, std::any::TypeId::of::<Self>());
+        }
+    }
+}
@@ -38,7 +38,8 @@ use crate::{
 use crate::core::{activity::ActivityState, get_root};
 use crate::game::character::{CharacterPrefabData, CharacterStore, UnassignedCharacter, CharacterRole};
 use crate::game::combat::{Team};
-use crate::game::combat::ability::{AbilityCharge, AbilityData, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityPerform, AbilityTarget, AbilityList, AbilityUsability};
+use crate::game::combat::ability::{AbilityCharge, AbilityData, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityPerform, AbilityTarget, AbilityList, AbilityUsability, AbilityId};
+use crate::game::combat::difficulty::DifficultyProfile;
 use crate::game::combat::spawn::SpawnAction;
 use crate::game::ui::select_character::{CharacterSelectedEvent, SelectCharacterEvent};
 use crate::game::ui::hack::{ShowHackUiEvent, HackSelectedEvent, UiHackOption};
@@ -52,11 +53,13 @@ pub struct HackAbility {
 }
 
 impl HackAbility {
+    pub const ID: AbilityId = AbilityId("hack");
+
     pub fn data() -> AbilityData {
         AbilityData {
             name: "Hack",
             desc: "Attempts to hack an enemy drone, resulting in all of the hacked type joining our team.",
-            id: TypeId::of::<Self>(),
+            id: Self::ID,
             system: TypeId::of::<HackAbilitySystem>(),
             charge: AbilityCharge::Range(100.0, 700.0),
             target_info: Some(AbilityTargetInfo {
@@ -64,6 +67,7 @@ impl HackAbility {
                 area: AbilityTargetArea::Single,
             }),
             cooldown: 4,
+            scaling: None,
         }
     }
 }
@@ -104,16 +108,17 @@ impl<'s> System<'s> for HackAbilitySystem {
         WriteStorage<'s, SpawnAction>,
         Read<'s, EventChannel<HackSelectedEvent>>,
         Write<'s, EventChannel<HackPerformedEvent>>,
+        Read<'s, DifficultyProfile>,
     );
 
     fn setup(&mut self, world: &mut World) {
         world.fetch_mut::<AbilityList>().register(HackAbility::data(), AbilityUsability::Role(CharacterRole::Master));
     }
 
-    fn run(&mut self, (entities, mut principals, mut slot_managers, mut characters, mut unassigned_characters, mut character_prefabs, mut spawn_processes, mut abilities, mut unassigned_abilities, mut ability_invokes, mut performs, mut hack_abilities, mut parents, combat_roots, mut transforms, teams, character_store, hierarchy, mut select_hack_events, mut spawn_actions, hack_selected_event, mut hack_performed_event): Self::SystemData) {
+    fn run(&mut self, (entities, mut principals, mut slot_managers, mut characters, mut unassigned_characters, mut character_prefabs, mut spawn_processes, mut abilities, mut unassigned_abilities, mut ability_invokes, mut performs, mut hack_abilities, mut parents, combat_roots, mut transforms, teams, character_store, hierarchy, mut select_hack_events, mut spawn_actions, hack_selected_event, mut hack_performed_event, difficulty): Self::SystemData) {
         let mut to_remove: Vec<Entity> = Vec::new();
         for (entity, ability, _) in (&entities, &abilities, &unassigned_abilities).join() {
-            if ability.data.id == TypeId::of::<HackAbility>() {
+            if ability.data.id == HackAbility::ID {
                 hack_abilities.insert(entity, HackAbility::default());
                 to_remove.push(entity);
             }
@@ -144,7 +149,7 @@ impl<'s> System<'s> for HackAbilitySystem {
                         if exponent < 0.1 {
                             exponent = 0.1;
                         }
-                        let chance: f32 = BASE_CHANCE.powf(exponent);
+                        let chance: f32 = (BASE_CHANCE * difficulty.hack_chance).min(1.0).powf(exponent);
                         let mut n: i32 = (1.0 / chance) as i32;
                         if n > MAX_OPTIONS {
                             n = MAX_OPTIONS;
@@ -6,13 +6,19 @@ use amethyst::{
     ecs::{
         prelude::*,
         storage::{
+            ComponentEvent,
             GenericReadStorage,
         }
     },
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
 };
 use rand::Rng;
 
 use crate::core::{
+    CombatRng,
     get_root,
     select_rng,
 };
@@ -21,360 +27,31 @@ use crate::game::character::{
     CharacterId,
     CharacterRole,
 };
-use crate::game::combat::{CombatRoot, Team};
+use crate::game::combat::{CombatRoot, Team, TickTurn};
 use crate::game::combat::process::Principal;
 use crate::game::combat::status::StatusType;
 use crate::game::combat::tactical::AiAbilitySelection;
 use crate::game::combat::tactical::AiAbilitySelectionQuery;
 use std::ops::Range;
 use crate::game::combat::spawn::SlotManager;
-
-macro_rules! define_ability {
-    (
-        $C:ident,
-        $S:ident,
-        $SD:ident,
-        $name:literal,
-        $description:literal,
-        $charge:literal,
-        $cooldown:literal,
-        $target_ty:expr,
-        $target_area:expr,
-        $usability:expr,
-        [ $( $desc:expr ),* ]
-    ) => {
-        #[derive(Debug, Copy, Clone, Default, Component)]
-        pub struct $C;
-
-        #[derive(Debug, Copy, Clone, Default, new, SystemDesc)]
-        #[system_desc(name($SD))]
-        pub struct $S;
-
-        impl $C {
-            pub fn data() -> AbilityData {
-                AbilityData {
-                    name: $name,
-                    desc: $description,
-                    id: TypeId::of::<Self>(),
-                    system: TypeId::of::<$S>(),
-                    charge: AbilityCharge::Static($charge),
-                    target_info: Some(AbilityTargetInfo {
-                        ty: $target_ty,
-                        area: $target_area,
-                    }),
-                    cooldown: $cooldown,
-                }
-            }
-        }
-
-        impl<'s> System<'s> for $S {
-            type SystemData = (
-                Entities<'s>,
-                WriteStorage<'s, Principal>,
-                WriteStorage<'s, CombatRoot>,
-                ReadStorage<'s, Team>,
-                ReadStorage<'s, SlotManager>,
-                WriteStorage<'s, amethyst::core::Parent>,
-                WriteStorage<'s, Character>,
-                ReadStorage<'s, Ability>,
-                WriteStorage<'s, AbilityPerform>,
-                WriteStorage<'s, AbilityProgression>,
-                WriteStorage<'s, AiAbilitySelectionQuery>,
-                WriteStorage<'s, UnassignedAbility>,
-                WriteStorage<'s, $C>,
-                Read<'s, amethyst::core::Time>,
-                Write<'s, amethyst::shrev::EventChannel<FireBulletEvent>>,
-                Write<'s, amethyst::shrev::EventChannel<FireTorpedoEvent>>,
-                Write<'s, amethyst::shrev::EventChannel<FireLaserEvent>>,
-                Write<'s, amethyst::shrev::EventChannel<FireWaveEvent>>,
-                Write<'s, amethyst::shrev::EventChannel<HealEvent>>,
-                Write<'s, amethyst::shrev::EventChannel<ChargeEvent>>,
-            );
-
-            fn setup(&mut self, world: &mut World) {
-                world.fetch_mut::<AbilityList>().register($C::data(), $usability);
-            }
-            fn run(&mut self, (entities, mut principals, combat_roots, teams, slot_managers, mut parents, mut characters, abilities, mut performs, mut progressions, mut ability_selections, mut unassigned, mut ability_components, time, mut fire_bullet_events, mut fire_torpedo_events, mut fire_laser_events, mut fire_wave_events, mut heal_events, mut charge_events): Self::SystemData) {
-                for (entity, ability, _, mut ability_selection) in (&entities, &abilities, ability_components.mask(), &mut ability_selections).join() {
-                    if ability_selection.result.is_none() {
-                        let target: AbilityTarget = {
-                            let target_info: AbilityTargetInfo = ability.data.target_info.expect("No target info for macro ability!");
-                            if target_info.area == AbilityTargetArea::Single {
-                                let mut chances: Vec<f32> = Vec::new();
-                                let mut targets: Vec<Entity> = Vec::new();
-                                for target in ability_selection.targets.iter() {
-                                    if let Some(character) = characters.get(*target) {
-                                        chances.push(2.0 - character.relative_health());
-                                        targets.push(*target);
-                                    }
-                                }
-                                AbilityTarget::Single(targets[select_rng(&chances).expect("Failed to get target for ability!")])
-                            } else if target_info.area == AbilityTargetArea::Flexible {
-                                let mut chances: Vec<f32> = Vec::new();
-                                let mut targets: Vec<Entity> = Vec::new();
-                                let mut multi: bool = false;
-                                for target in ability_selection.targets.iter() {
-                                    if let Some(character) = characters.get(*target) {
-                                        if (character.health() < 10.0) {
-                                            multi = true;
-                                        } else {
-                                            chances.push(2.0 - character.relative_health());
-                                            targets.push(*target);
-                                        }
-                                    }
-                                }
-                                if multi {
-                                    AbilityTarget::Multi(ability_selection.targets.clone())
-                                } else {
-                                    AbilityTarget::Single(targets[select_rng(&chances).expect("Failed to get target for ability!")])
-                                }
-                            } else {
-                                AbilityTarget::Multi(ability_selection.targets.clone())
-                            }
-                        };
-                        // The higher the charge, the greater the likelihood.
-                        let score: f32 = (ability.data.charge.rated_charge() * 0.3).powf(0.3);
-                        ability_selection.result = Some(
-                            AiAbilitySelection {
-                                score,
-                                target,
-                            }
-                        );
-                    }
-                }
-                for (entity, ability, _) in (&entities, &abilities, unassigned.mask().clone()).join() {
-                    if ability.data.id == TypeId::of::<$C>() {
-                        ability_components.insert(entity, $C::default());
-                        unassigned.remove(entity);
-                    }
-                }
-
-                for (entity, ability, _, _, _) in (&entities, &abilities, &ability_components, &performs, !progressions.mask().clone()).join() {
-                    if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, entity) {
-                        if Character::try_take_turn(&mut characters, character_ent, ability.data.charge.rated_charge()) {
-                            progressions.insert(entity, AbilityProgression::new());
-                        } else {
-                            panic!("[define_ability!] Unexpected failure to take turn.");
-                        }
-                    }
-                }
-
-                let mut to_remove: Vec<Entity> = Vec::new();
-                for (entity, _, perform, mut progression) in (&entities, &ability_components, &performs, &mut progressions).join() {
-                    progression.time += time.delta_seconds();
-                    let ability_actions = [$($desc,)*];
-                    if let Some((character, character_ent)) = crate::core::get_root::<Character, _, _>(&parents, &characters, entity) {
-                        loop {
-                            if progression.stage < ability_actions.len() {
-                                let desc: AbilityActionDesc = ability_actions[progression.stage].clone();
-                                match desc {
-                                    AbilityActionDesc::Fire(fire_desc, weapon_idx) => {
-                                        match fire_desc.ty {
-                                            FireType::Bullet => {
-                                                fire_bullet_events.single_write(
-                                                    FireBulletEvent {
-                                                        source: character_ent,
-                                                        weapon_idx,
-                                                        target: perform.target.select_uniform(progression.fire_count),
-                                                        accuracy: fire_desc.accuracy,
-                                                        effect: fire_desc.effect,
-                                                        power: fire_desc.power,
-                                                    }
-                                                );
-                                            },
-                                            FireType::Torpedo(element) => {
-                                                fire_torpedo_events.single_write(
-                                                    FireTorpedoEvent {
-                                                        source: character_ent,
-                                                        weapon_idx,
-                                                        target: perform.target.select_uniform(progression.fire_count),
-                                                        accuracy: fire_desc.accuracy,
-                                                        effect: fire_desc.effect,
-                                                        power: fire_desc.power,
-                                                        element,
-                                                    }
-                                                );
-                                            },
-                                            FireType::Laser(element, time, should_multiply) => {
-                                                let target_ent = perform.target.select_uniform(progression.fire_count);
-                                                let dmg_mul: f32 = {
-                                                    if should_multiply {
-                                                        if let Some((slot_manager, _)) = get_root::<SlotManager, _, _>(&parents, &slot_managers, target_ent) {
-                                                            if let Some((team, _)) = get_root::<Team, _, _>(&parents, &teams, target_ent) {
-                                                                (slot_manager.for_team(*team).count() as f32)
-                                                            } else {
-                                                                1.0
-                                                            }
-                                                        } else {
-                                                            1.0
-                                                        }
-
-                                                    } else {
-                                                        1.0
-                                                    }
-                                                };
-                                                fire_laser_events.single_write(
-                                                    FireLaserEvent {
-                                                        source: character_ent,
-                                                        weapon_idx,
-                                                        target: perform.target.select_uniform(progression.fire_count),
-                                                        time,
-                                                        accuracy: fire_desc.accuracy,
-                                                        effect: fire_desc.effect,
-                                                        power: fire_desc.power * dmg_mul,
-                                                        element,
-                                                    }
-                                                );
-                                            },
-                                            _ => {
-                                                unimplemented!();
-                                            },
-                                        }
-                                        progression.fire_count += 1;
-                                        progression.stage += 1;
-                                    },
-                                    AbilityActionDesc::Wave(weapon_idx, time, dmg) => {
-                                        fire_wave_events.single_write(
-                                            FireWaveEvent {
-                                                source: character_ent,
-                                                weapon_idx,
-                                                targets: perform.target.to_vec(),
-                                                time,
-                                                dmg,
-                                            }
-                                        );
-                                        progression.stage += 1;
-                                    },
-                                    AbilityActionDesc::InflictStatus(inflict) => {
-                                        for target_ent in perform.target.to_vec() {
-                                            Character::inflict_status_silent(&mut characters, target_ent, inflict);
-                                        }
-                                        progression.stage += 1;
-                                    },
-                                    AbilityActionDesc::InflictStatusFlexible(mut inflict, targeted) => {
-                                        match perform.target.clone() {
-                                            AbilityTarget::Single(target_ent) => {
-                                                inflict.turns = targeted;
-                                                Character::inflict_status_silent(&mut characters, target_ent, inflict);
-                                            },
-                                            AbilityTarget::Multi(targets) => {
-                                                for target_ent in targets {
-                                                    Character::inflict_status_silent(&mut characters, target_ent, inflict);
-                                                }
-                                            },
-                                        };
-                                        progression.stage += 1;
-                                    }
-                                    AbilityActionDesc::Heal(heal_value, target_ty) => {
-                                        let targets = perform.target.to_vec();
-                                        for target in targets.iter() {
-                                            let mut invoke: bool = false;
-                                            if let Some(ty) = target_ty {
-                                                if let Some((team, _)) = Team::get_team(&parents, &teams, *target) {
-                                                    if team.is_target_for(&ty) {
-                                                        invoke = true;
-                                                    }
-                                                }
-                                            } else {
-                                                invoke = true;
-                                            }
-                                            if invoke {
-                                                heal_events.single_write(
-                                                    HealEvent {
-                                                        owner: Some(entity),
-                                                        source: Some(character_ent),
-                                                        target: *target,
-                                                        heal_value: heal_value / (targets.len() as f32),
-                                                    }
-                                                );
-                                            }
-                                        }
-                                        progression.stage += 1;
-                                    },
-                                    AbilityActionDesc::Charge(charge_value, target_ty) => {
-                                        let targets = perform.target.to_vec();
-                                        for target in targets.iter() {
-                                            let mut invoke: bool = false;
-                                            if let Some(ty) = target_ty {
-                                                if let Some((team, _)) = Team::get_team(&parents, &teams, *target) {
-                                                    if team.is_target_for(&ty) {
-                                                        invoke = true;
-                                                    }
-                                                }
-                                            } else {
-                                                invoke = true;
-                                            }
-                                            if invoke {
-                                                charge_events.single_write(
-                                                    ChargeEvent {
-                                                        owner: Some(entity),
-                                                        source: Some(character_ent),
-                                                        target: *target,
-                                                        charge_value: charge_value / (targets.len() as f32),
-                                                    }
-                                                );
-                                            }
-                                        }
-                                        progression.stage += 1;
-                                    },
-                                    AbilityActionDesc::Wait(wait_time) => {
-                                        if progression.stage_time >= wait_time {
-                                            progression.stage_time = 0.0;
-                                            progression.stage += 1;
-                                        } else {
-                                            progression.stage_time += time.delta_seconds();
-                                            break;
-                                        }
-                                    },
-                                }
-                            } else {
-                                to_remove.push(entity);
-                                break;
-                            }
-                        }
-                    }
-                }
-
-                for entity in to_remove {
-                    Principal::try_root_disengage(&parents, &mut principals, entity, std::any::TypeId::of::<Self>());
-                    performs.remove(entity);
-                    progressions.remove(entity);
-                }
-            }
-        }
-    }
-}
+use crate::game::combat::ability::data::AbilityDef;
+use crate::core::registry::intern;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub mod charge;
 pub mod spawn;
 pub mod hack;
-pub mod solid_laser;
-pub mod focused_charge;
-pub mod shock;
-pub mod nanobots;
-pub mod scrambling_laser;
-pub mod overclock;
-pub mod twin_shot;
-pub mod snipe;
-pub mod barrage;
-pub mod annihilate;
-pub mod big_bullet;
-pub mod jammer;
-pub mod nuke;
-pub mod overload;
-pub mod corrupt;
-pub mod sheild;
-pub mod empower;
-pub mod focus;
-pub mod retribution;
 pub mod self_destruct;
+pub mod flee;
+pub mod data;
 //pub mod upgrade;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Element {
     Kinetic,
     Plasma,
     Ion,
+    Radiation,
     Quantum,
 }
 
@@ -385,6 +62,9 @@ pub struct DmgPackage {
     pub power: f32,
     pub element: Element,
     pub status: Option<StatusInflictDesc>,
+    /// Scales the damage of the attack itself (e.g. lasers which scale with the number of drones on the target's team).
+    pub scale: f32,
+    pub hit: HitDesc,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Component)]
@@ -420,20 +100,36 @@ pub struct ChargeEvent {
 }
 
 
-pub type AbilityId = std::any::TypeId;
+/// Identifies an ability, e.g. `"nuke"`. Abilities loaded from `assets/abilities` use the id in their file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AbilityId(pub &'static str);
+
+impl Serialize for AbilityId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for AbilityId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id: String = String::deserialize(deserializer)?;
+        Ok(AbilityId(intern(&id)))
+    }
+}
 
 /// Represents abilities associated with a drone.
 /// Each ability is an entity, who's parent is the owner.
 pub struct Abilities(Vec<Entity>);
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum AbilityUsability<'a> {
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AbilityUsability {
     Common,
     Role(CharacterRole),
-    Unique(&'a [CharacterId]),
+    /// Only usable by the characters with the specified ids.
+    Unique(Vec<CharacterId>),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AbilityCharge {
     Static(f32),
     Range(f32, f32),
@@ -446,6 +142,103 @@ impl AbilityCharge {
             AbilityCharge::Range(lower, upper) => *lower,
         }
     }
+
+    pub fn is_variable(&self) -> bool {
+        match self {
+            AbilityCharge::Static(_) => false,
+            AbilityCharge::Range(lower, upper) => upper > lower,
+        }
+    }
+
+    /// Clamps the invested charge to the range. Static abilities always cost their charge.
+    pub fn clamp(&self, charge: f32) -> f32 {
+        match self {
+            AbilityCharge::Static(val) => *val,
+            AbilityCharge::Range(lower, upper) => charge.max(*lower).min(*upper),
+        }
+    }
+
+    /// How much of the range the charge invests, from 0 (the lower bound) to 1 (the upper bound).
+    pub fn investment(&self, charge: f32) -> f32 {
+        match self {
+            AbilityCharge::Static(_) => 0.0,
+            AbilityCharge::Range(lower, upper) => {
+                if upper > lower {
+                    (self.clamp(charge) - *lower) / (*upper - *lower)
+                } else {
+                    0.0
+                }
+            },
+        }
+    }
+
+    /// Evenly spaced charges from the lower to the upper bound (e.g. for the stepped options in the ability panel).
+    pub fn steps(&self, count: usize) -> Vec<f32> {
+        match self {
+            AbilityCharge::Range(lower, upper) if self.is_variable() && count > 1 => {
+                (0..count).map(|i| *lower + (*upper - *lower) * (i as f32) / ((count - 1) as f32)).collect()
+            },
+            _ => vec![self.rated_charge()],
+        }
+    }
+}
+
+/// How investing more charge in an `AbilityCharge::Range` ability scales its effects.
+/// Each value is the multiplier at the upper bound of the range. It is interpolated from 1 at the lower bound.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChargeScaling {
+    /// Scales damage, healing and charge.
+    pub power: f32,
+    pub accuracy: f32,
+    pub status_chance: f32,
+}
+
+impl Default for ChargeScaling {
+    fn default() -> Self {
+        Self {
+            power: 1.0,
+            accuracy: 1.0,
+            status_chance: 1.0,
+        }
+    }
+}
+
+impl ChargeScaling {
+    /// The multipliers for the specified investment (see `AbilityCharge::investment`).
+    pub fn scaled(&self, investment: f32) -> Self {
+        let scale = |value: f32| 1.0 + (value - 1.0) * investment;
+        Self {
+            power: scale(self.power),
+            accuracy: scale(self.accuracy),
+            status_chance: scale(self.status_chance),
+        }
+    }
+
+    pub fn fire(&self, fire_desc: &FireDesc) -> FireDesc {
+        FireDesc {
+            power: fire_desc.power * self.power,
+            accuracy: (fire_desc.accuracy * self.accuracy).min(1.0),
+            effect: fire_desc.effect.map(|effect| self.status(effect)),
+            ..fire_desc.clone()
+        }
+    }
+
+    pub fn wave(&self, dmg: &WaveDmg) -> WaveDmg {
+        WaveDmg {
+            power: dmg.power * self.power,
+            accuracy: (dmg.accuracy * self.accuracy).min(1.0),
+            effect: dmg.effect.map(|effect| self.status(effect)),
+            ..*dmg
+        }
+    }
+
+    pub fn status(&self, effect: StatusInflictDesc) -> StatusInflictDesc {
+        StatusInflictDesc {
+            chance: (effect.chance * self.status_chance).min(1.0),
+            ..effect
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -458,23 +251,26 @@ pub struct AbilityData {
     pub desc: &'static str,
     /// This can be set to `None` for special abilities which do no take a target (e.g. spawn character).
     pub target_info: Option<AbilityTargetInfo>,
+    /// How the ability scales with the charge invested in it.
+    /// This is `None` for abilities which choose their own charge (e.g. hack), so the ability panel does not offer a choice.
+    pub scaling: Option<ChargeScaling>,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AbilityTargetType {
     Friendly,
     Enemy,
     All,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AbilityTargetArea {
     Single,
     Flexible,
     All,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AbilityTargetInfo {
     pub ty: AbilityTargetType,
     pub area: AbilityTargetArea,
@@ -487,14 +283,14 @@ pub enum AbilityTarget {
 }
 
 impl AbilityTarget {
-    pub fn select(&self) -> Entity {
+    pub fn select(&self, rng: &mut CombatRng) -> Entity {
         match self {
             AbilityTarget::Multi(entities) => {
                 debug_assert!(!entities.is_empty());
                 if entities.len() == 1 {
                     entities[0]
                 } else {
-                    entities[rand::thread_rng().gen_range(0, entities.len())]
+                    entities[rng.gen_range(0, entities.len())]
                 }
             }
             AbilityTarget::Single(ent) => *ent,
@@ -536,6 +332,8 @@ pub struct Ability {
     pub data: AbilityData,
     pub locked: bool,
     pub current_cooldown: i32,
+    /// Set when the cooldown is applied, so that the turn in which the ability was performed doesn't count towards it.
+    pub cooldown_pending: bool,
 }
 
 impl Component for Ability {
@@ -548,19 +346,34 @@ impl Ability {
             data,
             locked: false,
             current_cooldown: 0,
+            cooldown_pending: false,
         }
     }
     pub fn apply_cooldown(&mut self) {
         self.current_cooldown = self.data.cooldown;
+        self.cooldown_pending = true;
+    }
+
+    /// Counts the cooldown down at the end of one of the owner's turns.
+    pub fn end_turn(&mut self) {
+        if self.cooldown_pending {
+            self.cooldown_pending = false;
+        } else {
+            self.progress(1);
+        }
     }
 
     pub fn progress(&mut self, turns: i32) {
         self.current_cooldown -= turns;
-        if self.current_cooldown > 0 {
+        if self.current_cooldown < 0 {
             self.current_cooldown = 0;
         }
     }
 
+    pub fn is_on_cooldown(&self) -> bool {
+        self.current_cooldown > 0
+    }
+
     pub fn can_perform(&self) -> bool {
         self.current_cooldown == 0 && !self.locked
     }
@@ -611,20 +424,42 @@ impl Ability {
 pub struct Performing;
 
 #[derive(Debug, Copy, Clone, Default, Component)]
-pub struct AbilityInvoke;
+pub struct AbilityInvoke {
+    /// The charge chosen for `AbilityCharge::Range` abilities. `None` invests the lowest charge.
+    pub charge: Option<f32>,
+}
 
 pub struct AbilityPerform {
     pub target: AbilityTarget,
+    /// The charge chosen for `AbilityCharge::Range` abilities. `None` invests the lowest charge.
+    pub charge: Option<f32>,
 }
 
 impl Component for AbilityPerform {
-    type Storage = DenseVecStorage<Self>;
+    /// Flagged so that the cooldown is applied however the ability was performed.
+    type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
 }
 
 impl AbilityPerform {
     pub fn new(target: AbilityTarget) -> Self {
         Self {
             target,
+            charge: None,
+        }
+    }
+
+    pub fn with_charge(target: AbilityTarget, charge: Option<f32>) -> Self {
+        Self {
+            target,
+            charge,
+        }
+    }
+
+    /// The charge which is actually spent on the ability.
+    pub fn charge(&self, ability_charge: &AbilityCharge) -> f32 {
+        match self.charge {
+            Some(charge) => ability_charge.clamp(charge),
+            None => ability_charge.rated_charge(),
         }
     }
 }
@@ -676,9 +511,12 @@ impl<'a, 'b, 's> AbilityAggregator<'a, 'b, 's> {
         if let Some(ability) = self.get_ability(ability_ent) {
             if ability.can_perform() || force_perform {
                 self.ability_performs.insert(ability_ent, AbilityPerform::new(target));
+                Ok(())
+            } else if ability.locked {
+                Err(AbilityPerformError::Locked)
+            } else {
+                Err(AbilityPerformError::InCooldown)
             }
-
-            Ok(())
         } else {
             Err(AbilityPerformError::InvalidEntity)
         }
@@ -692,6 +530,8 @@ pub struct AbilityList {
     common: Vec<AbilityId>,
     role_specific: HashMap<CharacterRole, Vec<AbilityId>>,
     character_specific: HashMap<CharacterId, Vec<AbilityId>>,
+    /// The actions performed by abilities which were loaded from data files.
+    actions: HashMap<AbilityId, Vec<AbilityActionDesc>>,
 }
 
 impl AbilityList {
@@ -708,10 +548,10 @@ impl AbilityList {
             }
             AbilityUsability::Unique(character_ids) => {
                 for character_id in character_ids {
-                    if let Some(abilities) = self.character_specific.get_mut(character_id) {
+                    if let Some(abilities) = self.character_specific.get_mut(&character_id) {
                         abilities.push(ability.id);
                     } else {
-                        self.character_specific.insert(*character_id, vec![ability.id]);
+                        self.character_specific.insert(character_id, vec![ability.id]);
                     }
                 }
             }
@@ -721,11 +561,23 @@ impl AbilityList {
         }
     }
 
+    /// Registers an ability which was loaded from a data file, along with its actions.
+    pub fn register_def(&mut self, def: AbilityDef) {
+        let data: AbilityData = def.data();
+        self.actions.insert(data.id, def.actions);
+        self.register(data, def.usability);
+    }
+
     #[inline]
     pub fn ability(&self, id: &AbilityId) -> Option<AbilityData> {
         self.abilities.get(id).copied()
     }
 
+    #[inline]
+    pub fn actions(&self, id: &AbilityId) -> Option<&[AbilityActionDesc]> {
+        self.actions.get(id).map(|actions| actions.as_slice())
+    }
+
     pub fn abilities_for(&self, character: &Character) -> HashMap<AbilityId, AbilityData> {
         let mut res: HashMap<AbilityId, AbilityData> = HashMap::new();
 
@@ -769,7 +621,7 @@ impl UnassignedAbility {
 /// The 'base' function to invoke an ability.
 pub fn perform_ability<'s>(
     parents: &ReadStorage<'s, Parent>, principals: &mut WriteStorage<'s, Principal>, abilities: &ReadStorage<'s, Ability>, ability_performs: &mut WriteStorage<'s, AbilityPerform>,
-    ability_ent: Entity, target: AbilityTarget,
+    ability_ent: Entity, target: AbilityTarget, charge: Option<f32>,
 ) -> Option<bool> {
     if !ability_performs.contains(ability_ent) {
         if let Some(ability) = abilities.get(ability_ent) {
@@ -778,7 +630,7 @@ pub fn perform_ability<'s>(
                 ability_ent, ability.data.system,
             );
             if engage_result == Some(true) {
-                ability_performs.insert(ability_ent, AbilityPerform::new(target));
+                ability_performs.insert(ability_ent, AbilityPerform::with_charge(target, charge));
                 Some(true)
             } else {
                 Some(false)
@@ -797,6 +649,8 @@ pub struct AbilityProgression {
     pub stage_time: f32,
     pub stage: usize,
     pub fire_count: usize,
+    /// The scaling for the charge which was invested in the ability.
+    pub scaling: ChargeScaling,
 }
 
 impl AbilityProgression {
@@ -805,12 +659,14 @@ impl AbilityProgression {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FireDesc {
     pub ty: FireType,
     pub power: f32,
     pub accuracy: f32,
     pub effect: Option<StatusInflictDesc>,
+    #[serde(default)]
+    pub hit: HitDesc,
 }
 
 impl FireDesc {
@@ -820,6 +676,7 @@ impl FireDesc {
             power,
             accuracy,
             effect: None,
+            hit: HitDesc::exact(),
         }
     }
     pub const fn missile(power: f32, accuracy: f32) -> Self {
@@ -828,6 +685,7 @@ impl FireDesc {
             power,
             accuracy,
             effect: None,
+            hit: HitDesc::exact(),
         }
     }
     pub const fn torpedo(power: f32, element: Element, accuracy: f32) -> Self {
@@ -836,6 +694,7 @@ impl FireDesc {
             power,
             accuracy,
             effect: None,
+            hit: HitDesc::exact(),
         }
     }
     pub const fn laser(power: f32, element: Element, accuracy: f32, time: f32) -> Self {
@@ -844,11 +703,12 @@ impl FireDesc {
             power,
             accuracy,
             effect: None,
+            hit: HitDesc::exact(),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FireType {
     Bullet,
     Missile,
@@ -857,7 +717,7 @@ pub enum FireType {
     Wave(Option<WaveDmg>, f32),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AbilityActionDesc {
     Fire(FireDesc, usize),
     Wait(f32),
@@ -868,11 +728,43 @@ pub enum AbilityActionDesc {
     InflictStatusFlexible(StatusInflictDesc, usize),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StatusInflictDesc {
     pub ty: StatusType,
     pub turns: usize,
     pub chance: f32,
+    /// Scales the effects of the status.
+    #[serde(default = "StatusInflictDesc::default_strength")]
+    pub strength: f32,
+}
+
+impl StatusInflictDesc {
+    fn default_strength() -> f32 {
+        1.0
+    }
+}
+
+/// Modifies the outcome of each hit of an attack, on top of the crit stats of the attacker.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HitDesc {
+    /// Added to the crit chance of the attacker.
+    pub crit_chance: f32,
+    /// Added to the crit multiplier of the attacker.
+    pub crit_multiplier: f32,
+    /// The damage of each hit is randomly scaled by up to this proportion in either direction (e.g. 0.1 for +/-10%).
+    pub variance: f32,
+}
+
+impl HitDesc {
+    /// The attack relies entirely on the crit stats of the attacker, and has no variance.
+    pub const fn exact() -> Self {
+        Self {
+            crit_chance: 0.0,
+            crit_multiplier: 0.0,
+            variance: 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -883,6 +775,7 @@ pub struct FireBulletEvent {
     pub power: f32,
     pub accuracy: f32,
     pub effect: Option<StatusInflictDesc>,
+    pub hit: HitDesc,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -894,6 +787,7 @@ pub struct FireTorpedoEvent {
     pub accuracy: f32,
     pub element: Element,
     pub effect: Option<StatusInflictDesc>,
+    pub hit: HitDesc,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -903,17 +797,22 @@ pub struct FireLaserEvent {
     pub target: Entity,
     pub time: f32,
     pub power: f32,
+    /// Applied by the damage pipeline, rather than to the power.
+    pub scale: f32,
     pub accuracy: f32,
     pub element: Element,
     pub effect: Option<StatusInflictDesc>,
+    pub hit: HitDesc,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WaveDmg {
     pub accuracy: f32,
     pub power: f32,
     pub element: Element,
     pub effect: Option<StatusInflictDesc>,
+    #[serde(default)]
+    pub hit: HitDesc,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -954,4 +853,130 @@ impl<'s> System<'s> for PerformingAbilitySystem {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Applies the cooldown of each ability when it is performed.
+/// The cooldowns are counted down at the end of each of the owning character's turns,
+/// except the turn in which the ability was performed, so a cooldown of N blocks the next N turns.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(AbilityCooldownSystemDesc))]
+pub struct AbilityCooldownSystem {
+    #[system_desc(flagged_storage_reader(AbilityPerform))]
+    perform_event_reader: ReaderId<ComponentEvent>,
+    #[system_desc(event_channel_reader)]
+    tick_turn_event_reader: ReaderId<TickTurn>,
+}
+
+impl<'s> System<'s> for AbilityCooldownSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, Character>,
+        WriteStorage<'s, Ability>,
+        ReadStorage<'s, AbilityPerform>,
+        Read<'s, EventChannel<TickTurn>>,
+    );
+
+    fn run(&mut self, (entities, parents, teams, characters, mut abilities, ability_performs, tick_turn_events): Self::SystemData) {
+        for event in ability_performs.channel().read(&mut self.perform_event_reader) {
+            if let ComponentEvent::Inserted(id) = event {
+                if let Some(ability) = abilities.get_mut(entities.entity(*id)) {
+                    ability.apply_cooldown();
+                }
+            }
+        }
+
+        for event in tick_turn_events.read(&mut self.tick_turn_event_reader) {
+            for (ability_ent, ability) in (&entities, &mut abilities).join() {
+                if let Some((team, _)) = Team::get_team(&parents, &teams, ability_ent) {
+                    if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
+                        if event.is_own_turn(character_ent, team) {
+                            ability.end_turn();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn investment_spans_the_range() {
+        let charge: AbilityCharge = AbilityCharge::Range(20.0, 60.0);
+        assert_eq!(charge.investment(20.0), 0.0);
+        assert_eq!(charge.investment(40.0), 0.5);
+        assert_eq!(charge.investment(60.0), 1.0);
+    }
+
+    #[test]
+    fn investment_is_clamped_to_the_range() {
+        let charge: AbilityCharge = AbilityCharge::Range(20.0, 60.0);
+        assert_eq!(charge.investment(0.0), 0.0);
+        assert_eq!(charge.investment(100.0), 1.0);
+    }
+
+    #[test]
+    fn fixed_charges_invest_nothing() {
+        assert_eq!(AbilityCharge::Static(40.0).investment(100.0), 0.0);
+        assert_eq!(AbilityCharge::Range(40.0, 40.0).investment(40.0), 0.0);
+        assert_eq!(AbilityCharge::Range(60.0, 20.0).investment(40.0), 0.0);
+    }
+
+    #[test]
+    fn scaling_is_interpolated_by_the_investment() {
+        let scaling: ChargeScaling = ChargeScaling {
+            power: 3.0,
+            accuracy: 1.0,
+            status_chance: 0.5,
+        };
+        assert_eq!(scaling.scaled(0.0), ChargeScaling::default());
+        let half: ChargeScaling = scaling.scaled(0.5);
+        assert_eq!(half.power, 2.0);
+        assert_eq!(half.accuracy, 1.0);
+        assert_eq!(half.status_chance, 0.75);
+    }
+
+    fn ability_with_cooldown(cooldown: i32) -> Ability {
+        Ability::new(AbilityData {
+            cooldown,
+            ..charge::ChargeAbility::data()
+        })
+    }
+
+    #[test]
+    fn cooldown_blocks_the_following_turns() {
+        let mut ability: Ability = ability_with_cooldown(2);
+        ability.apply_cooldown();
+        // The turn in which the ability was performed.
+        ability.end_turn();
+        assert!(!ability.can_perform());
+        ability.end_turn();
+        assert!(!ability.can_perform());
+        ability.end_turn();
+        assert!(ability.can_perform());
+    }
+
+    #[test]
+    fn cooldown_of_one_blocks_the_next_turn() {
+        let mut ability: Ability = ability_with_cooldown(1);
+        ability.apply_cooldown();
+        ability.end_turn();
+        assert!(ability.is_on_cooldown());
+        ability.end_turn();
+        assert!(!ability.is_on_cooldown());
+    }
+
+    #[test]
+    fn no_cooldown_never_blocks() {
+        let mut ability: Ability = ability_with_cooldown(0);
+        ability.apply_cooldown();
+        assert!(ability.can_perform());
+        ability.end_turn();
+        assert!(ability.can_perform());
+    }
+}
@@ -32,7 +32,7 @@ use crate::{
 use crate::core::{activity::ActivityState, get_root, get_root_mut};
 use crate::game::character::{CharacterPrefabData, CharacterStore, UnassignedCharacter, CharacterRole};
 use crate::game::combat::{Team};
-use crate::game::combat::ability::{AbilityData, AbilityPerform, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityTarget, AbilityList, AbilityUsability, AbilityCharge};
+use crate::game::combat::ability::{AbilityData, AbilityPerform, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityTarget, AbilityList, AbilityUsability, AbilityCharge, AbilityId};
 use crate::game::combat::spawn::SpawnAction;
 use crate::game::ui::marker::{MarkerUiCompletedEvent, ShowUiMarkerEvent};
 use crate::game::ui::select_character::{CharacterSelectedEvent, SelectCharacterEvent};
@@ -46,15 +46,18 @@ pub struct SelfDestructAbility;
 pub struct SelfDestructPerform;
 
 impl SelfDestructAbility {
+    pub const ID: AbilityId = AbilityId("self_destruct");
+
     pub fn data() -> AbilityData {
         AbilityData {
             name: "Self Destruct",
             desc: "Self destructs the drone.",
-            id: TypeId::of::<Self>(),
+            id: Self::ID,
             system: TypeId::of::<SelfDestructAbilitySystem>(),
             charge: AbilityCharge::Static(0.0),
             target_info: None,
             cooldown: 4,
+            scaling: None,
         }
     }
 }
@@ -94,13 +97,14 @@ impl<'s> System<'s> for SelfDestructAbilitySystem {
                     AiAbilitySelection {
                         score: 0.0,
                         target: AbilityTarget::Single(character_ent),
+                        charge: None,
                     }
                 );
             }
         }
 
         for (entity, mut ability, _) in (&entities, &mut abilities, unassigned_abilities.mask().clone()).join() {
-            if ability.data.id == TypeId::of::<SelfDestructAbility>() {
+            if ability.data.id == SelfDestructAbility::ID {
                 self_destruct_abilities.insert(entity, SelfDestructAbility::default());
                 unassigned_abilities.remove(entity);
             }
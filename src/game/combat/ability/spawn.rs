@@ -33,7 +33,7 @@ use crate::{
 use crate::core::{activity::ActivityState, get_root};
 use crate::game::character::{CharacterPrefabData, CharacterStore, UnassignedCharacter, CharacterRole};
 use crate::game::combat::{Team, Rank, CharacterSpawn};
-use crate::game::combat::ability::{AbilityCharge, AbilityData, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityList, AbilityUsability, AbilityPerform};
+use crate::game::combat::ability::{AbilityCharge, AbilityData, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, UnassignedAbility, AbilityList, AbilityUsability, AbilityPerform, AbilityId};
 use crate::game::combat::spawn::SpawnAction;
 use crate::game::ui::select_character::{CharacterSelectedEvent, SelectCharacterEvent, UiCharacterSelectPanel};
 use crate::game::ui::select_rank::{RankSelectedEvent, ShowSelectRankUiEvent, UiSelectRankOption, UiSelectRankData};
@@ -46,15 +46,18 @@ pub struct SpawnAbility {
 }
 
 impl SpawnAbility {
+    pub const ID: AbilityId = AbilityId("spawn");
+
     pub fn data() -> AbilityData {
         AbilityData {
             name: "Spawn Drone",
             desc: "Spawns a new drone in your team.",
-            id: TypeId::of::<Self>(),
+            id: Self::ID,
             system: TypeId::of::<SpawnAbilitySystem>(),
             charge: AbilityCharge::Range(100.0, 700.0),
             target_info: None,
             cooldown: 4,
+            scaling: None,
         }
     }
 }
@@ -120,7 +123,7 @@ impl<'s> System<'s> for SpawnAbilitySystem {
 
     fn run(&mut self, (entities, mut principals, mut slot_managers, mut characters, mut unassigned_characters, mut character_prefabs, mut spawn_processes, mut abilities, mut unassigned_abilities, mut ability_invokes, mut ability_performs, mut spawn_abilities, mut display_tags, mut parents, teams, character_store, mut select_character_events, mut spawn_actions, character_selected_events, mut show_select_rank, mut rank_selected_events): Self::SystemData) {
         for (entity, ability, _) in (&entities, &abilities, unassigned_abilities.mask().clone()).join() {
-            if ability.data.id == TypeId::of::<SpawnAbility>() {
+            if ability.data.id == SpawnAbility::ID {
                 spawn_abilities.insert(entity, SpawnAbility::default());
                 unassigned_abilities.remove(entity);
             }
@@ -64,6 +64,7 @@ impl UpgradeAbility {
                 area: AbilityTargetArea::Single,
             }),
             cooldown: 4,
+            scaling: None,
         }
     }
 }
@@ -114,6 +115,7 @@ impl<'s> System<'s> for UpgradeAbilitySystem {
                     AiAbilitySelection {
                         score,
                         target: AbilityTarget::Single(character_ent),
+                        charge: None,
                     }
                 );
             }
@@ -30,16 +30,10 @@ use crate::{
     },
 };
 
-use crate::game::combat::ability::{
-    overclock::OverclockAbility,
-    sheild::ReinforceAbility,
-    nanobots::NanobotsAbility,
-    empower::EmpowerAbility,
-    AbilityTarget
-};
-use crate::core::{get_root, select_rng};
+use crate::game::combat::ability::AbilityTarget;
+use crate::core::{CombatRng, get_root, select_rng};
 use crate::game::combat::tactical::AiAbilitySelection;
-use crate::game::character::{SupporterSpacebotDrone, CharacterStore};
+use crate::game::character::CharacterStore;
 use crate::game::combat::status::StatusType;
 use crate::game::combat::ability::spawn::{all_rank_options, SpawnAbility};
 use crate::game::combat::{Rank, CharacterSpawn};
@@ -58,9 +52,10 @@ impl<'s> System<'s> for GuardianAiSystem {
         WriteStorage<'s, AiAbilitySelectionQuery>,
         WriteStorage<'s, SpawnAbility>,
         Read<'s, CharacterStore>,
+        Write<'s, CombatRng>,
     );
 
-    fn run(&mut self, (entities, parents, characters, slot_managers, teams, mut ability_selections, mut spawn_abilities, character_store): Self::SystemData) {
+    fn run(&mut self, (entities, parents, characters, slot_managers, teams, mut ability_selections, mut spawn_abilities, character_store, mut rng): Self::SystemData) {
         // Spawn
         for (ability_ent, selection, mut spawn_ability) in (&entities, &mut ability_selections, &mut spawn_abilities).join() {
             if let Some((team, _)) = Team::get_team(&parents, &teams, ability_ent) {
@@ -83,7 +78,7 @@ impl<'s> System<'s> for GuardianAiSystem {
                             chars.push(character_id);
                         }
 
-                        if let Some(char_idx) = select_rng(&chances) {
+                        if let Some(char_idx) = select_rng(&mut rng, &chances) {
                             let mut rank_chances: Vec<f32> = Vec::new();
                             let mut ranks: Vec<Rank> = Vec::new();
                             if let Some((character, _)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
@@ -95,7 +90,7 @@ impl<'s> System<'s> for GuardianAiSystem {
                                 }
                             }
 
-                            if let Some(rank_idx) = select_rng(&rank_chances) {
+                            if let Some(rank_idx) = select_rng(&mut rng, &rank_chances) {
                                 spawn_ability.next_spawn = Some(CharacterSpawn {
                                     character_id: chars[char_idx],
                                     rank: ranks[rank_idx],
@@ -103,6 +98,7 @@ impl<'s> System<'s> for GuardianAiSystem {
                                 selection.result = Some(AiAbilitySelection {
                                     target: AbilityTarget::Multi(Vec::new()),
                                     score: 15.0 / (slot_manager.enemy.count() as f32),
+                                    charge: None,
                                 });
                             }
                         }
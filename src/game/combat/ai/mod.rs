@@ -1,2 +1,21 @@
+use amethyst::ecs::prelude::*;
+
 pub mod spacebot;
-pub mod guardian;
\ No newline at end of file
+pub mod guardian;
+
+/// Overrides how a character's AI behaves (e.g. for a boss which becomes more reckless in its last phase).
+/// Characters without a profile use the default.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Component)]
+#[serde(default)]
+pub struct AiProfile {
+    /// Multiplies the aggressiveness of the difficulty.
+    pub aggressiveness: f32,
+}
+
+impl Default for AiProfile {
+    fn default() -> Self {
+        Self {
+            aggressiveness: 1.0,
+        }
+    }
+}
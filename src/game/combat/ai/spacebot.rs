@@ -31,18 +31,80 @@ use crate::{
 };
 
 use crate::game::combat::ability::{
-    overclock::OverclockAbility,
-    sheild::ReinforceAbility,
-    nanobots::NanobotsAbility,
-    empower::EmpowerAbility,
-    focus::FocusAbility,
-    AbilityTarget
+    AbilityActionDesc,
+    AbilityId,
+    AbilityList,
+    AbilityTarget,
+    AbilityTargetArea,
+    ChargeScaling,
+    DmgPackage,
+    Element,
+    FireType,
+    HitDesc,
 };
-use crate::core::{get_root, select_rng};
+use crate::core::{CombatRng, get_root, select_rng};
+use crate::game::combat::ai::AiProfile;
+use crate::game::combat::difficulty::DifficultyProfile;
+use crate::game::combat::element::ElementConfig;
+use crate::game::combat::pipeline::{DmgContext, DmgPipeline, HitChances, HitOutcome};
 use crate::game::combat::tactical::AiAbilitySelection;
-use crate::game::character::SupporterSpacebotDrone;
 use crate::game::combat::status::StatusType;
 
+/// The number of charge options which the AI considers for abilities with a range of charge.
+const AI_CHARGE_STEPS: usize = 3;
+/// How much of the target's health (from 0 to 1) investing the whole range of charge has to be worth.
+const AI_CHARGE_COST: f32 = 0.1;
+
+/// A single hit of a damaging ability, as far as the AI is concerned.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct ExpectedHit {
+    power: f32,
+    accuracy: f32,
+    element: Element,
+    hit: HitDesc,
+}
+
+impl ExpectedHit {
+    fn scaled(&self, scaling: &ChargeScaling) -> Self {
+        Self {
+            power: self.power * scaling.power,
+            accuracy: (self.accuracy * scaling.accuracy).min(1.0),
+            ..*self
+        }
+    }
+}
+
+/// Collects every hit which the ability deals to its target.
+fn expected_hits(actions: &[AbilityActionDesc]) -> Vec<ExpectedHit> {
+    let mut hits: Vec<ExpectedHit> = Vec::new();
+    for action in actions.iter() {
+        match action {
+            AbilityActionDesc::Fire(fire_desc, _) => {
+                let element: Element = match fire_desc.ty {
+                    FireType::Torpedo(element) | FireType::Laser(element, _, _) => element,
+                    _ => Element::Kinetic,
+                };
+                hits.push(ExpectedHit {
+                    power: fire_desc.power,
+                    accuracy: fire_desc.accuracy,
+                    element,
+                    hit: fire_desc.hit,
+                });
+            },
+            AbilityActionDesc::Wave(_, _, Some(dmg)) => {
+                hits.push(ExpectedHit {
+                    power: dmg.power,
+                    accuracy: dmg.accuracy,
+                    element: dmg.element,
+                    hit: dmg.hit,
+                });
+            },
+            _ => {},
+        }
+    }
+    hits
+}
+
 #[derive(Debug, SystemDesc, new)]
 #[system_desc(name(SpacebotAiSystemDesc))]
 pub struct SpacebotAiSystem;
@@ -51,19 +113,22 @@ impl<'s> System<'s> for SpacebotAiSystem {
     type SystemData = (
         Entities<'s>,
         ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
         ReadStorage<'s, Character>,
+        ReadStorage<'s, AiProfile>,
         WriteStorage<'s, AiAbilitySelectionQuery>,
-        ReadStorage<'s, OverclockAbility>,
-        ReadStorage<'s, ReinforceAbility>,
-        ReadStorage<'s, NanobotsAbility>,
-        ReadStorage<'s, EmpowerAbility>,
-        ReadStorage<'s, FocusAbility>,
+        ReadStorage<'s, Ability>,
+        Write<'s, CombatRng>,
+        Read<'s, AbilityList>,
+        Read<'s, DmgPipeline>,
+        Read<'s, ElementConfig>,
+        Read<'s, DifficultyProfile>,
     );
 
-    fn run(&mut self, (entities, parents, characters, mut ability_selections, overclocks, reinforces, nanobots, empowers, focuses): Self::SystemData) {
+    fn run(&mut self, (entities, parents, teams, characters, ai_profiles, mut ability_selections, abilities, mut rng, ability_list, pipeline, element_config, difficulty): Self::SystemData) {
 
         // Overclock
-        for (ability_ent, selection, _) in (&entities, &mut ability_selections, overclocks.mask()).join() {
+        for (ability_ent, selection, _) in (&entities, &mut ability_selections, &abilities).join().filter(|(_, _, ability)| ability.data.id == AbilityId("overclock")) {
             if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
                 let mut chances: Vec<f32> = Vec::new();
                 let mut targets: Vec<Entity> = Vec::new();
@@ -81,17 +146,18 @@ impl<'s> System<'s> for SpacebotAiSystem {
                         }
                     }
                 }
-                if let Some(target_idx) = select_rng(&chances) {
+                if let Some(target_idx) = select_rng(&mut rng, &chances) {
                     selection.result = Some(AiAbilitySelection {
                         target: AbilityTarget::Single(targets[target_idx]),
                         score: max_charge * 3.0,
+                        charge: None,
                     });
                 }
             }
         }
 
         // Defend
-        for (ability_ent, selection, _) in (&entities, &mut ability_selections, reinforces.mask()).join() {
+        for (ability_ent, selection, _) in (&entities, &mut ability_selections, &abilities).join().filter(|(_, _, ability)| ability.data.id == AbilityId("reinforce")) {
             let mut chances: Vec<f32> = Vec::new();
             let mut targets: Vec<Entity> = Vec::new();
             let mut max_health: f32 = 1.0;
@@ -104,16 +170,17 @@ impl<'s> System<'s> for SpacebotAiSystem {
                     }
                 }
             }
-            if let Some(target_idx) = select_rng(&chances) {
+            if let Some(target_idx) = select_rng(&mut rng, &chances) {
                 selection.result = Some(AiAbilitySelection {
                     target: AbilityTarget::Single(targets[target_idx]),
                     score: (1.0 - max_health) * 3.0,
+                    charge: None,
                 });
             }
         }
 
         // Nanobots
-        for (ability_ent, selection, _) in (&entities, &mut ability_selections, nanobots.mask()).join() {
+        for (ability_ent, selection, _) in (&entities, &mut ability_selections, &abilities).join().filter(|(_, _, ability)| ability.data.id == AbilityId("nanobots")) {
             let mut min_health: f32 = 1.0;
             let mut min_target: Option<Entity> = None;
             let mut relative_total: f32 = 0.0;
@@ -134,18 +201,20 @@ impl<'s> System<'s> for SpacebotAiSystem {
                 selection.result = Some(AiAbilitySelection {
                     target: AbilityTarget::Single(min_target.unwrap()),
                     score: (1.0 - min_health) * 5.0,
+                    charge: None,
                 });
             } else {
                 selection.result = Some(AiAbilitySelection {
                     target: AbilityTarget::Multi(selection.targets.clone()),
                     score: (1.0 - ave) * 3.0,
+                    charge: None,
                 });
             }
 
         }
 
         // Empower
-        for (ability_ent, selection, _) in (&entities, &mut ability_selections, empowers.mask()).join() {
+        for (ability_ent, selection, _) in (&entities, &mut ability_selections, &abilities).join().filter(|(_, _, ability)| ability.data.id == AbilityId("empower")) {
             if let Some((character, _)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
                 if !character.has_status(StatusType::Empower) {
                     let mut chances: Vec<f32> = Vec::new();
@@ -153,7 +222,7 @@ impl<'s> System<'s> for SpacebotAiSystem {
                     for target in selection.targets.iter() {
                         if let Some(character) = characters.get(*target) {
                             if !character.has_status(StatusType::Scramble) && !character.has_status(StatusType::Empower) {
-                                if character.id() != SupporterSpacebotDrone::character_id() {
+                                if character.id() != CharacterId("supporter_spacebot") {
                                     chances.push(0.5 + character.relative_charge());
                                     targets.push(*target);
                                 }
@@ -161,23 +230,25 @@ impl<'s> System<'s> for SpacebotAiSystem {
                         }
                     }
 
-                    if let Some(target_idx) = select_rng(&chances) {
+                    if let Some(target_idx) = select_rng(&mut rng, &chances) {
                         selection.result = Some(AiAbilitySelection {
                             target: AbilityTarget::Single(targets[target_idx]),
                             score: 3.0,
+                            charge: None,
                         });
                     }
                 } else {
                     selection.result = Some(AiAbilitySelection {
                         target: AbilityTarget::Multi(Vec::new()),
                         score: 0.0,
+                        charge: None,
                     });
                 }
             }
         }
 
         // Focus
-        for (ability_ent, selection, _) in (&entities, &mut ability_selections, focuses.mask()).join() {
+        for (ability_ent, selection, _) in (&entities, &mut ability_selections, &abilities).join().filter(|(_, _, ability)| ability.data.id == AbilityId("focus")) {
             if let Some((character, _)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
                 if !character.has_status(StatusType::Focus) {
                     let mut chances: Vec<f32> = Vec::new();
@@ -185,7 +256,7 @@ impl<'s> System<'s> for SpacebotAiSystem {
                     for target in selection.targets.iter() {
                         if let Some(character) = characters.get(*target) {
                             if !character.has_status(StatusType::Scramble) && !character.has_status(StatusType::Focus) {
-                                if character.id() != SupporterSpacebotDrone::character_id() {
+                                if character.id() != CharacterId("supporter_spacebot") {
                                     chances.push(0.5 + character.relative_charge());
                                     targets.push(*target);
                                 }
@@ -193,16 +264,101 @@ impl<'s> System<'s> for SpacebotAiSystem {
                         }
                     }
 
-                    if let Some(target_idx) = select_rng(&chances) {
+                    if let Some(target_idx) = select_rng(&mut rng, &chances) {
                         selection.result = Some(AiAbilitySelection {
                             target: AbilityTarget::Single(targets[target_idx]),
                             score: 3.0,
+                            charge: None,
                         });
                     }
                 } else {
                     selection.result = Some(AiAbilitySelection {
                         target: AbilityTarget::Multi(Vec::new()),
                         score: 0.0,
+                        charge: None,
+                    });
+                }
+            }
+        }
+
+
+        // Damage
+        // Single target attacks prefer the target which they are expected to deal the most damage to, relative to its health.
+        // The expected damage takes the hit chance, crits, glancing blows and the target's resistances into account.
+        // Abilities with a range of charge invest the least charge which gets the most out of the target.
+        for (ability_ent, selection, ability) in (&entities, &mut ability_selections, &abilities).join() {
+            if selection.result.is_some() || ability.data.target_info.map_or(true, |target_info| target_info.area != AbilityTargetArea::Single) {
+                continue;
+            }
+            let hits: Vec<ExpectedHit> = expected_hits(ability_list.actions(&ability.data.id).unwrap_or(&[]));
+            if hits.is_empty() {
+                continue;
+            }
+            if let Some((source, source_ent)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
+                let source_team: Option<Team> = Team::get_team(&parents, &teams, source_ent).map(|(team, _)| team);
+                // More aggressive AI prefers attacking and invests charge more freely.
+                let aggressiveness: f32 = difficulty.aggressiveness(source_team) * ai_profiles.get(source_ent).map_or(1.0, |profile| profile.aggressiveness);
+                // The lowest charge is always considered, since the ability would not have been queried if it could not be afforded.
+                let investments: Vec<(Option<f32>, f32, ChargeScaling)> = match ability.data.scaling {
+                    Some(scaling) if ability.data.charge.is_variable() => ability.data.charge.steps(AI_CHARGE_STEPS)
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, charge)| *i == 0 || source.charge() >= *charge)
+                        .map(|(_, charge)| {
+                            let investment: f32 = ability.data.charge.investment(charge);
+                            (Some(charge), investment, scaling.scaled(investment))
+                        })
+                        .collect(),
+                    _ => vec![(None, 0.0, ChargeScaling::default())],
+                };
+                let mut chances: Vec<f32> = Vec::new();
+                let mut targets: Vec<(Entity, Option<f32>)> = Vec::new();
+                for target_ent in selection.targets.iter() {
+                    if let Some(target) = characters.get(*target_ent) {
+                        let mut best: Option<(f32, f32, Option<f32>)> = None;
+                        for (charge, investment, scaling) in investments.iter() {
+                            let mut expected: f32 = 0.0;
+                            for hit in hits.iter().map(|hit| hit.scaled(scaling)) {
+                                let package: DmgPackage = DmgPackage {
+                                    source: Some(source_ent),
+                                    target: *target_ent,
+                                    power: hit.power,
+                                    element: hit.element,
+                                    status: None,
+                                    scale: 1.0,
+                                    hit: hit.hit,
+                                };
+                                expected += source.hit_chance(target, hit.accuracy) * pipeline.expected(&DmgContext {
+                                    package: &package,
+                                    source: Some(source),
+                                    source_team,
+                                    target,
+                                    element_config: &element_config,
+                                    difficulty: &difficulty,
+                                    chances: HitChances::new(Some(source), target, &hit.hit),
+                                    outcome: HitOutcome::Normal,
+                                    variance: 1.0,
+                                });
+                            }
+                            // Anything which is expected to destroy the target is equally good.
+                            let fraction: f32 = (expected / target.health().max(1.0)).min(1.0);
+                            let value: f32 = fraction - investment * AI_CHARGE_COST / aggressiveness;
+                            if best.map_or(true, |(best_value, _, _)| value > best_value) {
+                                best = Some((value, fraction, *charge));
+                            }
+                        }
+                        if let Some((_, fraction, charge)) = best {
+                            chances.push(fraction);
+                            targets.push((*target_ent, charge));
+                        }
+                    }
+                }
+                if let Some(target_idx) = select_rng(&mut rng, &chances) {
+                    let (target_ent, charge) = targets[target_idx];
+                    selection.result = Some(AiAbilitySelection {
+                        target: AbilityTarget::Single(target_ent),
+                        score: (1.0 + chances[target_idx] * 3.0) * aggressiveness,
+                        charge,
                     });
                 }
             }
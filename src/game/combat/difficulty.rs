@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use amethyst::{
+    assets::{
+        Format,
+        RonFormat,
+    },
+    log::{error, warn},
+    utils::application_root_dir,
+};
+
+use crate::game::character::CharacterData;
+use crate::game::combat::{Difficulty, Team};
+
+/// The file (relative to the application root) which configures the profile of each difficulty.
+pub const DIFFICULTY_FILE: &str = "assets/difficulty.ron";
+
+/// Multipliers of the base stats of a character.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatScaling {
+    pub max_health: f32,
+    pub max_charge: f32,
+    pub initial_charge: f32,
+    pub natural_charge: f32,
+    pub artificial_charge: f32,
+    pub evade: f32,
+    pub accuracy: f32,
+}
+
+impl Default for StatScaling {
+    fn default() -> Self {
+        Self {
+            max_health: 1.0,
+            max_charge: 1.0,
+            initial_charge: 1.0,
+            natural_charge: 1.0,
+            artificial_charge: 1.0,
+            evade: 1.0,
+            accuracy: 1.0,
+        }
+    }
+}
+
+impl StatScaling {
+    pub fn apply(&self, data: &mut CharacterData) {
+        data.max_health *= self.max_health;
+        data.max_charge *= self.max_charge;
+        data.initial_charge *= self.initial_charge;
+        data.natural_charge *= self.natural_charge;
+        data.artificial_charge *= self.artificial_charge;
+        data.base_evade *= self.evade;
+        data.base_accuracy *= self.accuracy;
+    }
+}
+
+/// Everything which the difficulty affects. The profile of the current difficulty is inserted as a resource for each combat.
+/// The default profile leaves everything unchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyProfile {
+    /// Applied to every enemy drone when it spawns, including bosses and summons.
+    pub enemy_stats: StatScaling,
+    /// The damage multiplier of the enemy team.
+    pub enemy_dmg: f32,
+    /// How eager the enemy AI is to attack and to invest charge in its attacks.
+    pub ai_aggressiveness: f32,
+    /// Added to the hit chance of the attacks of enemy drones.
+    pub hit_bias: f32,
+    /// The multiplier of the charge which the player's drones regain.
+    pub player_charge_regen: f32,
+    /// The multiplier of the base chance of a hack succeeding.
+    pub hack_chance: f32,
+    /// The multiplier of the upgrades which are rewarded for completing a point on the map.
+    pub reward: f32,
+}
+
+impl Default for DifficultyProfile {
+    fn default() -> Self {
+        Self {
+            enemy_stats: StatScaling::default(),
+            enemy_dmg: 1.0,
+            ai_aggressiveness: 1.0,
+            hit_bias: 0.0,
+            player_charge_regen: 1.0,
+            hack_chance: 1.0,
+            reward: 1.0,
+        }
+    }
+}
+
+impl DifficultyProfile {
+    /// Scales the data of a character which is about to spawn on the team.
+    pub fn scale(&self, team: Team, data: &mut CharacterData) {
+        match team {
+            Team::Friendly => {
+                data.natural_charge *= self.player_charge_regen;
+                data.artificial_charge *= self.player_charge_regen;
+            },
+            Team::Enemy => {
+                self.enemy_stats.apply(data);
+                data.hit_bias += self.hit_bias;
+            },
+        }
+    }
+
+    /// The damage multiplier of hits from the team.
+    pub fn dmg_multiplier(&self, source_team: Option<Team>) -> f32 {
+        match source_team {
+            Some(Team::Enemy) => self.enemy_dmg,
+            _ => 1.0,
+        }
+    }
+
+    /// The AI of the player's team (e.g. when running headless) is unaffected.
+    pub fn aggressiveness(&self, team: Option<Team>) -> f32 {
+        match team {
+            Some(Team::Enemy) => self.ai_aggressiveness,
+            _ => 1.0,
+        }
+    }
+}
+
+/// The difficulty configuration, as it is stored in `assets/difficulty.ron`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyConfig {
+    #[serde(default)]
+    pub profiles: HashMap<Difficulty, DifficultyProfile>,
+}
+
+impl DifficultyConfig {
+    /// Difficulties without a profile fall back to the default profile.
+    pub fn profile(&self, difficulty: Difficulty) -> DifficultyProfile {
+        self.profiles.get(&difficulty).copied().unwrap_or_default()
+    }
+}
+
+/// Loads the difficulty configuration from `assets/difficulty.ron`.
+pub fn difficulty_config() -> DifficultyConfig {
+    match application_root_dir() {
+        Ok(app_root) => load_difficulty_config(app_root.join(DIFFICULTY_FILE)),
+        Err(err) => {
+            error!("[difficulty_config] Failed to find the application root: {:?}", err);
+            DifficultyConfig::default()
+        },
+    }
+}
+
+/// Falls back to the default profile for every difficulty if the file fails to load.
+pub fn load_difficulty_config(path: impl AsRef<Path>) -> DifficultyConfig {
+    match fs::read(path.as_ref()) {
+        Ok(bytes) => {
+            match Format::<DifficultyConfig>::import_simple(&RonFormat, bytes) {
+                Ok(config) => config,
+                Err(err) => {
+                    warn!("[load_difficulty_config] Failed to parse {:?}: {:?}", path.as_ref(), err);
+                    DifficultyConfig::default()
+                },
+            }
+        },
+        Err(err) => {
+            warn!("[load_difficulty_config] Failed to read {:?}: {:?}", path.as_ref(), err);
+            DifficultyConfig::default()
+        },
+    }
+}
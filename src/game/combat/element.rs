@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use amethyst::{
+    assets::{
+        Format,
+        RonFormat,
+    },
+    log::{error, warn},
+    utils::application_root_dir,
+};
+use rand::Rng;
+
+use crate::core::CombatRng;
+use crate::game::combat::ability::{Element, StatusInflictDesc};
+use crate::game::combat::status::StatusType;
+
+/// The file (relative to the application root) which configures the on-hit procs and effectiveness of each element.
+pub const ELEMENT_FILE: &str = "assets/elements.ron";
+
+/// A status which may be inflicted whenever an attack of a particular element hits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementProc {
+    /// One of these statuses is picked at random each time the proc is rolled (e.g. for quantum attacks).
+    pub statuses: Vec<StatusType>,
+    pub turns: usize,
+    pub chance: f32,
+    #[serde(default = "ElementProc::default_strength")]
+    pub strength: f32,
+}
+
+impl ElementProc {
+    fn default_strength() -> f32 {
+        1.0
+    }
+
+    /// Picks the status to inflict. The chance is rolled when the status is inflicted.
+    pub fn select(&self, rng: &mut CombatRng) -> Option<StatusInflictDesc> {
+        let ty: StatusType = match self.statuses.len() {
+            0 => return None,
+            1 => self.statuses[0],
+            len => self.statuses[rng.gen_range(0, len)],
+        };
+        Some(StatusInflictDesc {
+            ty,
+            turns: self.turns,
+            chance: self.chance,
+            strength: self.strength,
+        })
+    }
+}
+
+/// The element configuration, as it is stored in `assets/elements.ron`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ElementConfig {
+    /// Elements without a proc do not inflict any status by themselves.
+    #[serde(default)]
+    pub procs: HashMap<Element, ElementProc>,
+    /// The type-effectiveness matrix: the damage multiplier of each attacking element against characters of each element.
+    /// Pairs which are not listed (and characters without an element) are neutral.
+    #[serde(default)]
+    pub effectiveness: HashMap<Element, HashMap<Element, f32>>,
+}
+
+impl ElementConfig {
+    pub fn proc(&self, element: Element) -> Option<&ElementProc> {
+        self.procs.get(&element)
+    }
+
+    pub fn effectiveness(&self, attack: Element, defence: Option<Element>) -> f32 {
+        defence
+            .and_then(|defence| self.effectiveness.get(&attack).and_then(|row| row.get(&defence)))
+            .copied()
+            .unwrap_or(1.0)
+    }
+}
+
+/// Loads the element configuration from `assets/elements.ron`.
+pub fn element_config() -> ElementConfig {
+    match application_root_dir() {
+        Ok(app_root) => load_element_config(app_root.join(ELEMENT_FILE)),
+        Err(err) => {
+            error!("[element_config] Failed to find the application root: {:?}", err);
+            ElementConfig::default()
+        },
+    }
+}
+
+/// Falls back to no procs and neutral effectiveness if the file fails to load.
+pub fn load_element_config(path: impl AsRef<Path>) -> ElementConfig {
+    match fs::read(path.as_ref()) {
+        Ok(bytes) => {
+            match Format::<ElementConfig>::import_simple(&RonFormat, bytes) {
+                Ok(config) => config,
+                Err(err) => {
+                    warn!("[load_element_config] Failed to parse {:?}: {:?}", path.as_ref(), err);
+                    ElementConfig::default()
+                },
+            }
+        },
+        Err(err) => {
+            warn!("[load_element_config] Failed to read {:?}: {:?}", path.as_ref(), err);
+            ElementConfig::default()
+        },
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::thread::current;
 
 use amethyst::{
@@ -29,13 +29,15 @@ use crate::{
         },
     },
 };
-use crate::core::{get_root, select_rng};
+use crate::core::{CombatRng, get_root, select_rng};
 use crate::game::combat::ability::{AbilityPerform, perform_ability, AbilityTargetType, AbilityTargetArea};
+use crate::game::combat::ability::flee::FleeAbility;
 use crate::game::combat::process::Principal;
 use crate::game::combat::systems::delay::Delay;
 use crate::game::combat::tactical::AiAbilitySelection;
 use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
 use crate::game::character::CharacterDefeatedEvent;
+use crate::game::combat::replay::ReplayPlayback;
 
 /// Contains 'live' data relating to the enemy team.
 pub struct EnemyState {
@@ -46,6 +48,22 @@ pub struct EnemyState {
 #[derive(Debug, Copy, Clone, Default, Component)]
 pub struct HasDelayedTag;
 
+/// The teams whose turns are played by the AI rather than the player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AiControlledTeams(pub Vec<Team>);
+
+impl Default for AiControlledTeams {
+    fn default() -> Self {
+        AiControlledTeams(vec![Team::Enemy])
+    }
+}
+
+impl AiControlledTeams {
+    pub fn controls(&self, team: Team) -> bool {
+        self.0.contains(&team)
+    }
+}
+
 #[derive(Debug, new, SystemDesc)]
 #[system_desc(name(EnemyControllerSystemDesc))]
 pub struct EnemyControllerSystem {
@@ -69,9 +87,15 @@ impl<'s> System<'s> for EnemyControllerSystem {
         ReadExpect<'s, ParentHierarchy>,
         Write<'s, EventChannel<SpawnWaveEvent>>,
         Read<'s, EventChannel<CharacterDefeatedEvent>>,
+        Read<'s, AiControlledTeams>,
+        Read<'s, ReplayPlayback>,
     );
 
-    fn run(&mut self, (entities, mut characters, teams, abilities, mut ability_selections, slot_managers, mut combat_roots, mut delayed_tags, mut principals, mut delays, parents, hierarchy, mut spawn_wave_events, defeated_events): Self::SystemData) {
+    fn run(&mut self, (entities, mut characters, teams, abilities, mut ability_selections, slot_managers, mut combat_roots, mut delayed_tags, mut principals, mut delays, parents, hierarchy, mut spawn_wave_events, defeated_events, ai_teams, playback): Self::SystemData) {
+        // Replays perform the recorded abilities instead.
+        if playback.is_active() {
+            return;
+        }
         for (entity, mut root, slot_manager) in (&entities, &mut combat_roots, &slot_managers).join() {
             // Only execute in no principals are running.
             if Principal::is_root_engaged(
@@ -79,10 +103,13 @@ impl<'s> System<'s> for EnemyControllerSystem {
                 &principals,
                 entity,
             ) != Some(true) {
-                // Enemy turn!
-                if root.current_state == CombatState::InTurn(Team::Enemy) {
+                // AI turn!
+                if let CombatState::InTurn(team) = root.current_state {
+                    if !ai_teams.controls(team) {
+                        continue;
+                    }
                     if delayed_tags.contains(entity) {
-                        for (i, entity_opt) in slot_manager.enemy.occupied().iter().enumerate() {
+                        for (i, entity_opt) in slot_manager.for_team(team).occupied().iter().enumerate() {
                             if let Some(character_ent) = *entity_opt {
                                 if let Some(character) = characters.get(character_ent) {
                                     if character.has_turn() {
@@ -90,8 +117,12 @@ impl<'s> System<'s> for EnemyControllerSystem {
                                         let children: BitSet = hierarchy.all_children(character_ent);
                                         let mut has_active_abilities: bool = false;
                                         for (entity, ability, bit) in (&entities, &abilities, &children).join() {
+                                            // The AI never flees, so fleeing is never offered to it.
+                                            if ability.data.id == FleeAbility::ID {
+                                                continue;
+                                            }
                                             // Check if ability can be performed.
-                                            if Character::can_take_turn(
+                                            if ability.can_perform() && Character::can_take_turn(
                                                 &characters,
                                                 character_ent,
                                                 ability.data.charge.rated_charge(),
@@ -152,8 +183,10 @@ impl<'s> System<'s> for EnemyControllerSystem {
                             0.4,
                         );
                     }
-                } else if root.current_state == CombatState::DoneTurn(Team::Enemy) {
-                    delayed_tags.remove(entity);
+                } else if let CombatState::DoneTurn(team) = root.current_state {
+                    if ai_teams.controls(team) {
+                        delayed_tags.remove(entity);
+                    }
                 }
             }
         }
@@ -173,19 +206,19 @@ impl<'s> System<'s> for EnemyAbilityInvocationSystem {
         ReadStorage<'s, Ability>,
         WriteStorage<'s, AiAbilitySelectionQuery>,
         WriteStorage<'s, AbilityPerform>,
+        Write<'s, CombatRng>,
     );
 
-    fn run(&mut self, (entities, parents, mut principals, mut characters, abilities, mut ability_selections, mut ability_performs): Self::SystemData) {
-        let mut sets: HashMap<Entity, Vec<(AiAbilitySelection, Entity)>> = HashMap::new();
+    fn run(&mut self, (entities, parents, mut principals, mut characters, abilities, mut ability_selections, mut ability_performs, mut rng): Self::SystemData) {
+        // Ordered so that the `CombatRng` is used in the same order every time.
+        let mut sets: BTreeMap<Entity, Vec<(AiAbilitySelection, Entity)>> = BTreeMap::new();
         let mut to_remove: Vec<Entity> = Vec::new();
         for (entity, ability, selection) in (&entities, &abilities, &ability_selections).join() {
             if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, entity) {
+                // Characters whose abilities all declined are still tracked so that they pass their turn.
+                let existing = sets.entry(character_ent).or_insert_with(Vec::new);
                 if let Some(res) = selection.result.clone() {
-                    if let Some(existing) = sets.get_mut(&character_ent) {
-                        existing.push((res, entity));
-                    } else {
-                        sets.insert(character_ent, vec![(res, entity)]);
-                    }
+                    existing.push((res, entity));
                 }
             }
             to_remove.push(entity);
@@ -201,12 +234,13 @@ impl<'s> System<'s> for EnemyAbilityInvocationSystem {
                 values.push(selection.score);
             }
 
-            if let Some(selection) = select_rng(&values) {
+            if let Some(selection) = select_rng(&mut rng, &values) {
                 // make principal
                 perform_ability(
                     &parents, &mut principals, &abilities, &mut ability_performs,
                     selections[selection].1,
                     selections[selection].0.target.clone(),
+                    selections[selection].0.charge,
                 ).expect("No `Principal` in hierarchy!");
             } else {
                 if let Some(character) = characters.get_mut(character_ent) {
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use amethyst::ecs::{
+    prelude::*,
+    storage::GenericReadStorage,
+};
+
+use crate::game::character::Character;
+use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::Team;
+
+/// The time a character with a speed of 1 waits between its turns.
+pub const INITIATIVE_DELAY: f32 = 100.0;
+
+/// Stops characters with no speed from never acting again.
+pub const MIN_SPEED: f32 = 0.1;
+
+/// The turn order of the initiative turn mode, which is shared by both teams.
+/// Each character is scheduled to act after a delay which is inversely proportional to its speed.
+/// When the speed of a waiting character changes (e.g. it is slowed), the rest of its wait is scaled to match.
+/// Ties are broken by entity, so that the order is the same when a combat is replayed.
+#[derive(Debug, Clone, Default)]
+pub struct InitiativeQueue {
+    /// The time at which each character takes its next turn, along with the speed it was scheduled with.
+    times: BTreeMap<Entity, (f32, f32)>,
+    /// The time of the current turn.
+    time: f32,
+    current: Option<Entity>,
+}
+
+impl InitiativeQueue {
+    /// The character which is currently taking its turn.
+    #[inline]
+    pub fn current(&self) -> Option<Entity> {
+        self.current
+    }
+
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Collects the characters in the combat, along with their speeds.
+    pub fn participants(slot_manager: &SlotManager, characters: &impl GenericReadStorage<Component=Character>) -> Vec<(Entity, f32)> {
+        let mut participants: Vec<(Entity, f32)> = Vec::new();
+        for team in [Team::Friendly, Team::Enemy].iter() {
+            for entity_opt in slot_manager.for_team(*team).occupied().iter() {
+                if let Some(character_ent) = *entity_opt {
+                    if let Some(character) = characters.get(character_ent) {
+                        participants.push((character_ent, character.speed()));
+                    }
+                }
+            }
+        }
+        participants
+    }
+
+    fn delay(speed: f32) -> f32 {
+        INITIATIVE_DELAY / speed.max(MIN_SPEED)
+    }
+
+    /// Removes the characters which have left the combat, and reschedules those whose speed has changed.
+    /// Characters which have joined wait for a full turn, so the fastest characters act first at the start of the combat.
+    pub fn sync(&mut self, participants: &[(Entity, f32)]) {
+        self.times.retain(|entity, _| participants.iter().any(|(participant, _)| participant == entity));
+        let now: f32 = self.time;
+        for (entity, speed) in participants.iter() {
+            let speed: f32 = speed.max(MIN_SPEED);
+            match self.times.get_mut(entity) {
+                Some((time, scheduled_speed)) => {
+                    if *scheduled_speed != speed {
+                        *time = now + (*time - now) * *scheduled_speed / speed;
+                        *scheduled_speed = speed;
+                    }
+                },
+                None => {
+                    self.times.insert(*entity, (now + Self::delay(speed), speed));
+                },
+            }
+        }
+        if let Some(current) = self.current {
+            if !self.times.contains_key(&current) {
+                self.current = None;
+            }
+        }
+    }
+
+    /// Ends the turn of the current character and moves on to the next character in the queue.
+    /// Returns `None` if there are no characters left.
+    pub fn advance(&mut self, participants: &[(Entity, f32)]) -> Option<Entity> {
+        self.sync(participants);
+        if let Some(current) = self.current.take() {
+            if let Some((_, speed)) = participants.iter().find(|(entity, _)| *entity == current) {
+                let speed: f32 = speed.max(MIN_SPEED);
+                self.times.insert(current, (self.time + Self::delay(speed), speed));
+            }
+        }
+        let (next, time) = Self::first(&self.times)?;
+        self.time = time;
+        self.current = Some(next);
+        Some(next)
+    }
+
+    /// Predicts the next characters to act, starting with the current character.
+    /// The prediction assumes that the speeds of the characters do not change.
+    pub fn upcoming(&self, participants: &[(Entity, f32)], count: usize) -> Vec<Entity> {
+        let mut queue: InitiativeQueue = self.clone();
+        queue.sync(participants);
+        let mut upcoming: Vec<Entity> = Vec::with_capacity(count);
+        if let Some(current) = queue.current {
+            upcoming.push(current);
+        }
+        while upcoming.len() < count {
+            match queue.advance(participants) {
+                Some(next) => upcoming.push(next),
+                None => break,
+            }
+        }
+        upcoming
+    }
+
+    fn first(times: &BTreeMap<Entity, (f32, f32)>) -> Option<(Entity, f32)> {
+        // Iterating over the map in entity order means that the earliest entity wins ties.
+        times.iter().fold(None, |first: Option<(Entity, f32)>, (entity, (time, _))| {
+            match first {
+                Some((_, first_time)) if time.partial_cmp(&first_time) != Some(Ordering::Less) => first,
+                _ => Some((*entity, *time)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities(count: usize) -> Vec<Entity> {
+        let mut world = World::new();
+        (0..count).map(|_| world.create_entity().build()).collect()
+    }
+
+    #[test]
+    fn faster_characters_act_more_often() {
+        let ents: Vec<Entity> = entities(2);
+        let participants: Vec<(Entity, f32)> = vec![(ents[0], 2.0), (ents[1], 1.0)];
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        let order: Vec<Entity> = (0..6).filter_map(|_| queue.advance(&participants)).collect();
+        assert_eq!(order, vec![ents[0], ents[0], ents[1], ents[0], ents[0], ents[1]]);
+        assert_eq!(queue.time(), 200.0);
+    }
+
+    #[test]
+    fn advance_returns_none_without_participants() {
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        assert_eq!(queue.advance(&[]), None);
+        assert_eq!(queue.current(), None);
+    }
+
+    #[test]
+    fn sync_removes_characters_which_have_left() {
+        let ents: Vec<Entity> = entities(2);
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        assert_eq!(queue.advance(&[(ents[0], 1.0), (ents[1], 1.0)]), Some(ents[0]));
+        queue.sync(&[(ents[1], 1.0)]);
+        assert_eq!(queue.current(), None);
+        assert_eq!(queue.advance(&[(ents[1], 1.0)]), Some(ents[1]));
+    }
+
+    #[test]
+    fn sync_scales_the_wait_when_the_speed_changes() {
+        let ents: Vec<Entity> = entities(2);
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        assert_eq!(queue.advance(&[(ents[0], 1.0), (ents[1], 0.5)]), Some(ents[0]));
+        // Half of the slow character's wait is left, which is halved again by doubling its speed.
+        let hasted: Vec<(Entity, f32)> = vec![(ents[0], 1.0), (ents[1], 1.0)];
+        queue.sync(&hasted);
+        assert_eq!(queue.advance(&hasted), Some(ents[1]));
+        assert_eq!(queue.time(), 150.0);
+    }
+
+    #[test]
+    fn characters_without_speed_still_act() {
+        let ents: Vec<Entity> = entities(1);
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        assert_eq!(queue.advance(&[(ents[0], 0.0)]), Some(ents[0]));
+        assert_eq!(queue.time(), INITIATIVE_DELAY / MIN_SPEED);
+    }
+
+    #[test]
+    fn upcoming_starts_with_the_current_character_and_leaves_the_queue_alone() {
+        let ents: Vec<Entity> = entities(2);
+        let participants: Vec<(Entity, f32)> = vec![(ents[0], 2.0), (ents[1], 1.0)];
+        let mut queue: InitiativeQueue = InitiativeQueue::default();
+        queue.advance(&participants);
+        assert_eq!(queue.upcoming(&participants, 4), vec![ents[0], ents[0], ents[1], ents[0]]);
+        assert_eq!(queue.current(), Some(ents[0]));
+        assert_eq!(queue.time(), 50.0);
+    }
+}
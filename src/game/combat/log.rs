@@ -0,0 +1,391 @@
+use amethyst::{
+    core::Parent,
+    ecs::{
+        prelude::*,
+        storage::{
+            ComponentEvent,
+            GenericReadStorage,
+        },
+    },
+    log::error,
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+};
+
+use crate::core::get_root;
+use crate::game::character::{Character, CharacterDefeatedEvent};
+use crate::game::combat::{CombatRoot, Rank, Team, TickTurn};
+use crate::game::combat::ability::{Ability, AbilityPerform, ChargeEvent, Element, HealEvent, MissEvent};
+use crate::game::combat::ability::flee::FleeEvent;
+use crate::game::combat::ability::hack::HackPerformedEvent;
+use crate::game::combat::pipeline::{DmgBreakdown, DmgResolvedEvent};
+use crate::game::combat::spawn::CharacterSpawnedEvent;
+use crate::game::combat::status::{StatusInflictedEvent, StatusType};
+use crate::game::combat::systems::standard_combat::ExitCombat;
+
+/// The file the log of the last combat is written to.
+pub const COMBAT_LOG_PATH: &str = "combat_log.json";
+
+/// A character as it appears in the log.
+/// Entities are only meaningful within a single run, so the name and team are kept alongside the id.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogCharacter {
+    pub id: u32,
+    pub name: String,
+    pub team: Option<Team>,
+}
+
+impl LogCharacter {
+    pub fn find(
+        parents: &impl GenericReadStorage<Component=Parent>,
+        teams: &impl GenericReadStorage<Component=Team>,
+        characters: &impl GenericReadStorage<Component=Character>,
+        character_ent: Entity,
+    ) -> Self {
+        Self {
+            id: character_ent.id(),
+            name: characters.get(character_ent).map_or("Unknown", |character| character.name()).to_string(),
+            team: Team::get_team(parents, teams, character_ent).map(|(team, _)| team),
+        }
+    }
+
+    /// The character has already lost its `Character` by the time the event is read, so the event carries its name and team.
+    pub fn defeated(event: &CharacterDefeatedEvent) -> Self {
+        Self {
+            id: event.character_ent.id(),
+            name: event.name.to_string(),
+            team: event.team,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.team {
+            Some(Team::Friendly) => format!("{} (ally)", self.name),
+            Some(Team::Enemy) => format!("{} (enemy)", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Something which happened during the combat.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum LogEvent {
+    TurnEnded {
+        ended_team: Team,
+        next_team: Team,
+        actor: Option<LogCharacter>,
+    },
+    AbilityUsed {
+        source: Option<LogCharacter>,
+        ability: String,
+        targets: Vec<LogCharacter>,
+        charge: Option<f32>,
+    },
+    Hit {
+        source: Option<LogCharacter>,
+        target: LogCharacter,
+        element: Element,
+        breakdown: DmgBreakdown,
+    },
+    Miss {
+        source: Option<LogCharacter>,
+        target: LogCharacter,
+    },
+    Heal {
+        source: Option<LogCharacter>,
+        target: LogCharacter,
+        amount: f32,
+    },
+    Charge {
+        source: Option<LogCharacter>,
+        target: LogCharacter,
+        amount: f32,
+    },
+    StatusInflicted {
+        source: Option<LogCharacter>,
+        target: LogCharacter,
+        status: StatusType,
+        turns: usize,
+    },
+    Spawned {
+        character: LogCharacter,
+        rank: Rank,
+    },
+    Hacked {
+        target: LogCharacter,
+    },
+    Defeated {
+        character: LogCharacter,
+        killer: Option<LogCharacter>,
+    },
+    Fled {
+        character: LogCharacter,
+        chance: f32,
+        escaped: bool,
+    },
+}
+
+impl LogEvent {
+    /// Describes the event in a single line, for the log panel.
+    pub fn describe(&self) -> String {
+        let source_prefix = |source: &Option<LogCharacter>| source.as_ref().map_or(String::new(), |source| format!("{}: ", source.describe()));
+        match self {
+            LogEvent::TurnEnded { ended_team, next_team, actor } => match actor {
+                Some(actor) => format!("{} ended its turn", actor.describe()),
+                None => format!("{:?} turn ended, {:?} turn started", ended_team, next_team),
+            },
+            LogEvent::AbilityUsed { source, ability, targets, charge } => {
+                let targets: Vec<String> = targets.iter().map(LogCharacter::describe).collect();
+                let mut text: String = format!("{}used {}", source_prefix(source), ability);
+                if !targets.is_empty() {
+                    text += &format!(" on {}", targets.join(", "));
+                }
+                if let Some(charge) = charge {
+                    text += &format!(" ({:.0} charge)", charge);
+                }
+                text
+            },
+            LogEvent::Hit { source, target, element, breakdown } => {
+                let steps: Vec<String> = breakdown.steps.iter().map(|step| format!("x{:.2} {}", step.multiplier, step.name)).collect();
+                format!("{}hit {} for {:.1} {:?} ({:?}: {:.1} base {})", source_prefix(source), target.describe(), breakdown.total, element, breakdown.outcome, breakdown.base, steps.join(" "))
+            },
+            LogEvent::Miss { source, target } => format!("{}missed {}", source_prefix(source), target.describe()),
+            LogEvent::Heal { source, target, amount } => format!("{}healed {} by {:.1}", source_prefix(source), target.describe(), amount),
+            LogEvent::Charge { source, target, amount } => format!("{}charged {} by {:.1}", source_prefix(source), target.describe(), amount),
+            LogEvent::StatusInflicted { source, target, status, turns } => format!("{}inflicted {:?} on {} for {} turns", source_prefix(source), status, target.describe(), turns),
+            LogEvent::Spawned { character, rank } => format!("{} ({:?}) joined the combat", character.describe(), rank),
+            LogEvent::Hacked { target } => format!("{} was hacked", target.describe()),
+            LogEvent::Defeated { character, killer } => match killer {
+                Some(killer) => format!("{} was defeated by {}", character.describe(), killer.describe()),
+                None => format!("{} was defeated", character.describe()),
+            },
+            LogEvent::Fled { character, chance, escaped } => format!(
+                "{} {} ({:.0}% chance)",
+                character.describe(), if *escaped { "escaped" } else { "failed to escape" }, chance * 100.0,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogEntry {
+    /// The turn count of the combat root when the event happened.
+    pub turn: i32,
+    pub event: LogEvent,
+}
+
+impl LogEntry {
+    pub fn describe(&self) -> String {
+        format!("[{}] {}", self.turn, self.event.describe())
+    }
+}
+
+/// Everything which happened in the combat in progress, in order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CombatLog {
+    pub combat: String,
+    pub seed: u64,
+    pub entries: Vec<LogEntry>,
+    /// Where to write the log once the combat exits. Nothing is written if this is not set.
+    #[serde(skip)]
+    pub path: Option<String>,
+}
+
+impl CombatLog {
+    pub fn new(combat: impl Into<String>, seed: u64) -> Self {
+        Self {
+            combat: combat.into(),
+            seed,
+            entries: Vec::new(),
+            path: None,
+        }
+    }
+
+    pub fn push(&mut self, turn: i32, event: LogEvent) {
+        self.entries.push(LogEntry {
+            turn,
+            event,
+        });
+    }
+
+    pub fn to_json(&self) -> Option<String> {
+        serde_json::to_string_pretty(self).ok()
+    }
+
+    /// Writes the log to disk. This only happens once per combat.
+    pub fn write(&mut self) {
+        if let Some(path) = self.path.take() {
+            if let Some(data) = self.to_json() {
+                if let Err(err) = std::fs::write(&path, data) {
+                    error!("[CombatLog::write] Failed to write {:?}: {:?}", path, err);
+                }
+            }
+        }
+    }
+}
+
+/// Records the events of the combat into the `CombatLog`.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(CombatLogSystemDesc))]
+pub struct CombatLogSystem {
+    #[system_desc(event_channel_reader)]
+    tick_turn_reader: ReaderId<TickTurn>,
+
+    #[system_desc(flagged_storage_reader(AbilityPerform))]
+    perform_reader: ReaderId<ComponentEvent>,
+
+    #[system_desc(event_channel_reader)]
+    dmg_resolved_reader: ReaderId<DmgResolvedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    miss_reader: ReaderId<MissEvent>,
+
+    #[system_desc(event_channel_reader)]
+    heal_reader: ReaderId<HealEvent>,
+
+    #[system_desc(event_channel_reader)]
+    charge_reader: ReaderId<ChargeEvent>,
+
+    #[system_desc(event_channel_reader)]
+    status_reader: ReaderId<StatusInflictedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    spawn_reader: ReaderId<CharacterSpawnedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    hack_reader: ReaderId<HackPerformedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    defeated_reader: ReaderId<CharacterDefeatedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    flee_reader: ReaderId<FleeEvent>,
+}
+
+impl<'s> System<'s> for CombatLogSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, Character>,
+        ReadStorage<'s, Ability>,
+        ReadStorage<'s, AbilityPerform>,
+        ReadStorage<'s, CombatRoot>,
+        Read<'s, EventChannel<TickTurn>>,
+        Read<'s, EventChannel<DmgResolvedEvent>>,
+        Read<'s, EventChannel<MissEvent>>,
+        Read<'s, EventChannel<HealEvent>>,
+        Read<'s, EventChannel<ChargeEvent>>,
+        Read<'s, EventChannel<StatusInflictedEvent>>,
+        Read<'s, EventChannel<CharacterSpawnedEvent>>,
+        Read<'s, EventChannel<HackPerformedEvent>>,
+        Read<'s, EventChannel<CharacterDefeatedEvent>>,
+        Read<'s, EventChannel<FleeEvent>>,
+        Read<'s, Option<ExitCombat>>,
+        Write<'s, CombatLog>,
+    );
+
+    fn run(&mut self, (entities, parents, teams, characters, abilities, performs, roots, tick_turn_events, dmg_resolved_events, miss_events, heal_events, charge_events, status_events, spawn_events, hack_events, defeated_events, flee_events, exit, mut log): Self::SystemData) {
+        let turn: i32 = (&roots).join().next().map_or(0, |root| root.turn_count);
+        let character = |character_ent: Entity| LogCharacter::find(&parents, &teams, &characters, character_ent);
+
+        // Turns are logged with the count of the turn which ended.
+        for event in tick_turn_events.read(&mut self.tick_turn_reader) {
+            log.push(event.count, LogEvent::TurnEnded {
+                ended_team: event.ended_team,
+                next_team: event.next_team,
+                actor: event.actor.map(character),
+            });
+        }
+
+        for event in performs.channel().read(&mut self.perform_reader) {
+            if let ComponentEvent::Inserted(id) = event {
+                let ability_ent: Entity = entities.entity(*id);
+                if let (Some(ability), Some(perform)) = (abilities.get(ability_ent), performs.get(ability_ent)) {
+                    log.push(turn, LogEvent::AbilityUsed {
+                        source: get_root::<Character, _, _>(&parents, &characters, ability_ent).map(|(_, character_ent)| character(character_ent)),
+                        ability: ability.data.name.to_string(),
+                        targets: perform.target.to_vec().into_iter().map(character).collect(),
+                        charge: perform.charge,
+                    });
+                }
+            }
+        }
+
+        for event in dmg_resolved_events.read(&mut self.dmg_resolved_reader) {
+            log.push(turn, LogEvent::Hit {
+                source: event.package.source.map(character),
+                target: character(event.package.target),
+                element: event.package.element,
+                breakdown: event.breakdown.clone(),
+            });
+        }
+
+        for event in miss_events.read(&mut self.miss_reader) {
+            log.push(turn, LogEvent::Miss {
+                source: event.source.map(character),
+                target: character(event.target),
+            });
+        }
+
+        for event in heal_events.read(&mut self.heal_reader) {
+            log.push(turn, LogEvent::Heal {
+                source: event.source.map(character),
+                target: character(event.target),
+                amount: event.heal_value,
+            });
+        }
+
+        for event in charge_events.read(&mut self.charge_reader) {
+            log.push(turn, LogEvent::Charge {
+                source: event.source.map(character),
+                target: character(event.target),
+                amount: event.charge_value,
+            });
+        }
+
+        for event in status_events.read(&mut self.status_reader) {
+            log.push(turn, LogEvent::StatusInflicted {
+                source: event.source.map(character),
+                target: character(event.target),
+                status: event.ty,
+                turns: event.turns,
+            });
+        }
+
+        for event in spawn_events.read(&mut self.spawn_reader) {
+            log.push(turn, LogEvent::Spawned {
+                character: character(event.character_ent),
+                rank: event.action.rank,
+            });
+        }
+
+        for event in hack_events.read(&mut self.hack_reader) {
+            log.push(turn, LogEvent::Hacked {
+                target: character(event.target_ent),
+            });
+        }
+
+        for event in defeated_events.read(&mut self.defeated_reader) {
+            log.push(turn, LogEvent::Defeated {
+                character: LogCharacter::defeated(event),
+                killer: event.killer.map(character),
+            });
+        }
+
+        for event in flee_events.read(&mut self.flee_reader) {
+            log.push(turn, LogEvent::Fled {
+                character: character(event.character_ent),
+                chance: event.chance,
+                escaped: event.escaped,
+            });
+        }
+
+        if exit.is_some() {
+            log.write();
+        }
+    }
+}
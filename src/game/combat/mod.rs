@@ -1,12 +1,15 @@
-use std::any::TypeId;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::default::Default;
 
 use amethyst::{
     core::{
-        math::Isometry3,
+        math::{
+            Isometry3,
+            Vector3,
+        },
         Parent,
         ParentHierarchy,
+        Transform,
     },
     core::{
         SystemBundle,
@@ -20,16 +23,35 @@ use amethyst::{
         },
     },
     Error,
+    shrev::EventChannel,
 };
 
-use crate::core::get_root;
-use crate::game::character::CharacterId;
+use crate::core::{CombatRng, get_root};
+use crate::core::registry::intern;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::game::character::{Character, CharacterData, CharacterId, CharacterStore};
+use crate::game::combat::process::Principal;
+use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
+use crate::game::map::CurrentState;
 use crate::game::combat::ability::{AbilityTarget, AbilityTargetType};
-use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::spawn::{SlotManager, Slots};
 use crate::game::ui::font::GameFonts;
+use crate::game::combat::replay::{CombatReplay, ReplayRecorder};
+use crate::game::combat::initiative::InitiativeQueue;
+use crate::game::combat::log::CombatLog;
+use crate::game::combat::stats::CombatStats;
+use crate::game::combat::difficulty::difficulty_config;
+use crate::game::combat::trigger::Trigger;
+use crate::game::combat::phase::{BossPhase, PhaseChange};
+use crate::game::combat::rules::{RuleParams, RuleState};
 
-#[macro_use]
 pub mod ability;
+pub mod element;
+pub mod difficulty;
+pub mod initiative;
+pub mod log;
+pub mod stats;
+pub mod pipeline;
 pub mod status;
 pub mod spawn;
 pub mod tactical;
@@ -38,8 +60,16 @@ pub mod enemy;
 pub mod process;
 pub mod player;
 pub mod ai;
+pub mod replay;
+pub mod trigger;
+pub mod phase;
+pub mod rules;
 
-pub struct CombatBundle;
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CombatBundle {
+    /// When set, only the combat logic is registered (no camera, HUD or other UI systems).
+    pub headless: bool,
+}
 
 impl<'a, 'b> SystemBundle<'a, 'b> for CombatBundle {
     fn build(
@@ -47,11 +77,6 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CombatBundle {
         world: &mut World,
         builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> Result<(), Error> {
-
-        // Load fonts.
-        let fonts: GameFonts = GameFonts::load(world);
-        world.insert(fonts);
-
         // Controller systems.
         builder.add(
             systems::standard_combat::StandardCombatSystemDesc::default()
@@ -60,18 +85,10 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CombatBundle {
             &[],
         );
         builder.add(
-            crate::game::control::camera::combat::CombatCameraSystemDesc::new(
-                0.1, 0.1, -std::f32::consts::FRAC_PI_2, std::f32::consts::FRAC_PI_2, 0.1, 10.0, 80.0, -std::f32::consts::FRAC_PI_8, 0.0, false,
-            )
+            rules::CombatRuleSystemDesc::default()
                 .build(world),
-            "combat_camera",
-            &[],
-        );
-        builder.add(
-            crate::game::control::camera::combat::CameraDriftSystemDesc::new(10.0) // input camera movement speed
-                .build(world),
-            "camera_drift",
-            &["combat_camera"],
+            "combat_rules",
+            &["standard_combat"],
         );
         builder.add(
             enemy::EnemyControllerSystemDesc::default()
@@ -79,98 +96,27 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CombatBundle {
             "enemy_control",
             &["standard_combat"],
         );
-
-        // HUD systems
-        builder.add(
-            crate::game::combat::player::CombatUiSystemDesc::default()
-                .build(world),
-            "combat_ui",
-            &[],
-        );
-        builder.add(
-            crate::game::combat::player::CrosshairUiControllerSystemDesc::default()
-                .build(world),
-            "crosshair_control_ui",
-            &[],
-        );
-        builder.add(
-            crate::game::ui::hud::UiCharacterBaseSystemDesc::default()
-                .build(world),
-            "character_ui",
-            &["combat_ui"],
-        );
-        builder.add(
-            crate::game::ui::ability::AbilitySelectSystemDesc::new(None)
-                .build(world),
-            "ability_ui",
-            &["character_ui"],
-        );
-        builder.add(
-            crate::game::ui::status::StatusUiSystemDesc::new(HashMap::new(), HashMap::new())
-                .build(world),
-            "status_ui",
-            &["character_ui"],
-        );
-        builder.add(
-            crate::game::ui::crosshair::CrosshairUiSystemDesc::new(HashMap::new())
-                .build(world),
-            "crosshair_ui",
-            &["character_ui"],
-        );
-        builder.add(
-            crate::game::ui::select_character::CharacterSelectSystemDesc::default()
-                .build(world),
-            "select_character",
-            &["combat_ui"],
-        );
-        builder.add(
-            crate::game::ui::description::DescriptionPanelSystemDesc::default()
-                .build(world),
-            "description_ui",
-            &["combat_ui"],
-        );
-        builder.add(
-            systems::target_select::SelectAbilityTargetSystemDesc::default()
-                .build(world),
-            "select_ability_target",
-            &["standard_combat"],
-        );
         builder.add(
-            crate::game::ui::select_all_button::SelectAllUiSystemDesc::default()
-                .build(world),
-            "select_all_button",
-            &["standard_combat"],
-        );
-        builder.add(
-            crate::game::ui::marker::MarkerUiSystemDesc::default()
-                .build(world),
-            "marker",
-            &["standard_combat"],
-        );
-        builder.add(
-            crate::game::ui::turn_notification::TurnNotificationUiSystemDesc::default()
+            replay::ReplayPlaybackSystemDesc::default()
                 .build(world),
-            "turn_notificaion",
+            "replay_playback",
             &["standard_combat"],
         );
+
+        // The hack ability waits for one of the options shown by its UI, so the UI is needed without a display too.
+        let fonts: GameFonts = GameFonts::load(world);
+        world.insert(fonts);
         builder.add(
             crate::game::ui::hack::HackUiSystemDesc::new(None, None)
                 .build(world),
             "hack_ui",
             &["standard_combat"],
         );
-        builder.add(
-            crate::game::ui::banner::BannerUiSystemDesc::default()
-                .build(world),
-            "banner_ui",
-            &["standard_combat"],
-        );
-        builder.add(
-            crate::game::ui::select_rank::SelectRankUiSystemDesc::new(HashMap::new(), None)
-                .build(world),
-            "select_rank_ui",
-            &["standard_combat"],
-        );
+
+        if !self.headless {
+            self.build_ui(world, builder);
+        }
+
         builder.add(
             ai::spacebot::SpacebotAiSystemDesc::default()
                 .build(world),
@@ -204,255 +150,290 @@ impl<'a, 'b> SystemBundle<'a, 'b> for CombatBundle {
             &["enemy_control"],
         );
         builder.add(
-            ability::focused_charge::FocusedChargeAbilitySystemDesc::default()
-                .build(world),
-            "ability_focused_charge",
-            &["enemy_control"],
-        );
-        builder.add(
-            ability::nanobots::NanobotsAbilitySystemDesc::default()
+            ability::data::DataAbilitySystemDesc::default()
                 .build(world),
-            "ability_nanobots",
+            "ability_data",
             &["enemy_control"],
         );
         builder.add(
-            ability::twin_shot::TwinShotAbilitySystemDesc::default()
+            ability::self_destruct::SelfDestructAbilitySystemDesc::default()
                 .build(world),
-            "ability_twin_shot",
+            "ability_self_destruct",
             &["enemy_control"],
         );
         builder.add(
-            ability::snipe::SnipeAbilitySystemDesc::default()
+            ability::flee::FleeAbilitySystemDesc::default()
                 .build(world),
-            "ability_snipe",
+            "ability_flee",
             &["enemy_control"],
         );
+//        builder.add(
+//            ability::upgrade::UpgradeAbilitySystemDesc::default()
+//                .build(world),
+//            "ability_upgrade",
+//            &["enemy_control"],
+//        );
+
+
+
+        builder.add_barrier();
+        // Enemy Ability invocation
         builder.add(
-            ability::barrage::BarrageAbilitySystemDesc::default()
+            enemy::EnemyAbilityInvocationSystemDesc::default()
                 .build(world),
-            "ability_barrage",
+            "enemy_ability_invocation",
             &["enemy_control"],
         );
         builder.add(
-            ability::barrage::HyperBarrageAbilitySystemDesc::default()
+            replay::ReplayRecordSystemDesc::default()
                 .build(world),
-            "ability_hyper_barrage",
-            &["enemy_control"],
+            "replay_record",
+            &["enemy_ability_invocation"],
         );
+
+        // Process systems.
         builder.add(
-            ability::annihilate::AnnihilateAbilitySystemDesc::default()
+            systems::dmg::DmgSystemDesc::default()
                 .build(world),
-            "ability_annihilate",
-            &["enemy_control"],
+            "dmg",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::big_bullet::BigBulletAbilitySystemDesc::default()
+            systems::buff::BuffSystemDesc::default()
                 .build(world),
-            "ability_big_bullet",
-            &["enemy_control"],
+            "buff",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::scrambling_laser::ScramblingLaserAbilitySystemDesc::default()
+            log::CombatLogSystemDesc::default()
                 .build(world),
-            "ability_scrambling_laser",
-            &["enemy_control"],
+            "combat_log",
+            &["dmg", "buff"],
         );
         builder.add(
-            ability::overclock::OverclockAbilitySystemDesc::default()
+            stats::CombatStatsSystemDesc::default()
                 .build(world),
-            "ability_overclock",
-            &["enemy_control"],
+            "combat_stats",
+            &["dmg", "buff"],
         );
         builder.add(
-            ability::overclock::SystemOverclockAbilitySystemDesc::default()
+            spawn::SpawnInvokeSystemDesc::default()
                 .build(world),
-            "ability_system_overclock",
-            &["enemy_control"],
+            "spawn_invoke",
+            &[],
         );
         builder.add(
-            ability::shock::ShockAbilitySystemDesc::default()
+            spawn::SpawnSystemDesc::default()
                 .build(world),
-            "ability_shock",
-            &["enemy_control"],
+            "spawn",
+            &["enemy_control", "spawn_invoke"],
         );
         builder.add(
-            ability::solid_laser::SolidLaserAbilitySystemDesc::default()
+            systems::fire_bullet::FireBulletSystemDesc::new(None)
                 .build(world),
-            "ability_solid_laser",
-            &["enemy_control"],
+            "fire_bullet",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::jammer::JammerAbilitySystemDesc::default()
+            systems::fire_torpedo::FireTorpedoSystemDesc::new(None)
                 .build(world),
-            "ability_jammer",
-            &["enemy_control"],
+            "fire_torpedo",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::nuke::NukeAbilitySystemDesc::default()
+            systems::fire_laser::FireLaserSystemDesc::new(None)
                 .build(world),
-            "ability_nuke",
-            &["enemy_control"],
+            "fire_laser",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::overload::EnergyOverloadAbilitySystemDesc::default()
+            systems::fire_wave::FireWaveSystemDesc::new(None)
                 .build(world),
-            "ability_overload",
-            &["enemy_control"],
+            "fire_wave",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::corrupt::CorruptAbilitySystemDesc::default()
+            systems::kill::CharacterDefeatSystemDesc::default()
                 .build(world),
-            "ability_corrupt",
-            &["enemy_control"],
+            "character_defeat",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::sheild::ReinforceAbilitySystemDesc::default()
+            systems::delay::DelaySystemDesc::default()
                 .build(world),
-            "ability_reinforce",
-            &["enemy_control"],
+            "delay",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::sheild::ShieldAbilitySystemDesc::default()
+            systems::enemy_wave::EnemyWaveSystemDesc::default()
                 .build(world),
-            "ability_shield",
-            &["enemy_control"],
+            "enemy_wave",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::empower::EmpowerAbilitySystemDesc::default()
+            trigger::TriggerSystemDesc::new(None)
                 .build(world),
-            "ability_empower",
-            &["enemy_control"],
+            "trigger",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::focus::FocusAbilitySystemDesc::default()
+            status::StatusSystemDesc::default()
                 .build(world),
-            "ability_focus",
-            &["enemy_control"],
+            "status",
+            &["standard_combat", "enemy_control"],
         );
         builder.add(
-            ability::annihilate::AnnihilatePlusAbilitySystemDesc::default()
+            phase::BossPhaseSystemDesc::default()
                 .build(world),
-            "ability_annihilate_plus",
-            &["enemy_control"],
+            "boss_phase",
+            &["spawn"],
         );
         builder.add(
-            ability::retribution::RetributionAbilitySystemDesc::default()
+            ability::PerformingAbilitySystemDesc::default()
                 .build(world),
-            "ability_retribution",
-            &["enemy_control"],
+            "perform_ability",
+            &[],
         );
         builder.add(
-            ability::self_destruct::SelfDestructAbilitySystemDesc::default()
+            ability::AbilityCooldownSystemDesc::default()
                 .build(world),
-            "ability_self_destruct",
-            &["enemy_control"],
+            "ability_cooldown",
+            &["standard_combat", "enemy_control"],
         );
-//        builder.add(
-//            ability::upgrade::UpgradeAbilitySystemDesc::default()
-//                .build(world),
-//            "ability_upgrade",
-//            &["enemy_control"],
-//        );
 
 
+        Ok(())
+    }
+}
 
-        builder.add_barrier();
-        // Enemy Ability invocation
+impl CombatBundle {
+    /// A combat bundle which can be run without a window, renderer or audio output.
+    pub fn headless() -> Self {
+        Self {
+            headless: true,
+        }
+    }
+
+    /// Registers the camera, HUD and UI systems which are only needed when the combat is displayed.
+    fn build_ui<'a, 'b>(&self, world: &mut World, builder: &mut DispatcherBuilder<'a, 'b>) {
         builder.add(
-            enemy::EnemyAbilityInvocationSystemDesc::default()
+            crate::game::control::camera::combat::CombatCameraSystemDesc::new(
+                0.1, 0.1, -std::f32::consts::FRAC_PI_2, std::f32::consts::FRAC_PI_2, 0.1, 10.0, 80.0, -std::f32::consts::FRAC_PI_8, 0.0, false,
+            )
                 .build(world),
-            "enemy_ability_invocation",
-            &["enemy_control"],
+            "combat_camera",
+            &[],
         );
-
-        // Process systems.
         builder.add(
-            systems::dmg::DmgSystemDesc::default()
+            crate::game::control::camera::combat::CameraDriftSystemDesc::new(10.0) // input camera movement speed
                 .build(world),
-            "dmg",
-            &["standard_combat", "enemy_control"],
+            "camera_drift",
+            &["combat_camera"],
         );
+
+        // HUD systems
         builder.add(
-            systems::buff::BuffSystemDesc::default()
+            crate::game::combat::player::CombatUiSystemDesc::default()
                 .build(world),
-            "buff",
-            &["standard_combat", "enemy_control"],
+            "combat_ui",
+            &[],
         );
         builder.add(
-            spawn::SpawnInvokeSystemDesc::default()
+            crate::game::combat::player::CrosshairUiControllerSystemDesc::default()
                 .build(world),
-            "spawn_invoke",
+            "crosshair_control_ui",
             &[],
         );
         builder.add(
-            spawn::SpawnSystemDesc::default()
+            crate::game::ui::hud::UiCharacterBaseSystemDesc::default()
                 .build(world),
-            "spawn",
-            &["enemy_control", "spawn_invoke"],
+            "character_ui",
+            &["combat_ui"],
         );
         builder.add(
-            systems::fire_bullet::FireBulletSystemDesc::new(None)
+            crate::game::ui::ability::AbilitySelectSystemDesc::new(None)
                 .build(world),
-            "fire_bullet",
-            &["standard_combat", "enemy_control"],
+            "ability_ui",
+            &["character_ui"],
         );
         builder.add(
-            systems::fire_torpedo::FireTorpedoSystemDesc::new(None)
+            crate::game::ui::status::StatusUiSystemDesc::new(HashMap::new(), HashMap::new())
                 .build(world),
-            "fire_torpedo",
-            &["standard_combat", "enemy_control"],
+            "status_ui",
+            &["character_ui"],
         );
         builder.add(
-            systems::fire_laser::FireLaserSystemDesc::new(None)
+            crate::game::ui::crosshair::CrosshairUiSystemDesc::new(HashMap::new())
                 .build(world),
-            "fire_laser",
-            &["standard_combat", "enemy_control"],
+            "crosshair_ui",
+            &["character_ui"],
         );
         builder.add(
-            systems::fire_wave::FireWaveSystemDesc::new(None)
+            crate::game::ui::select_character::CharacterSelectSystemDesc::default()
                 .build(world),
-            "fire_wave",
-            &["standard_combat", "enemy_control"],
+            "select_character",
+            &["combat_ui"],
         );
         builder.add(
-            systems::kill::CharacterDefeatSystemDesc::default()
+            crate::game::ui::description::DescriptionPanelSystemDesc::default()
                 .build(world),
-            "character_defeat",
-            &["standard_combat", "enemy_control"],
+            "description_ui",
+            &["combat_ui"],
         );
         builder.add(
-            systems::delay::DelaySystemDesc::default()
+            systems::target_select::SelectAbilityTargetSystemDesc::default()
                 .build(world),
-            "delay",
-            &["standard_combat", "enemy_control"],
+            "select_ability_target",
+            &["standard_combat"],
         );
         builder.add(
-            systems::enemy_wave::EnemyWaveSystemDesc::default()
+            crate::game::ui::select_all_button::SelectAllUiSystemDesc::default()
                 .build(world),
-            "enemy_wave",
-            &["standard_combat", "enemy_control"],
+            "select_all_button",
+            &["standard_combat"],
         );
         builder.add(
-            status::StatusSystemDesc::default()
+            crate::game::ui::marker::MarkerUiSystemDesc::default()
                 .build(world),
-            "status",
-            &["standard_combat", "enemy_control"],
+            "marker",
+            &["standard_combat"],
         );
         builder.add(
-            systems::earth_combat::EarthCombatSystemDesc::default()
+            crate::game::ui::turn_notification::TurnNotificationUiSystemDesc::default()
                 .build(world),
-            "earth_combat",
-            &["spawn"],
+            "turn_notificaion",
+            &["standard_combat"],
         );
         builder.add(
-            ability::PerformingAbilitySystemDesc::default()
+            crate::game::ui::turn_order::TurnOrderUiSystemDesc::default()
                 .build(world),
-            "perform_ability",
-            &[],
+            "turn_order",
+            &["standard_combat"],
+        );
+        builder.add(
+            crate::game::ui::combat_log::CombatLogUiSystemDesc::default()
+                .build(world),
+            "combat_log_ui",
+            &["standard_combat"],
+        );
+        builder.add(
+            crate::game::ui::results::ResultsUiSystemDesc::default()
+                .build(world),
+            "results_ui",
+            &["standard_combat"],
+        );
+        builder.add(
+            crate::game::ui::banner::BannerUiSystemDesc::default()
+                .build(world),
+            "banner_ui",
+            &["standard_combat"],
+        );
+        builder.add(
+            crate::game::ui::select_rank::SelectRankUiSystemDesc::new(HashMap::new(), None)
+                .build(world),
+            "select_rank_ui",
+            &["standard_combat"],
         );
-
-
-        Ok(())
     }
 }
 
@@ -504,64 +485,182 @@ impl ToString for Difficulty {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Identifies the rule which decides the outcome of a combat, e.g. `"standard"`.
+/// The rules are registered with the `CombatRules` resource, which maps the ids to the rule types through a `TypeRegistry`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CombatSystemId(pub &'static str);
+
+impl CombatSystemId {
+    pub const STANDARD: CombatSystemId = CombatSystemId("standard");
+    pub const SURVIVE: CombatSystemId = CombatSystemId("survive");
+    pub const ESCORT: CombatSystemId = CombatSystemId("escort");
+    pub const BOSS: CombatSystemId = CombatSystemId("boss");
+    pub const KING_OF_THE_HILL: CombatSystemId = CombatSystemId("king_of_the_hill");
+}
+
+impl Serialize for CombatSystemId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CombatSystemId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id: String = String::deserialize(deserializer)?;
+        Ok(CombatSystemId(intern(&id)))
+    }
+}
+
+impl Default for CombatSystemId {
+    fn default() -> Self {
+        CombatSystemId::STANDARD
+    }
+}
+
+/// Determines the order in which characters take their turns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TurnMode {
+    /// The teams take turns, and every character of the team may act during its team's turn.
+    Alternating,
+    /// Each character acts on its own in a queue shared by both teams, so faster characters act more often.
+    /// See `initiative::InitiativeQueue`.
+    Initiative,
+}
+
+impl Default for TurnMode {
+    fn default() -> Self {
+        TurnMode::Alternating
+    }
+}
+
+/// A combat, as it is stored in `assets/combats/<name>.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CombatData {
     /// The name of the combat (internal).
-    name: &'static str,
+    name: String,
+
+    /// The rule which decides when the combat is won or lost.
+    #[serde(default)]
+    system: CombatSystemId,
 
-    /// The system to be used.
-    system: TypeId,
+    /// The parameters of the rule (e.g. the number of turns to survive).
+    #[serde(default)]
+    rule: RuleParams,
+
+    /// The order in which the characters take their turns (only used by the standard combat system).
+    #[serde(default)]
+    turn_mode: TurnMode,
 
     /// The enemy data used by the default combat system.
     /// If a custom system is used, this could be set to `None` if the system spawns the enemies.
     waves: Vec<Wave>,
 
+    /// Scripted events of the combat (e.g. reinforcements which arrive on a certain turn).
+    #[serde(default)]
+    triggers: Vec<Trigger>,
+
+    /// Applied to the player's master drone when it spawns (e.g. to arm it for the final battle).
+    #[serde(default)]
+    master_change: Option<PhaseChange>,
+
     /// The prefab path of the combat data.
-    prefab_path: &'static str,
+    prefab_path: String,
 }
 
 impl CombatData {
-    pub fn basic(name: &'static str, prefab_path: &'static str, waves: Vec<Wave>) -> Self {
+    pub fn basic(name: impl Into<String>, prefab_path: impl Into<String>, waves: Vec<Wave>) -> Self {
         Self {
-            name,
-            system: TypeId::of::<systems::standard_combat::StandardCombatSystem>(),
+            name: name.into(),
+            system: CombatSystemId::STANDARD,
+            rule: RuleParams::default(),
+            turn_mode: TurnMode::Alternating,
             waves,
-            prefab_path,
+            triggers: Vec::new(),
+            master_change: None,
+            prefab_path: prefab_path.into(),
         }
     }
 
     #[inline]
-    pub fn name(&self) -> &'static str {
-        self.name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     #[inline]
-    pub fn prefab_path(&self) -> &'static str {
-        self.prefab_path
+    pub fn prefab_path(&self) -> &str {
+        &self.prefab_path
     }
 
     #[inline]
-    pub fn system(&self) -> TypeId {
+    pub fn system(&self) -> CombatSystemId {
         self.system
     }
 
+    #[inline]
+    pub fn turn_mode(&self) -> TurnMode {
+        self.turn_mode
+    }
+
+    pub fn with_turn_mode(mut self, turn_mode: TurnMode) -> Self {
+        self.turn_mode = turn_mode;
+        self
+    }
+
+    #[inline]
+    pub fn rule(&self) -> &RuleParams {
+        &self.rule
+    }
+
+    pub fn with_rule(mut self, system: CombatSystemId, rule: RuleParams) -> Self {
+        self.system = system;
+        self.rule = rule;
+        self
+    }
+
     #[inline]
     pub fn waves(&self) -> &[Wave] {
         self.waves.as_slice()
     }
 
+    #[inline]
+    pub fn triggers(&self) -> &[Trigger] {
+        self.triggers.as_slice()
+    }
+
+    pub fn with_triggers(mut self, triggers: Vec<Trigger>) -> Self {
+        self.triggers = triggers;
+        self
+    }
+
+    #[inline]
+    pub fn master_change(&self) -> Option<&PhaseChange> {
+        self.master_change.as_ref()
+    }
+
+    pub fn with_master_change(mut self, change: PhaseChange) -> Self {
+        self.master_change = Some(change);
+        self
+    }
+
     #[inline]
     pub fn poster(&self) -> CharacterId {
         self.waves[0].characters[0].character_id
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Wave {
     /// This is where a boss would go.
+    #[serde(default)]
     pub master: Option<CharacterSpawn>,
     /// The 'ordinary' enemies.
     pub characters: Vec<CharacterSpawn>,
+    /// Prevents the player from fleeing while this wave is being fought (e.g. for bosses).
+    #[serde(default)]
+    pub forbid_flee: bool,
+    /// The phases of the boss, in the order in which they are entered.
+    #[serde(default)]
+    pub phases: Vec<BossPhase>,
 }
 
 impl Wave {
@@ -569,17 +668,31 @@ impl Wave {
         Self {
             master: Some(character),
             characters: Vec::new(),
+            forbid_flee: false,
+            phases: Vec::new(),
         }
     }
     pub fn new_simple(characters: Vec<CharacterSpawn>) -> Self {
         Self {
             master: None,
             characters,
+            forbid_flee: false,
+            phases: Vec::new(),
         }
     }
+
+    pub fn with_forbid_flee(mut self) -> Self {
+        self.forbid_flee = true;
+        self
+    }
+
+    pub fn with_phases(mut self, phases: Vec<BossPhase>) -> Self {
+        self.phases = phases;
+        self
+    }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Rank {
     Basic,
     Advanced,
@@ -655,7 +768,7 @@ impl ToString for Rank {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CharacterSpawn {
     pub character_id: CharacterId,
     pub rank: Rank,
@@ -670,7 +783,7 @@ impl CharacterSpawn {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Team {
     Friendly,
     Enemy,
@@ -739,6 +852,8 @@ pub enum CombatState {
     InTurn(Team),
     DoneTurn(Team),
     Victory(Team),
+    /// The player escaped from the combat.
+    Fled,
 }
 
 /// The tag which represents the root object of the combat.
@@ -748,17 +863,34 @@ pub struct CombatRoot {
     pub current_state: CombatState,
     pub turn_count: i32,
     pub current_wave: usize,
+    /// The seed of the `CombatRng` used for this combat.
+    pub seed: u64,
+    /// The turn order of the characters. Only used by the initiative turn mode.
+    pub initiative: InitiativeQueue,
+    /// The indices of the triggers of the combat data which have fired.
+    pub fired_triggers: BTreeSet<usize>,
+    /// The state which the combat's rule keeps.
+    pub rule_state: RuleState,
 }
 
 impl CombatRoot {
-    pub fn new(data: CombatData) -> Self {
+    pub fn new(data: CombatData, seed: u64) -> Self {
         Self {
             data,
             current_state: CombatState::Init,
             turn_count: 0,
             current_wave: 0,
+            seed,
+            initiative: InitiativeQueue::default(),
+            fired_triggers: BTreeSet::new(),
+            rule_state: RuleState::default(),
         }
     }
+
+    /// Whether the player may flee from the current wave.
+    pub fn can_flee(&self) -> bool {
+        self.data.waves().get(self.current_wave).map_or(true, |wave| !wave.forbid_flee)
+    }
     /// Searches for the combat root of the specified entity.
     /// This function is called recursively called until either a `CombatRoot` is located or we reach a top level entity in the hierarchy.
     pub fn get_root<'s>(
@@ -781,8 +913,118 @@ impl Component for CombatRoot {
     type Storage = DenseVecStorage<Self>;
 }
 
+/// The entities which make up a single combat.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CombatInstance {
+    pub root_ent: Entity,
+    pub friendly_ent: Entity,
+    pub enemy_ent: Entity,
+}
+
+impl CombatInstance {
+    /// Creates the combat root and its team entities for the specified combat.
+    /// All randomness within the combat is driven by a `CombatRng` created from `seed`.
+    pub fn create(world: &mut World, data: CombatData, seed: u64) -> Self {
+        world.insert(CombatRng::new(seed));
+        let current_state: CurrentState = world.read_resource::<CurrentState>().clone();
+        world.insert(difficulty_config().profile(current_state.difficulty));
+        world.insert(ReplayRecorder::new(CombatReplay::new(data.name(), seed, current_state)));
+        world.insert(CombatLog::new(data.name(), seed));
+        world.insert(CombatStats::default());
+        world.exec(|(entities, mut parents, mut transforms, mut roots, mut principals, mut slot_managers, mut teams): (Entities, WriteStorage<Parent>, WriteStorage<Transform>, WriteStorage<CombatRoot>, WriteStorage<Principal>, WriteStorage<SlotManager>, WriteStorage<Team>)| {
+            let root_ent = entities.create();
+            let root: CombatRoot = CombatRoot::new(data, seed);
+            let slot_manager: SlotManager = SlotManager::new();
+            let principal: Principal = Principal::new();
+
+            roots.insert(root_ent, root);
+            slot_managers.insert(root_ent, slot_manager);
+            principals.insert(root_ent, principal);
+            transforms.insert(root_ent, Transform::default());
+
+            // Set up teams.
+            let friendly_ent: Entity = entities.create();
+            teams.insert(friendly_ent, Team::Friendly);
+            parents.insert(friendly_ent, Parent { entity: root_ent });
+            // Set transform.
+            let friendly_transform: Transform = Transform::from(Vector3::new(0.0, 0.0, 30.0));
+            transforms.insert(friendly_ent, friendly_transform);
+
+            let enemy_ent: Entity = entities.create();
+            teams.insert(enemy_ent, Team::Enemy);
+            parents.insert(enemy_ent, Parent { entity: root_ent });
+            // Set transform.
+            let mut enemy_transform: Transform = Transform::from(Vector3::new(0.0, 0.0, -30.0));
+            enemy_transform.append_rotation_y_axis(std::f32::consts::PI);
+            transforms.insert(enemy_ent, enemy_transform);
+
+            Self {
+                root_ent,
+                friendly_ent,
+                enemy_ent,
+            }
+        })
+    }
+
+    /// Spawns the master drone and invokes the first wave of the combat.
+    pub fn begin(&self, world: &mut World, data: &CombatData) {
+        let current_state: CurrentState = world.read_resource::<CurrentState>().clone();
+
+        let mut master_data: CharacterData = world.read_resource::<CharacterStore>().data(&CharacterId::MASTER).expect("No master drone data!");
+        master_data.max_health *= current_state.master_health_mul;
+        master_data.max_charge *= current_state.master_charge_mul;
+        master_data.artificial_charge *= current_state.master_charge_mul;
+        // Spawn friendlies.
+        Character::spawn_to_world(
+            world,
+            self.friendly_ent,
+            CharacterId::MASTER,
+            Some(master_data),
+            Rank::Basic,
+            Team::Friendly,
+            0,
+            true,
+        );
+        if let Some(escort) = data.rule().escort {
+            Character::spawn_to_world(
+                world,
+                self.friendly_ent,
+                escort.character_id,
+                None,
+                escort.rank,
+                Team::Friendly,
+                Slots::PRIMARY_1,
+                false,
+            );
+        }
+
+        world.write_resource::<EventChannel<SpawnWaveEvent>>().single_write(
+            SpawnWaveEvent {
+                team_ent: self.enemy_ent,
+                idx: 0,
+                wave: data.waves().get(0).expect("The combat has no waves!").clone(),
+            }
+        );
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct TickTurn {
     pub count: i32,
+    /// The team which has just finished its turn.
+    pub ended_team: Team,
     pub next_team: Team,
+    /// The character which has just finished its turn in the initiative turn mode.
+    /// This is `None` when the whole team takes its turn at once.
+    pub actor: Option<Entity>,
+}
+
+impl TickTurn {
+    /// Checks whether the turn which has just ended belonged to the character.
+    pub fn is_own_turn(&self, character_ent: Entity, team: Team) -> bool {
+        match self.actor {
+            Some(actor) => actor == character_ent,
+            None => team == self.ended_team,
+        }
+    }
 }
@@ -0,0 +1,246 @@
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Handle,
+        Loader,
+        Prefab,
+        RonFormat,
+    },
+    core::{
+        Parent,
+        ParentHierarchy,
+        Transform,
+        math::Vector3,
+    },
+    ecs::prelude::*,
+    log::warn,
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+};
+
+use crate::core::get_root;
+use crate::game::character::{Character, CharacterId, CharacterPrefabData};
+use crate::game::combat::{CharacterSpawn, CombatRoot, Team};
+use crate::game::combat::ability::{Ability, AbilityId, AbilityList, UnassignedAbility};
+use crate::game::combat::ai::AiProfile;
+use crate::game::combat::difficulty::StatScaling;
+use crate::game::combat::process::Principal;
+use crate::game::combat::spawn::{CharacterSpawnedEvent, SlotManager, Slots, SpawnAction, SpawnProcess};
+use crate::game::ui::banner::ShowUiBannerDisplayEvent;
+
+pub const PHASE_BANNER_COLOR: [f32; 4] = [1.0, 0.5, 0.0, 1.0];
+
+/// A change to a character, e.g. when a boss enters a new phase.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhaseChange {
+    /// Replaces every ability of the character.
+    pub abilities: Option<Vec<AbilityId>>,
+    /// Scales the stats of the character.
+    pub stats: Option<StatScaling>,
+    /// Sets the charge of the character to the proportion (from 0 to 1) of its max charge.
+    pub charge: Option<f32>,
+    /// Replaces the model with the prefab, relative to the assets directory.
+    pub prefab: Option<String>,
+    /// Replaces the AI profile of the character.
+    pub ai: Option<AiProfile>,
+    /// Keeps the character where its prefab places it instead of moving it into its slot (e.g. for planets).
+    pub stationary: bool,
+    /// The text of the banner which announces the change.
+    pub banner: Option<String>,
+    /// Summoned into the free slots of the character's team.
+    pub summon: Vec<CharacterSpawn>,
+}
+
+/// A phase of a boss, which is entered once the boss's health falls to the threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossPhase {
+    /// The proportion (from 0 to 1) of the boss's max health. A threshold of 1 is entered as soon as the boss spawns.
+    pub health_below: f32,
+    pub change: PhaseChange,
+}
+
+/// Tracks the phases of a boss which have not been entered yet.
+#[derive(Debug, Clone, Component)]
+pub struct BossPhases {
+    phases: Vec<BossPhase>,
+    /// The models of the phases, which are loaded when the boss spawns so that they are ready when they are needed.
+    prefabs: Vec<Option<Handle<Prefab<CharacterPrefabData>>>>,
+    next: usize,
+}
+
+/// Applies the combat's change to the master drone and moves bosses through their phases.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(BossPhaseSystemDesc))]
+pub struct BossPhaseSystem {
+    #[system_desc(event_channel_reader)]
+    character_spawned_event_reader: ReaderId<CharacterSpawnedEvent>,
+}
+
+impl<'s> System<'s> for BossPhaseSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Character>,
+        WriteStorage<'s, BossPhases>,
+        WriteStorage<'s, AiProfile>,
+        ReadStorage<'s, CombatRoot>,
+        ReadStorage<'s, SlotManager>,
+        ReadStorage<'s, Team>,
+        WriteStorage<'s, Principal>,
+        WriteStorage<'s, Parent>,
+        WriteStorage<'s, SpawnAction>,
+        WriteStorage<'s, SpawnProcess>,
+        WriteStorage<'s, Ability>,
+        WriteStorage<'s, UnassignedAbility>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Handle<Prefab<CharacterPrefabData>>>,
+        ReadExpect<'s, ParentHierarchy>,
+        Read<'s, EventChannel<CharacterSpawnedEvent>>,
+        Write<'s, EventChannel<ShowUiBannerDisplayEvent>>,
+        Read<'s, AbilityList>,
+        ReadExpect<'s, Loader>,
+        Read<'s, AssetStorage<Prefab<CharacterPrefabData>>>,
+    );
+
+    fn run(&mut self, (entities, mut characters, mut boss_phases, mut ai_profiles, roots, slot_managers, teams, mut principals, mut parents, mut spawn_actions, mut spawn_processes, mut abilities, mut unassigned_abilities, mut transforms, mut character_prefabs, hierarchy, character_spawned_events, mut show_banners, ability_list, loader, prefab_storage): Self::SystemData) {
+        let mut changes: Vec<(Entity, PhaseChange, Option<Handle<Prefab<CharacterPrefabData>>>)> = Vec::new();
+        for event in character_spawned_events.read(&mut self.character_spawned_event_reader) {
+            if let Some((root, root_ent)) = get_root::<CombatRoot, _, _>(&parents, &roots, event.character_ent) {
+                if let Some(slot_manager) = slot_managers.get(root_ent) {
+                    if slot_manager.friendly.master() == Some(event.character_ent) && event.action.character_id == CharacterId::MASTER {
+                        if let Some(change) = root.data.master_change() {
+                            let prefab: Option<Handle<Prefab<CharacterPrefabData>>> = change.prefab.as_ref().map(|path| loader.load(path.as_str(), RonFormat, (), &prefab_storage));
+                            changes.push((event.character_ent, change.clone(), prefab));
+                        }
+                    }
+                    if slot_manager.enemy.master() == Some(event.character_ent) {
+                        if let Some(wave) = root.data.waves().get(root.current_wave) {
+                            if !wave.phases.is_empty() {
+                                boss_phases.insert(event.character_ent, BossPhases {
+                                    phases: wave.phases.clone(),
+                                    prefabs: wave.phases.iter()
+                                        .map(|phase| phase.change.prefab.as_ref().map(|path| loader.load(path.as_str(), RonFormat, (), &prefab_storage)))
+                                        .collect(),
+                                    next: 0,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Enter every phase whose threshold the boss has fallen to. Defeated bosses stay in their last phase.
+        for (character_ent, character, boss) in (&entities, &characters, &mut boss_phases).join() {
+            if character.health() <= 0.0 {
+                continue;
+            }
+            while let Some(phase) = boss.phases.get(boss.next) {
+                if character.relative_health() > phase.health_below {
+                    break;
+                }
+                changes.push((character_ent, phase.change.clone(), boss.prefabs[boss.next].clone()));
+                boss.next += 1;
+            }
+        }
+
+        for (character_ent, change, prefab) in changes {
+            if let Some(character) = characters.get_mut(character_ent) {
+                if let Some(stats) = change.stats {
+                    character.scale(&stats);
+                }
+                if let Some(charge) = change.charge {
+                    character.set_charge(character.max_charge() * charge);
+                }
+            }
+
+            if let Some(ids) = change.abilities {
+                for (ability_ent, _, parent) in (&entities, abilities.mask(), &parents).join() {
+                    if parent.entity == character_ent {
+                        entities.delete(ability_ent);
+                    }
+                }
+                for id in ids {
+                    if let Some(ability_data) = ability_list.ability(&id) {
+                        Character::insert_ability(
+                            &entities,
+                            &mut parents,
+                            &mut abilities,
+                            &mut unassigned_abilities,
+                            character_ent,
+                            ability_data,
+                        );
+                    } else {
+                        warn!("[BossPhaseSystem] No ability with id {:?}.", id);
+                    }
+                }
+            }
+
+            if let Some(ai) = change.ai {
+                ai_profiles.insert(character_ent, ai);
+            }
+
+            if let Some(prefab) = prefab {
+                // The parts of the old model (e.g. its weapon slots) are replaced by those of the new one.
+                let children: Vec<Entity> = (&entities, hierarchy.all_children(character_ent)).join()
+                    .map(|(child_ent, _)| child_ent)
+                    .filter(|child_ent| !abilities.contains(*child_ent))
+                    .collect();
+                for child_ent in children {
+                    entities.delete(child_ent);
+                }
+                // Like a freshly spawned character, the new model moves from where its prefab places it back into the slot.
+                let end: Vector3<f32> = transforms.get(character_ent).map_or(Vector3::zeros(), |transform| *transform.translation());
+                transforms.insert(character_ent, Transform::default());
+                character_prefabs.insert(character_ent, prefab);
+                spawn_processes.insert(character_ent, SpawnProcess {
+                    speed: 5.0,
+                    end,
+                });
+            }
+
+            if change.stationary {
+                spawn_processes.remove(character_ent);
+            }
+
+            if let Some((_, root_ent)) = get_root::<CombatRoot, _, _>(&parents, &roots, character_ent) {
+                if let Some(text) = change.banner {
+                    show_banners.single_write(
+                        ShowUiBannerDisplayEvent {
+                            text,
+                            color: PHASE_BANNER_COLOR,
+                            owner: root_ent,
+                        }
+                    );
+                }
+
+                if let (Some((team, team_ent)), Some(slot_manager)) = (Team::get_team(&parents, &teams, character_ent), slot_managers.get(root_ent)) {
+                    // The slots are only occupied once the summons have spawned, so the claimed slots are tracked here.
+                    // Summons which don't fit into the free slots are dropped.
+                    let mut slots: Slots = *slot_manager.for_team(team);
+                    for spawn in change.summon {
+                        if let Some(slot_idx) = slots.find_next(false) {
+                            slots.occupy(slot_idx, character_ent);
+                            Character::invoke_spawn(
+                                &entities,
+                                &mut principals,
+                                &mut parents,
+                                &mut spawn_actions,
+                                team_ent,
+                                spawn.character_id,
+                                None,
+                                spawn.rank,
+                                team,
+                                slot_idx,
+                                false,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use std::fmt::Debug;
+
+use rand::Rng;
+
+use crate::core::CombatRng;
+use crate::game::character::Character;
+use crate::game::combat::Team;
+use crate::game::combat::ability::{DmgPackage, Element, HitDesc};
+use crate::game::combat::difficulty::DifficultyProfile;
+use crate::game::combat::element::ElementConfig;
+
+/// The stages of the damage pipeline, in the order that they are applied.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DmgStage {
+    /// Modifiers of the attack itself (e.g. lasers which scale with the number of drones).
+    Base,
+    /// Modifiers of the attacker (e.g. rank, attack stats and statuses).
+    Output,
+    /// Modifiers of the target (e.g. resistance, armour and statuses).
+    Receive,
+    /// Modifiers which apply to every hit (e.g. difficulty and environment).
+    Global,
+}
+
+/// The outcome of a hit, which is rolled once the hit has landed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum HitOutcome {
+    Normal,
+    Critical,
+    Glancing,
+}
+
+impl Default for HitOutcome {
+    fn default() -> Self {
+        HitOutcome::Normal
+    }
+}
+
+/// The chances and multipliers of each outcome of a hit.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HitChances {
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
+    pub glance_chance: f32,
+    pub glance_multiplier: f32,
+}
+
+impl HitChances {
+    /// Combines the crit stats of the attacker and the attack with the glance stats of the target.
+    /// Hits without an attacking character (e.g. splash damage) are always normal.
+    pub fn new(source: Option<&Character>, target: &Character, hit: &HitDesc) -> Self {
+        match source {
+            Some(source) => {
+                let crit_chance: f32 = (source.crit_chance() + hit.crit_chance).max(0.0).min(1.0);
+                Self {
+                    crit_chance,
+                    crit_multiplier: source.crit_multiplier() + hit.crit_multiplier,
+                    // A hit can not be both critical and glancing, so crits take priority.
+                    glance_chance: target.glance_chance().max(0.0).min(1.0 - crit_chance),
+                    glance_multiplier: target.glance_multiplier(),
+                }
+            },
+            None => Self {
+                crit_chance: 0.0,
+                crit_multiplier: 1.0,
+                glance_chance: 0.0,
+                glance_multiplier: 1.0,
+            },
+        }
+    }
+
+    /// Uses a single roll, so that the chances of each outcome are exact.
+    pub fn roll(&self, rng: &mut CombatRng) -> HitOutcome {
+        if self.crit_chance <= 0.0 && self.glance_chance <= 0.0 {
+            return HitOutcome::Normal;
+        }
+        let value: f32 = rng.gen_range(0.0, 1.0);
+        if value < self.crit_chance {
+            HitOutcome::Critical
+        } else if value < self.crit_chance + self.glance_chance {
+            HitOutcome::Glancing
+        } else {
+            HitOutcome::Normal
+        }
+    }
+
+    pub fn multiplier(&self, outcome: HitOutcome) -> f32 {
+        match outcome {
+            HitOutcome::Normal => 1.0,
+            HitOutcome::Critical => self.crit_multiplier,
+            HitOutcome::Glancing => self.glance_multiplier,
+        }
+    }
+
+    /// The average multiplier of a hit, taking the chance of each outcome into account.
+    pub fn expected_multiplier(&self) -> f32 {
+        let normal_chance: f32 = 1.0 - self.crit_chance - self.glance_chance;
+        normal_chance + self.crit_chance * self.crit_multiplier + self.glance_chance * self.glance_multiplier
+    }
+}
+
+/// Rolls the variance multiplier of a hit. Attacks without variance do not use the rng.
+pub fn roll_variance(rng: &mut CombatRng, hit: &HitDesc) -> f32 {
+    let variance: f32 = hit.variance.max(0.0).min(1.0);
+    if variance > 0.0 {
+        1.0 + rng.gen_range(-variance, variance)
+    } else {
+        1.0
+    }
+}
+
+/// A single multiplier which was applied to the damage.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DmgStep {
+    pub stage: DmgStage,
+    pub name: String,
+    pub multiplier: f32,
+    /// The damage after this step was applied.
+    pub dmg: f32,
+}
+
+/// The resolved damage of a hit, along with every step which contributed to it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DmgBreakdown {
+    pub base: f32,
+    pub outcome: HitOutcome,
+    pub steps: Vec<DmgStep>,
+    pub total: f32,
+}
+
+impl DmgBreakdown {
+    pub fn new(base: f32, outcome: HitOutcome) -> Self {
+        Self {
+            base,
+            outcome,
+            steps: Vec::new(),
+            total: base,
+        }
+    }
+
+    /// Neutral multipliers are not recorded, to keep the breakdown readable.
+    pub fn apply(&mut self, stage: DmgStage, name: impl Into<String>, multiplier: f32) {
+        if multiplier != 1.0 {
+            self.total *= multiplier;
+            self.steps.push(DmgStep {
+                stage,
+                name: name.into(),
+                multiplier,
+                dmg: self.total,
+            });
+        }
+    }
+
+    /// The combined multiplier of every step in the stage.
+    pub fn multiplier(&self, stage: DmgStage) -> f32 {
+        self.steps.iter().filter(|step| step.stage == stage).map(|step| step.multiplier).product()
+    }
+
+    /// Describes the breakdown line by line, e.g. for a tooltip.
+    pub fn describe(&self) -> String {
+        let mut lines: Vec<String> = vec![format!("{:.1} base", self.base)];
+        for step in self.steps.iter() {
+            lines.push(format!("x{:.2} {}", step.multiplier, step.name));
+        }
+        lines.push(format!("= {:.1}", self.total));
+        lines.join("\n")
+    }
+}
+
+/// The outcome of a package which was inflicted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DmgResult {
+    pub breakdown: DmgBreakdown,
+    /// Packages returned by the post-hit hooks, which should be inflicted afterwards.
+    pub follow_ups: Vec<DmgPackage>,
+}
+
+/// Sent whenever a package is inflicted, so that the breakdown can be logged or shown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DmgResolvedEvent {
+    pub package: DmgPackage,
+    pub breakdown: DmgBreakdown,
+}
+
+/// Everything the damage modifiers know about a hit.
+pub struct DmgContext<'a> {
+    pub package: &'a DmgPackage,
+    pub source: Option<&'a Character>,
+    pub source_team: Option<Team>,
+    pub target: &'a Character,
+    pub element_config: &'a ElementConfig,
+    pub difficulty: &'a DifficultyProfile,
+    pub chances: HitChances,
+    pub outcome: HitOutcome,
+    /// The rolled variance multiplier of the hit.
+    pub variance: f32,
+}
+
+/// A modifier which is registered with the `DmgPipeline`.
+pub trait DmgModifier: Debug + Send + Sync {
+    /// Called before the damage is inflicted. The modifier applies its multipliers to the breakdown.
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown);
+
+    /// Called once the damage has been inflicted.
+    /// Any packages which are returned are inflicted afterwards (e.g. reflected damage).
+    fn post_hit(&self, _ctx: &DmgContext, _breakdown: &DmgBreakdown) -> Vec<DmgPackage> {
+        Vec::new()
+    }
+}
+
+/// Resolves the damage of each `DmgPackage` by applying the registered modifiers in order of their stage.
+/// Modifiers of the same stage are applied in the order they were registered.
+#[derive(Debug)]
+pub struct DmgPipeline {
+    modifiers: Vec<(DmgStage, Box<dyn DmgModifier>)>,
+}
+
+impl Default for DmgPipeline {
+    fn default() -> Self {
+        let mut pipeline: DmgPipeline = DmgPipeline { modifiers: Vec::new() };
+        pipeline.register(DmgStage::Base, ScaleModifier);
+        pipeline.register(DmgStage::Base, VarianceModifier);
+        pipeline.register(DmgStage::Output, AttackModifier);
+        pipeline.register(DmgStage::Output, StatusOutputModifier);
+        pipeline.register(DmgStage::Output, CriticalModifier);
+        pipeline.register(DmgStage::Receive, ResistanceModifier);
+        pipeline.register(DmgStage::Receive, ArmourModifier);
+        pipeline.register(DmgStage::Receive, StatusReceiveModifier);
+        pipeline.register(DmgStage::Receive, GlancingModifier);
+        pipeline.register(DmgStage::Global, DifficultyModifier);
+        pipeline
+    }
+}
+
+impl DmgPipeline {
+    pub fn register(&mut self, stage: DmgStage, modifier: impl DmgModifier + 'static) {
+        let idx: usize = self.modifiers.iter().position(|(other, _)| *other > stage).unwrap_or_else(|| self.modifiers.len());
+        self.modifiers.insert(idx, (stage, Box::new(modifier)));
+    }
+
+    pub fn resolve(&self, ctx: &DmgContext) -> DmgBreakdown {
+        let mut breakdown: DmgBreakdown = DmgBreakdown::new(ctx.package.power, ctx.outcome);
+        for (stage, modifier) in self.modifiers.iter() {
+            modifier.pre_hit(*stage, ctx, &mut breakdown);
+        }
+        breakdown
+    }
+
+    /// The average damage of a hit which lands, taking the chance of each outcome into account.
+    /// The outcome and variance of the context are ignored.
+    pub fn expected(&self, ctx: &DmgContext) -> f32 {
+        let normal: DmgContext = DmgContext {
+            outcome: HitOutcome::Normal,
+            variance: 1.0,
+            ..*ctx
+        };
+        self.resolve(&normal).total * ctx.chances.expected_multiplier()
+    }
+
+    pub fn post_hit(&self, ctx: &DmgContext, breakdown: &DmgBreakdown) -> Vec<DmgPackage> {
+        self.modifiers.iter().flat_map(|(_, modifier)| modifier.post_hit(ctx, breakdown)).collect()
+    }
+}
+
+/// Applies the scale of the package itself.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ScaleModifier;
+
+impl DmgModifier for ScaleModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        breakdown.apply(stage, "Attack scale", ctx.package.scale);
+    }
+}
+
+/// Applies the rolled variance of the attack.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct VarianceModifier;
+
+impl DmgModifier for VarianceModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        breakdown.apply(stage, "Variance", ctx.variance);
+    }
+}
+
+/// Applies the attack stats, base damage and rank of the attacker.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct AttackModifier;
+
+impl DmgModifier for AttackModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        if let Some(source) = ctx.source {
+            breakdown.apply(stage, format!("{:?} attack", ctx.package.element), source.attack().element_inflict(ctx.package.element));
+            breakdown.apply(stage, "Base damage", source.base_dmg());
+            breakdown.apply(stage, "Rank", source.rank().base_multiplier());
+        }
+    }
+}
+
+/// Applies the statuses of the attacker (e.g. Empower).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct StatusOutputModifier;
+
+impl DmgModifier for StatusOutputModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        if let Some(source) = ctx.source {
+            for effect in source.status_effects() {
+                breakdown.apply(stage, effect.def.name.clone(), effect.modifiers().dmg_output);
+            }
+        }
+    }
+}
+
+/// Applies the crit multiplier of critical hits.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CriticalModifier;
+
+impl DmgModifier for CriticalModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        if ctx.outcome == HitOutcome::Critical {
+            breakdown.apply(stage, "Critical hit", ctx.chances.multiplier(HitOutcome::Critical));
+        }
+    }
+}
+
+/// Applies the resistance of the target, and the type-effectiveness of the element against it.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ResistanceModifier;
+
+impl DmgModifier for ResistanceModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        let element: Element = ctx.package.element;
+        breakdown.apply(stage, format!("{:?} resistance", element), ctx.target.resistance().element_receive(element));
+        breakdown.apply(stage, "Effectiveness", ctx.element_config.effectiveness(element, ctx.target.element()));
+    }
+}
+
+/// Applies the armour of the target, less the penetration of the attacker.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ArmourModifier;
+
+impl DmgModifier for ArmourModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        let penetration: f32 = ctx.source.map_or(0.0, |source| source.penetration());
+        let armour: f32 = ctx.target.armour() * (1.0 - penetration.max(0.0).min(1.0));
+        breakdown.apply(stage, "Armour", 1.0 - armour);
+    }
+}
+
+/// Applies the statuses of the target (e.g. Defend).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct StatusReceiveModifier;
+
+impl DmgModifier for StatusReceiveModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        for effect in ctx.target.status_effects() {
+            breakdown.apply(stage, effect.def.name.clone(), effect.modifiers().dmg_receive);
+        }
+    }
+}
+
+/// Applies the glance multiplier of glancing blows.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GlancingModifier;
+
+impl DmgModifier for GlancingModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        if ctx.outcome == HitOutcome::Glancing {
+            breakdown.apply(stage, "Glancing blow", ctx.chances.multiplier(HitOutcome::Glancing));
+        }
+    }
+}
+
+/// Scales the damage dealt by the enemy team with the `DifficultyProfile`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DifficultyModifier;
+
+impl DmgModifier for DifficultyModifier {
+    fn pre_hit(&self, stage: DmgStage, ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+        if ctx.source_team == Some(Team::Enemy) {
+            breakdown.apply(stage, "Difficulty", ctx.difficulty.dmg_multiplier(ctx.source_team));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use amethyst::ecs::prelude::*;
+
+    use super::*;
+    use crate::game::character::{CharacterData, CharacterId};
+    use crate::game::combat::Rank;
+
+    /// Records the order the modifiers are applied in.
+    #[derive(Debug)]
+    struct NamedModifier(&'static str);
+
+    impl DmgModifier for NamedModifier {
+        fn pre_hit(&self, stage: DmgStage, _ctx: &DmgContext, breakdown: &mut DmgBreakdown) {
+            breakdown.apply(stage, self.0, 2.0);
+        }
+    }
+
+    fn package(target: Entity) -> DmgPackage {
+        DmgPackage {
+            source: None,
+            target,
+            power: 10.0,
+            element: Element::Kinetic,
+            status: None,
+            scale: 1.0,
+            hit: HitDesc::exact(),
+        }
+    }
+
+    fn context<'a>(package: &'a DmgPackage, source: &'a Character, target: &'a Character, element_config: &'a ElementConfig, difficulty: &'a DifficultyProfile, outcome: HitOutcome) -> DmgContext<'a> {
+        DmgContext {
+            package,
+            source: Some(source),
+            source_team: Some(Team::Friendly),
+            target,
+            element_config,
+            difficulty,
+            chances: HitChances::new(Some(source), target, &package.hit),
+            outcome,
+            variance: 1.0,
+        }
+    }
+
+    fn character(team: Team) -> Character {
+        Character::new(CharacterData::neutral(team, 100.0, 1.0), CharacterId("test"), Rank::Basic)
+    }
+
+    #[test]
+    fn modifiers_are_applied_in_order_of_their_stage() {
+        let mut world = World::new();
+        let target_ent: Entity = world.create_entity().build();
+        let package: DmgPackage = package(target_ent);
+        let (source, target) = (character(Team::Friendly), character(Team::Enemy));
+        let (element_config, difficulty) = (ElementConfig::default(), DifficultyProfile::default());
+        let ctx: DmgContext = context(&package, &source, &target, &element_config, &difficulty, HitOutcome::Normal);
+
+        let mut pipeline: DmgPipeline = DmgPipeline { modifiers: Vec::new() };
+        pipeline.register(DmgStage::Global, NamedModifier("global"));
+        pipeline.register(DmgStage::Receive, NamedModifier("receive"));
+        pipeline.register(DmgStage::Base, NamedModifier("base"));
+        pipeline.register(DmgStage::Receive, NamedModifier("receive 2"));
+        pipeline.register(DmgStage::Output, NamedModifier("output"));
+
+        let breakdown: DmgBreakdown = pipeline.resolve(&ctx);
+        let names: Vec<&str> = breakdown.steps.iter().map(|step| step.name.as_str()).collect();
+        assert_eq!(names, vec!["base", "output", "receive", "receive 2", "global"]);
+        assert_eq!(breakdown.multiplier(DmgStage::Receive), 4.0);
+        assert_eq!(breakdown.total, 320.0);
+    }
+
+    #[test]
+    fn neutral_multipliers_are_not_recorded() {
+        let mut breakdown: DmgBreakdown = DmgBreakdown::new(10.0, HitOutcome::Normal);
+        breakdown.apply(DmgStage::Base, "Neutral", 1.0);
+        breakdown.apply(DmgStage::Output, "Double", 2.0);
+        assert_eq!(breakdown.steps.len(), 1);
+        assert_eq!(breakdown.steps[0].dmg, 20.0);
+        assert_eq!(breakdown.total, 20.0);
+    }
+
+    #[test]
+    fn critical_hits_apply_the_crit_multiplier() {
+        let mut world = World::new();
+        let target_ent: Entity = world.create_entity().build();
+        let package: DmgPackage = package(target_ent);
+        let (source, target) = (character(Team::Friendly), character(Team::Enemy));
+        let (element_config, difficulty) = (ElementConfig::default(), DifficultyProfile::default());
+        let pipeline: DmgPipeline = DmgPipeline::default();
+
+        let normal: DmgBreakdown = pipeline.resolve(&context(&package, &source, &target, &element_config, &difficulty, HitOutcome::Normal));
+        assert_eq!(normal.total, 10.0);
+        assert!(normal.steps.is_empty());
+
+        let critical: DmgBreakdown = pipeline.resolve(&context(&package, &source, &target, &element_config, &difficulty, HitOutcome::Critical));
+        assert_eq!(critical.total, 15.0);
+        assert_eq!(critical.multiplier(DmgStage::Output), 1.5);
+    }
+}
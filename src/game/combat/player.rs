@@ -37,6 +37,7 @@ use crate::game::ui::status::UiStatus;
 use crate::game::ui::UiDisengageEvent;
 use crate::game::combat::ability::charge::ChargeAbility;
 use crate::game::map::CurrentState;
+use crate::game::combat::replay::ReplayPlayback;
 
 #[derive(Debug, SystemDesc, new)]
 #[system_desc(name(CombatUiSystemDesc))]
@@ -79,9 +80,10 @@ impl<'s> System<'s> for CombatUiSystem {
         Write<'s, EventChannel<UiDisengageEvent>>,
         Write<'s, EventChannel<UiAbilitySelectEvent>>,
         Write<'s, EventChannel<SelectAbilityTargetEvent>>,
+        Read<'s, ReplayPlayback>,
     );
 
-    fn run(&mut self, (entities, mut principals, roots, mut parents, teams, transforms, mut characters, abilities, mut ability_invokes, mut ui_bases, mut ui_character_bases, mut ui_statuses, mut ui_crosshairs, mut descriptions, slot_managers, charge_abilities, select_ability_targets, hierarchy, mut target_point, current_state, spawned_events, mut show_abilities_events, input_events, mut disengage_events, mut ability_select_events, mut select_ability_target_events): Self::SystemData) {
+    fn run(&mut self, (entities, mut principals, roots, mut parents, teams, transforms, mut characters, abilities, mut ability_invokes, mut ui_bases, mut ui_character_bases, mut ui_statuses, mut ui_crosshairs, mut descriptions, slot_managers, charge_abilities, select_ability_targets, hierarchy, mut target_point, current_state, spawned_events, mut show_abilities_events, input_events, mut disengage_events, mut ability_select_events, mut select_ability_target_events, playback): Self::SystemData) {
         for event in input_events.read(&mut self.input_reader) {
             match event {
                 InputEvent::MouseButtonPressed(btn) => {
@@ -92,7 +94,7 @@ impl<'s> System<'s> for CombatUiSystem {
                 InputEvent::MouseWheelMoved(_) => {
                     disengage_events.single_write(UiDisengageEvent::NegatingInput);
                 }
-                InputEvent::KeyPressed { key_code: VirtualKeyCode::Space, .. } => {
+                InputEvent::KeyPressed { key_code: VirtualKeyCode::Space, .. } if !playback.is_active() => {
                     if let Some(character_ent) = target_point.1 {
                         if let Some((root, _)) = get_root::<CombatRoot, _, _>(&parents, &roots, character_ent) {
                             if root.current_state == CombatState::InTurn(Team::Friendly) {
@@ -102,6 +104,7 @@ impl<'s> System<'s> for CombatUiSystem {
                                             ability_select_events.single_write(
                                                 UiAbilitySelectEvent {
                                                     ability_ent,
+                                                    charge: None,
                                                 }
                                             );
                                             break;
@@ -148,11 +151,18 @@ impl<'s> System<'s> for CombatUiSystem {
         }
 
         for event in ability_select_events.read(&mut self.ability_select_reader) {
+            // Replays select abilities on the player's behalf.
+            if playback.is_active() {
+                continue;
+            }
             let ability_ent = event.ability_ent;
             if let Some(ability) = abilities.get(ability_ent) {
                 if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
-                    if Character::can_take_turn(&characters, character_ent, ability.data.charge.rated_charge()) {
-                        ability_invokes.insert(ability_ent, AbilityInvoke::default());
+                    let charge: f32 = event.charge.map_or_else(|| ability.data.charge.rated_charge(), |charge| ability.data.charge.clamp(charge));
+                    if Character::can_take_turn(&characters, character_ent, charge) {
+                        ability_invokes.insert(ability_ent, AbilityInvoke {
+                            charge: event.charge,
+                        });
                         if let Some(_) = ability.data.target_info {
                             select_ability_target_events.single_write(SelectAbilityTargetEvent {
                                 ability_ent,
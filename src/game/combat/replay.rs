@@ -0,0 +1,513 @@
+use amethyst::{
+    core::{
+        Parent,
+        ParentHierarchy,
+    },
+    ecs::prelude::*,
+    log::error,
+    prelude::SystemDesc,
+    shrev::EventChannel,
+};
+
+use crate::core::{CombatRng, get_root, roll};
+use crate::game::character::{Character, CharacterId, CharacterStore};
+use crate::game::combat::{CharacterSpawn, CombatRoot, CombatState, Rank, Team};
+use crate::game::combat::ability::{Ability, AbilityId, AbilityPerform, AbilityTarget, perform_ability};
+use crate::game::combat::ability::spawn::SpawnAbility;
+use crate::game::combat::process::Principal;
+use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
+use crate::game::combat::systems::standard_combat::ExitCombat;
+use crate::game::ui::hack::{HackSelectedEvent, UiHackData};
+use crate::game::ui::select_rank::RankSelectedEvent;
+use crate::game::map::CurrentState;
+
+/// The file the replay of the last combat is written to.
+pub const REPLAY_PATH: &str = "replay.json";
+
+/// Identifies a character by its slot, since entities differ between runs.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySlot {
+    pub team: Team,
+    pub idx: usize,
+}
+
+impl ReplaySlot {
+    pub fn find(slot_manager: &SlotManager, character_ent: Entity) -> Option<Self> {
+        for team in [Team::Friendly, Team::Enemy].iter() {
+            for (idx, entity_opt) in slot_manager.for_team(*team).occupied().iter().enumerate() {
+                if *entity_opt == Some(character_ent) {
+                    return Some(Self {
+                        team: *team,
+                        idx,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    pub fn resolve(&self, slot_manager: &SlotManager) -> Option<Entity> {
+        slot_manager.for_team(self.team).character(self.idx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplayTarget {
+    Multi(Vec<ReplaySlot>),
+    Single(ReplaySlot),
+}
+
+impl ReplayTarget {
+    pub fn find(slot_manager: &SlotManager, target: &AbilityTarget) -> Option<Self> {
+        match target {
+            AbilityTarget::Multi(entities) => {
+                Some(ReplayTarget::Multi(entities.iter().filter_map(|entity| ReplaySlot::find(slot_manager, *entity)).collect()))
+            }
+            AbilityTarget::Single(entity) => {
+                Some(ReplayTarget::Single(ReplaySlot::find(slot_manager, *entity)?))
+            }
+        }
+    }
+
+    pub fn resolve(&self, slot_manager: &SlotManager) -> Option<AbilityTarget> {
+        match self {
+            ReplayTarget::Multi(slots) => {
+                Some(AbilityTarget::Multi(slots.iter().filter_map(|slot| slot.resolve(slot_manager)).collect()))
+            }
+            ReplayTarget::Single(slot) => {
+                Some(AbilityTarget::Single(slot.resolve(slot_manager)?))
+            }
+        }
+    }
+}
+
+/// The character and rank chosen for a spawn ability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySpawn {
+    pub character: CharacterId,
+    pub rank: Rank,
+}
+
+/// The option chosen for a hack ability.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayHack {
+    pub charge: f32,
+    pub chance: f32,
+    pub succeeded: bool,
+}
+
+/// A single ability which was performed during the combat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayInput {
+    pub turn: i32,
+    /// The number of values drawn from the `CombatRng` before the ability was performed.
+    pub draws: u64,
+    pub source: ReplaySlot,
+    pub ability: AbilityId,
+    pub target: ReplayTarget,
+    pub spawn: Option<ReplaySpawn>,
+    pub hack: Option<ReplayHack>,
+    /// The charge invested in `AbilityCharge::Range` abilities.
+    #[serde(default)]
+    pub charge: Option<f32>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayWave {
+    pub turn: i32,
+    pub idx: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error(display = "could not access the replay file: {}", _0)]
+    Io(std::io::Error),
+    #[error(display = "invalid replay: {}", _0)]
+    Json(serde_json::Error),
+}
+
+/// Everything needed to play a combat back exactly as it happened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CombatReplay {
+    pub combat: String,
+    pub seed: u64,
+    /// The campaign state at the start of the combat, which determines the master's health and the difficulty.
+    pub state: CurrentState,
+    pub inputs: Vec<ReplayInput>,
+    pub waves: Vec<ReplayWave>,
+}
+
+impl CombatReplay {
+    pub fn new(combat: impl Into<String>, seed: u64, state: CurrentState) -> Self {
+        Self {
+            combat: combat.into(),
+            seed,
+            state,
+            inputs: Vec::new(),
+            waves: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, ReplayError> {
+        let data: String = std::fs::read_to_string(path).map_err(ReplayError::Io)?;
+        serde_json::from_str(&data).map_err(ReplayError::Json)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), ReplayError> {
+        let data: String = serde_json::to_string(self).map_err(ReplayError::Json)?;
+        std::fs::write(path, data).map_err(ReplayError::Io)
+    }
+}
+
+/// The replay of the combat in progress.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    pub replay: CombatReplay,
+    /// Where to write the replay once the combat exits. Nothing is written if this is not set.
+    pub path: Option<String>,
+}
+
+impl ReplayRecorder {
+    pub fn new(replay: CombatReplay) -> Self {
+        Self {
+            replay,
+            path: None,
+        }
+    }
+
+    /// Writes the replay to disk. This only happens once per combat.
+    pub fn write(&mut self) {
+        if let Some(path) = self.path.take() {
+            if let Err(err) = self.replay.save(&path) {
+                error!("[ReplayRecorder::write] Failed to write {:?}: {}", path, err);
+            }
+        }
+    }
+
+    /// The most recent input for the specified ability, if nothing has been performed since.
+    fn last_input_mut(&mut self, source: ReplaySlot, ability: AbilityId) -> Option<&mut ReplayInput> {
+        let input = self.replay.inputs.last_mut()?;
+        if input.source == source && input.ability == ability {
+            Some(input)
+        } else {
+            None
+        }
+    }
+}
+
+/// The replay being played back, if any.
+/// While active, the replay takes the place of both the player's input and the AI.
+/// Playback stops as soon as the combat diverges from the recording, since everything after that point would be wrong.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayPlayback {
+    replay: Option<CombatReplay>,
+    next_input: usize,
+    next_wave: usize,
+    /// The hack ability (and its target) waiting for the recorded option to be selected.
+    pending_hack: Option<(Entity, Entity, ReplayHack)>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: CombatReplay) -> Self {
+        Self {
+            replay: Some(replay),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Stops the playback, handing control back to the player and the AI.
+    fn diverge(&mut self, reason: String) {
+        error!("[ReplayPlayback] Stopped playback: {}", reason);
+        self.replay = None;
+        self.pending_hack = None;
+    }
+
+    fn next_input(&self) -> Option<ReplayInput> {
+        self.replay.as_ref()?.inputs.get(self.next_input).cloned()
+    }
+
+    fn take_wave(&mut self) -> Option<ReplayWave> {
+        let wave: ReplayWave = *self.replay.as_ref()?.waves.get(self.next_wave)?;
+        self.next_wave += 1;
+        Some(wave)
+    }
+}
+
+/// Marks an ability whose current perform has already been recorded.
+#[derive(Debug, Copy, Clone, Default, Component)]
+pub struct ReplayRecordedTag;
+
+/// Finds the slot of the character which owns an ability, along with the current turn.
+fn describe_ability<'s, 'a>(
+    parents: &ReadStorage<'s, Parent>,
+    characters: &ReadStorage<'s, Character>,
+    roots: &ReadStorage<'s, CombatRoot>,
+    slot_managers: &'a ReadStorage<'s, SlotManager>,
+    ability_ent: Entity,
+) -> Option<(i32, ReplaySlot, &'a SlotManager)> {
+    let (root, _) = get_root::<CombatRoot, _, _>(parents, roots, ability_ent)?;
+    let (slot_manager, _) = get_root::<SlotManager, _, _>(parents, slot_managers, ability_ent)?;
+    let (_, character_ent) = get_root::<Character, _, _>(parents, characters, ability_ent)?;
+    let source: ReplaySlot = ReplaySlot::find(slot_manager, character_ent)?;
+    Some((root.turn_count, source, slot_manager))
+}
+
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(ReplayRecordSystemDesc))]
+pub struct ReplayRecordSystem {
+    #[system_desc(event_channel_reader)]
+    wave_reader: ReaderId<SpawnWaveEvent>,
+
+    #[system_desc(event_channel_reader)]
+    rank_selected_reader: ReaderId<RankSelectedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    hack_selected_reader: ReaderId<HackSelectedEvent>,
+}
+
+impl<'s> System<'s> for ReplayRecordSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Character>,
+        ReadStorage<'s, Ability>,
+        ReadStorage<'s, AbilityPerform>,
+        ReadStorage<'s, CombatRoot>,
+        ReadStorage<'s, SlotManager>,
+        WriteStorage<'s, ReplayRecordedTag>,
+        Read<'s, CharacterStore>,
+        Read<'s, CombatRng>,
+        Read<'s, Option<ExitCombat>>,
+        Read<'s, EventChannel<SpawnWaveEvent>>,
+        Read<'s, EventChannel<RankSelectedEvent>>,
+        Read<'s, EventChannel<HackSelectedEvent>>,
+        Write<'s, ReplayRecorder>,
+    );
+
+    fn run(&mut self, (entities, parents, characters, abilities, performs, roots, slot_managers, mut recorded_tags, character_store, rng, exit, wave_events, rank_selected_events, hack_selected_events, mut recorder): Self::SystemData) {
+        // Forget abilities which have finished performing, so that they are recorded again next time.
+        let mut finished: Vec<Entity> = Vec::new();
+        for (entity, _, _) in (&entities, recorded_tags.mask(), !performs.mask().clone()).join() {
+            finished.push(entity);
+        }
+        for entity in finished {
+            recorded_tags.remove(entity);
+        }
+
+        let mut recorded: Vec<Entity> = Vec::new();
+        for (entity, ability, perform, _) in (&entities, &abilities, &performs, !recorded_tags.mask().clone()).join() {
+            if let Some((turn, source, slot_manager)) = describe_ability(&parents, &characters, &roots, &slot_managers, entity) {
+                if let Some(target) = ReplayTarget::find(slot_manager, &perform.target) {
+                    recorder.replay.inputs.push(ReplayInput {
+                        turn,
+                        draws: rng.draws(),
+                        source,
+                        ability: ability.data.id,
+                        target,
+                        spawn: None,
+                        hack: None,
+                        charge: perform.charge,
+                    });
+                }
+            }
+            recorded.push(entity);
+        }
+        for entity in recorded {
+            recorded_tags.insert(entity, ReplayRecordedTag);
+        }
+
+        for event in rank_selected_events.read(&mut self.rank_selected_reader) {
+            if let (Some(selection), Some(ability)) = (event.selection, abilities.get(event.owner)) {
+                if let Some((turn, source, _)) = describe_ability(&parents, &characters, &roots, &slot_managers, event.owner) {
+                    if character_store.characters.contains_key(&event.character_id) {
+                        let spawn: ReplaySpawn = ReplaySpawn {
+                            character: event.character_id,
+                            rank: selection.rank,
+                        };
+                        // The AI performs the spawn ability, whereas the player only invokes it.
+                        let mut attached: bool = false;
+                        if let Some(input) = recorder.last_input_mut(source, ability.data.id) {
+                            if input.spawn.is_none() {
+                                input.spawn = Some(spawn.clone());
+                                attached = true;
+                            }
+                        }
+                        if !attached {
+                            recorder.replay.inputs.push(ReplayInput {
+                                turn,
+                                draws: rng.draws(),
+                                source,
+                                ability: ability.data.id,
+                                target: ReplayTarget::Multi(Vec::new()),
+                                spawn: Some(spawn),
+                                hack: None,
+                                charge: None,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for event in hack_selected_events.read(&mut self.hack_selected_reader) {
+            if let (Some(charge), Some(ability)) = (event.charge, abilities.get(event.data.owner)) {
+                if let Some((_, source, _)) = describe_ability(&parents, &characters, &roots, &slot_managers, event.data.owner) {
+                    let chance: f32 = event.data.options.iter()
+                        .find(|option| option.charge == charge)
+                        .map(|option| option.chance)
+                        .unwrap_or(0.0);
+                    if let Some(input) = recorder.last_input_mut(source, ability.data.id) {
+                        input.hack = Some(ReplayHack {
+                            charge,
+                            chance,
+                            succeeded: event.succeeded,
+                        });
+                    }
+                }
+            }
+        }
+
+        for event in wave_events.read(&mut self.wave_reader) {
+            if let Some((root, _)) = get_root::<CombatRoot, _, _>(&parents, &roots, event.team_ent) {
+                recorder.replay.waves.push(ReplayWave {
+                    turn: root.turn_count,
+                    idx: event.idx,
+                });
+            }
+        }
+
+        if exit.is_some() {
+            recorder.write();
+        }
+    }
+}
+
+/// Performs the recorded abilities of a `ReplayPlayback`, in place of the player and the AI.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(ReplayPlaybackSystemDesc))]
+pub struct ReplayPlaybackSystem {
+    #[system_desc(event_channel_reader)]
+    wave_reader: ReaderId<SpawnWaveEvent>,
+}
+
+impl<'s> System<'s> for ReplayPlaybackSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Parent>,
+        WriteStorage<'s, Principal>,
+        WriteStorage<'s, Character>,
+        ReadStorage<'s, Ability>,
+        WriteStorage<'s, AbilityPerform>,
+        WriteStorage<'s, SpawnAbility>,
+        ReadStorage<'s, CombatRoot>,
+        ReadStorage<'s, SlotManager>,
+        ReadExpect<'s, ParentHierarchy>,
+        Write<'s, CombatRng>,
+        Write<'s, ReplayPlayback>,
+        Read<'s, EventChannel<SpawnWaveEvent>>,
+        Write<'s, EventChannel<HackSelectedEvent>>,
+    );
+
+    fn run(&mut self, (entities, parents, mut principals, mut characters, abilities, mut performs, mut spawn_abilities, roots, slot_managers, hierarchy, mut rng, mut playback, wave_events, mut hack_selected_events): Self::SystemData) {
+        for event in wave_events.read(&mut self.wave_reader) {
+            if playback.is_active() {
+                match playback.take_wave() {
+                    Some(wave) if wave.idx == event.idx => {}
+                    _ => playback.diverge(format!("wave {} was not spawned at this point in the recording", event.idx)),
+                }
+            }
+        }
+
+        if !playback.is_active() {
+            return;
+        }
+
+        // Select the recorded hack option once the hack has begun.
+        if let Some((ability_ent, target_ent, hack)) = playback.pending_hack.take() {
+            if performs.contains(ability_ent) {
+                let succeeded: bool = roll(&mut rng, hack.chance);
+                // The hack still has to finish, even when the recording can't be followed after it.
+                hack_selected_events.single_write(HackSelectedEvent {
+                    data: UiHackData {
+                        owner: ability_ent,
+                        target: target_ent,
+                        options: Vec::new(),
+                    },
+                    charge: Some(hack.charge),
+                    succeeded,
+                });
+                if succeeded != hack.succeeded {
+                    playback.diverge("the hack result differs from the recording".to_string());
+                    return;
+                }
+            }
+        }
+
+        for (root_ent, root, slot_manager) in (&entities, &roots, &slot_managers).join() {
+            if Principal::is_root_engaged(&parents, &principals, root_ent) == Some(true) {
+                continue;
+            }
+            if let CombatState::InTurn(team) = root.current_state {
+                let input: ReplayInput = match playback.next_input() {
+                    Some(input) => input,
+                    None => continue,
+                };
+
+                if input.turn < root.turn_count {
+                    playback.diverge(format!("the combat moved past turn {} before `{}` was performed", input.turn, input.ability.0));
+                    return;
+                } else if input.turn == root.turn_count && input.source.team == team {
+                    playback.next_input += 1;
+                    // The AI draws values while recording which it does not draw during playback, so those are skipped.
+                    // Having drawn more values than the recording did means that the combat is no longer deterministic.
+                    if !rng.skip_to(input.draws) {
+                        playback.diverge(format!("more random values were drawn than in the recording before `{}` on turn {}", input.ability.0, input.turn));
+                        return;
+                    }
+
+                    let mut ability_ent: Option<Entity> = None;
+                    if let Some(character_ent) = input.source.resolve(slot_manager) {
+                        for (entity, ability, _) in (&entities, &abilities, hierarchy.all_children(character_ent)).join() {
+                            if ability.data.id == input.ability {
+                                ability_ent = Some(entity);
+                                break;
+                            }
+                        }
+                    }
+
+                    if let (Some(ability_ent), Some(target)) = (ability_ent, input.target.resolve(slot_manager)) {
+                        if let Some(spawn) = &input.spawn {
+                            if let Some(spawn_ability) = spawn_abilities.get_mut(ability_ent) {
+                                spawn_ability.next_spawn = Some(CharacterSpawn::new(spawn.character, spawn.rank));
+                            }
+                        }
+                        if let (Some(hack), AbilityTarget::Single(target_ent)) = (input.hack, &target) {
+                            playback.pending_hack = Some((ability_ent, *target_ent, hack));
+                        }
+                        if perform_ability(&parents, &mut principals, &abilities, &mut performs, ability_ent, target, input.charge) != Some(true) {
+                            playback.diverge(format!("failed to perform `{}` on turn {}", input.ability.0, input.turn));
+                            return;
+                        }
+                    } else {
+                        playback.diverge(format!("could not find `{}` for {:?} slot {} on turn {}", input.ability.0, input.source.team, input.source.idx, input.turn));
+                        return;
+                    }
+                } else {
+                    // Nothing else was recorded for this turn, so pass it.
+                    for entity_opt in slot_manager.for_team(team).occupied().iter() {
+                        if let Some(character_ent) = *entity_opt {
+                            if let Some(character) = characters.get_mut(character_ent) {
+                                character.use_turns();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use amethyst::{
+    core::{
+        Parent,
+        ParentHierarchy,
+    },
+    ecs::prelude::*,
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+};
+
+use crate::core::get_root;
+use crate::core::registry::TypeRegistry;
+use crate::game::character::{CharacterDefeatedEvent, CharacterId};
+use crate::game::combat::{CharacterSpawn, CombatData, CombatRoot, CombatState, CombatSystemId, Team, TickTurn};
+use crate::game::combat::ability::hack::HackPerformedEvent;
+use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
+use crate::game::combat::systems::standard_combat::{ExitCombat, EXIT_TIMER};
+use crate::game::ui::banner::ShowUiBannerDisplayEvent;
+use crate::game::ui::crosshair::UiCrosshair;
+
+/// The parameters of the rules, as they are stored in the combat data. Each rule only uses the ones it needs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleParams {
+    /// The number of turns (counting the turns of both teams) which the player has to survive,
+    /// which the boss has to be defeated within or which the hill has to be held for.
+    pub turns: Option<i32>,
+    /// The character which is spawned alongside the master drone and has to be protected.
+    /// Its id should differ from those of the other friendly characters.
+    pub escort: Option<CharacterSpawn>,
+}
+
+/// The state which the rules keep for each combat.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct RuleState {
+    /// The team which holds the hill, and for how many consecutive turns it has held it.
+    pub hill: Option<(Team, i32)>,
+}
+
+/// What the rule decides whenever it is checked.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RuleOutcome {
+    Continue,
+    /// Spawns the wave with the index.
+    SpawnWave(usize),
+    /// Ends the combat.
+    Winner(Team),
+}
+
+/// Everything the rules know about a combat.
+pub struct RuleContext<'a> {
+    pub data: &'a CombatData,
+    pub turn_count: i32,
+    pub current_wave: usize,
+    pub slot_manager: &'a SlotManager,
+    /// The characters of the combat which have been defeated since the last check.
+    pub defeated: &'a [(CharacterId, Team)],
+    /// Set if a character has been defeated or hacked since the last check.
+    pub changed: bool,
+    /// Set if a turn has ended since the last check.
+    pub turn_ended: bool,
+    pub state: &'a mut RuleState,
+}
+
+/// A rule module, which decides when a combat is won or lost. It is selected by `CombatData::system`.
+pub trait CombatRule: Debug + Send + Sync {
+    /// Called whenever a character has been defeated or hacked, or a turn has ended.
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome;
+}
+
+/// The standard outcome: the player loses with their master drone, the next wave is spawned once the enemies have been cleared,
+/// and the player wins once there are no waves left.
+fn clear_waves(ctx: &RuleContext) -> RuleOutcome {
+    if !ctx.changed {
+        RuleOutcome::Continue
+    } else if ctx.slot_manager.friendly.master().is_none() {
+        RuleOutcome::Winner(Team::Enemy)
+    } else if ctx.slot_manager.enemy.is_empty() {
+        let idx: usize = ctx.current_wave + 1;
+        if idx < ctx.data.waves().len() {
+            RuleOutcome::SpawnWave(idx)
+        } else {
+            RuleOutcome::Winner(Team::Friendly)
+        }
+    } else {
+        RuleOutcome::Continue
+    }
+}
+
+/// Clear every wave.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct WaveClearRule;
+
+impl CombatRule for WaveClearRule {
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome {
+        clear_waves(ctx)
+    }
+}
+
+/// Survive for the number of turns. The last wave is repeated until then.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SurviveRule;
+
+impl CombatRule for SurviveRule {
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome {
+        if ctx.changed && ctx.slot_manager.friendly.master().is_none() {
+            RuleOutcome::Winner(Team::Enemy)
+        } else if ctx.data.rule().turns.map_or(false, |turns| ctx.turn_count >= turns) {
+            RuleOutcome::Winner(Team::Friendly)
+        } else if ctx.changed && ctx.slot_manager.enemy.is_empty() {
+            let last: usize = ctx.data.waves().len().saturating_sub(1);
+            RuleOutcome::SpawnWave((ctx.current_wave + 1).min(last))
+        } else {
+            RuleOutcome::Continue
+        }
+    }
+}
+
+/// Clear every wave without losing the escort.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct EscortRule;
+
+impl CombatRule for EscortRule {
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome {
+        if let Some(escort) = ctx.data.rule().escort {
+            if ctx.defeated.contains(&(escort.character_id, Team::Friendly)) {
+                return RuleOutcome::Winner(Team::Enemy);
+            }
+        }
+        clear_waves(ctx)
+    }
+}
+
+/// Defeat the boss (the master of the last wave) before the turn limit.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BossRule;
+
+impl CombatRule for BossRule {
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome {
+        if let Some(boss) = ctx.data.waves().last().and_then(|wave| wave.master) {
+            if ctx.current_wave + 1 == ctx.data.waves().len() && ctx.defeated.contains(&(boss.character_id, Team::Enemy)) {
+                return RuleOutcome::Winner(Team::Friendly);
+            }
+        }
+        if ctx.data.rule().turns.map_or(false, |turns| ctx.turn_count >= turns) {
+            RuleOutcome::Winner(Team::Enemy)
+        } else {
+            clear_waves(ctx)
+        }
+    }
+}
+
+/// Whichever team has more characters at the end of a turn holds the hill. The first team to hold it for the number of turns wins.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct KingOfTheHillRule;
+
+impl CombatRule for KingOfTheHillRule {
+    fn check(&self, ctx: &mut RuleContext) -> RuleOutcome {
+        if ctx.turn_ended {
+            let friendly: usize = ctx.slot_manager.friendly.count();
+            let enemy: usize = ctx.slot_manager.enemy.count();
+            let holder: Option<Team> = if friendly > enemy {
+                Some(Team::Friendly)
+            } else if enemy > friendly {
+                Some(Team::Enemy)
+            } else {
+                None
+            };
+            ctx.state.hill = holder.map(|team| match ctx.state.hill {
+                Some((held, turns)) if held == team => (team, turns + 1),
+                _ => (team, 1),
+            });
+            if let (Some((team, held)), Some(turns)) = (ctx.state.hill, ctx.data.rule().turns) {
+                if held >= turns {
+                    return RuleOutcome::Winner(team);
+                }
+            }
+        }
+        clear_waves(ctx)
+    }
+}
+
+/// The rule modules which can be selected by `CombatData::system`.
+#[derive(Debug)]
+pub struct CombatRules {
+    /// Maps the ids which are stored in the combat data to the types of the rules.
+    ids: TypeRegistry,
+    rules: HashMap<TypeId, Box<dyn CombatRule>>,
+}
+
+impl Default for CombatRules {
+    fn default() -> Self {
+        let mut rules: CombatRules = CombatRules { ids: TypeRegistry::default(), rules: HashMap::new() };
+        rules.register(CombatSystemId::STANDARD, WaveClearRule);
+        rules.register(CombatSystemId::SURVIVE, SurviveRule);
+        rules.register(CombatSystemId::ESCORT, EscortRule);
+        rules.register(CombatSystemId::BOSS, BossRule);
+        rules.register(CombatSystemId::KING_OF_THE_HILL, KingOfTheHillRule);
+        rules
+    }
+}
+
+impl CombatRules {
+    /// Overwrites any rule which was previously registered with the same id.
+    pub fn register<R: CombatRule + 'static>(&mut self, id: CombatSystemId, rule: R) {
+        self.ids.register::<R>(id.0);
+        self.rules.insert(TypeId::of::<R>(), Box::new(rule));
+    }
+
+    pub fn get(&self, id: CombatSystemId) -> Option<&dyn CombatRule> {
+        self.ids.type_id(id.0)
+            .and_then(|ty| self.rules.get(&ty))
+            .map(|rule| rule.as_ref())
+    }
+}
+
+/// Checks the rule of each combat, and spawns its waves or ends it accordingly.
+/// Only the rule which the combat has selected acts on it.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(CombatRuleSystemDesc))]
+pub struct CombatRuleSystem {
+    #[system_desc(event_channel_reader)]
+    defeated_event_reader: ReaderId<CharacterDefeatedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    hack_event_reader: ReaderId<HackPerformedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    tick_turn_reader: ReaderId<TickTurn>,
+}
+
+impl<'s> System<'s> for CombatRuleSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, SlotManager>,
+        WriteStorage<'s, CombatRoot>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, UiCrosshair>,
+        ReadExpect<'s, ParentHierarchy>,
+        Write<'s, Option<ExitCombat>>,
+        Read<'s, EventChannel<CharacterDefeatedEvent>>,
+        Read<'s, EventChannel<HackPerformedEvent>>,
+        Read<'s, EventChannel<TickTurn>>,
+        Write<'s, EventChannel<SpawnWaveEvent>>,
+        Write<'s, EventChannel<ShowUiBannerDisplayEvent>>,
+        Read<'s, CombatRules>,
+    );
+
+    fn run(&mut self, (entities, slot_managers, mut roots, parents, teams, crosshairs, hierarchy, mut exit, defeated_events, hack_events, tick_turn_events, mut spawn_wave_events, mut show_banners, rules): Self::SystemData) {
+        // The defeated characters have already lost their `Character`, so the event carries what the rules need.
+        let mut defeated: Vec<(Option<Entity>, CharacterId, Option<Team>)> = Vec::new();
+        for event in defeated_events.read(&mut self.defeated_event_reader) {
+            let root_ent: Option<Entity> = get_root::<CombatRoot, _, _>(&parents, &roots, event.character_ent).map(|(_, root_ent)| root_ent);
+            defeated.push((root_ent, event.character_id, event.team));
+        }
+        let hacked: Vec<Option<Entity>> = hack_events.read(&mut self.hack_event_reader)
+            .map(|event| get_root::<CombatRoot, _, _>(&parents, &roots, event.target_ent).map(|(_, root_ent)| root_ent))
+            .collect();
+        let turn_ended: bool = tick_turn_events.read(&mut self.tick_turn_reader).count() > 0;
+        if defeated.is_empty() && hacked.is_empty() && !turn_ended {
+            return;
+        }
+
+        for (entity, root, slot_manager) in (&entities, &mut roots, &slot_managers).join() {
+            match root.current_state {
+                CombatState::Victory(_) | CombatState::Fled => continue,
+                _ => {},
+            }
+            let rule: &dyn CombatRule = match rules.get(root.data.system()) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let root_defeated: Vec<(CharacterId, Team)> = defeated.iter()
+                .filter(|(root_ent, _, _)| *root_ent == Some(entity))
+                .filter_map(|(_, character_id, team)| team.map(|team| (*character_id, team)))
+                .collect();
+            // Only the characters of this combat count as a change to it.
+            let changed: bool = defeated.iter().any(|(root_ent, _, _)| *root_ent == Some(entity))
+                || hacked.contains(&Some(entity));
+            let outcome: RuleOutcome = {
+                let CombatRoot { data, turn_count, current_wave, rule_state, .. } = &mut *root;
+                rule.check(&mut RuleContext {
+                    data,
+                    turn_count: *turn_count,
+                    current_wave: *current_wave,
+                    slot_manager,
+                    changed,
+                    defeated: &root_defeated,
+                    turn_ended,
+                    state: rule_state,
+                })
+            };
+
+            match outcome {
+                RuleOutcome::Continue => {},
+                RuleOutcome::SpawnWave(idx) => {
+                    if let Some(wave) = root.data.waves().get(idx) {
+                        let team_ent: Option<Entity> = (&entities, &teams, hierarchy.all_children(entity)).join()
+                            .find(|(_, team, _)| **team == Team::Enemy)
+                            .map(|(team_ent, _, _)| team_ent);
+                        if let Some(team_ent) = team_ent {
+                            spawn_wave_events.single_write(
+                                SpawnWaveEvent {
+                                    wave: wave.clone(),
+                                    idx,
+                                    team_ent,
+                                }
+                            );
+                        }
+                        root.current_wave = idx;
+                    }
+                },
+                RuleOutcome::Winner(team) => {
+                    show_banners.single_write(
+                        ShowUiBannerDisplayEvent {
+                            text: match team {
+                                Team::Friendly => "Victory".to_string(),
+                                Team::Enemy => "Defeated".to_string(),
+                            },
+                            color: match team {
+                                Team::Friendly => [0.0, 1.0, 0.0, 1.0],
+                                Team::Enemy => [1.0, 0.0, 0.0, 1.0],
+                            },
+                            owner: entity,
+                        }
+                    );
+                    root.current_state = CombatState::Victory(team);
+                    *exit = Some(ExitCombat::new(EXIT_TIMER, Some(team)));
+                    for (crosshair_ent, _) in (&entities, crosshairs.mask()).join() {
+                        entities.delete(crosshair_ent);
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::combat::{Rank, Wave};
+
+    const GRUNT: CharacterId = CharacterId("grunt");
+    const BOSS: CharacterId = CharacterId("boss");
+    const ESCORT: CharacterId = CharacterId("escort");
+
+    fn spawn(character_id: CharacterId) -> CharacterSpawn {
+        CharacterSpawn::new(character_id, Rank::Basic)
+    }
+
+    fn combat(system: CombatSystemId, rule: RuleParams, waves: usize) -> CombatData {
+        let waves: Vec<Wave> = (0..waves).map(|_| Wave::new_simple(vec![spawn(GRUNT)])).collect();
+        CombatData::basic("test", "", waves).with_rule(system, rule)
+    }
+
+    /// Occupies the first slots of each team, starting with the master slot.
+    fn slots(world: &mut World, friendly: usize, enemy: usize) -> SlotManager {
+        let mut slot_manager: SlotManager = SlotManager::new();
+        for idx in 0..friendly {
+            slot_manager.friendly.occupy(idx, world.create_entity().build());
+        }
+        for idx in 0..enemy {
+            slot_manager.enemy.occupy(idx, world.create_entity().build());
+        }
+        slot_manager
+    }
+
+    fn ctx<'a>(data: &'a CombatData, slot_manager: &'a SlotManager, defeated: &'a [(CharacterId, Team)], state: &'a mut RuleState) -> RuleContext<'a> {
+        RuleContext {
+            data,
+            turn_count: 0,
+            current_wave: 0,
+            slot_manager,
+            defeated,
+            changed: true,
+            turn_ended: false,
+            state,
+        }
+    }
+
+    #[test]
+    fn every_rule_is_registered() {
+        let rules: CombatRules = CombatRules::default();
+        for id in [CombatSystemId::STANDARD, CombatSystemId::SURVIVE, CombatSystemId::ESCORT, CombatSystemId::BOSS, CombatSystemId::KING_OF_THE_HILL].iter() {
+            assert!(rules.get(*id).is_some(), "{:?} is not registered", id);
+        }
+        assert!(rules.get(CombatSystemId("unknown")).is_none());
+    }
+
+    #[test]
+    fn wave_clear_spawns_the_next_wave_and_then_wins() {
+        let mut world = World::new();
+        let data: CombatData = combat(CombatSystemId::STANDARD, RuleParams::default(), 2);
+        let (fighting, cleared) = (slots(&mut world, 1, 1), slots(&mut world, 1, 0));
+        let mut state: RuleState = RuleState::default();
+
+        assert_eq!(WaveClearRule.check(&mut ctx(&data, &fighting, &[], &mut state)), RuleOutcome::Continue);
+        assert_eq!(WaveClearRule.check(&mut RuleContext { changed: false, ..ctx(&data, &cleared, &[], &mut state) }), RuleOutcome::Continue);
+        assert_eq!(WaveClearRule.check(&mut ctx(&data, &cleared, &[], &mut state)), RuleOutcome::SpawnWave(1));
+        assert_eq!(WaveClearRule.check(&mut RuleContext { current_wave: 1, ..ctx(&data, &cleared, &[], &mut state) }), RuleOutcome::Winner(Team::Friendly));
+    }
+
+    #[test]
+    fn wave_clear_is_lost_without_the_master() {
+        let mut world = World::new();
+        let data: CombatData = combat(CombatSystemId::STANDARD, RuleParams::default(), 1);
+        let slot_manager: SlotManager = slots(&mut world, 0, 0);
+        let mut state: RuleState = RuleState::default();
+        assert_eq!(WaveClearRule.check(&mut ctx(&data, &slot_manager, &[], &mut state)), RuleOutcome::Winner(Team::Enemy));
+    }
+
+    #[test]
+    fn survive_wins_once_the_turns_have_passed() {
+        let mut world = World::new();
+        let rule: RuleParams = RuleParams { turns: Some(10), ..Default::default() };
+        let data: CombatData = combat(CombatSystemId::SURVIVE, rule, 2);
+        let slot_manager: SlotManager = slots(&mut world, 1, 1);
+        let mut state: RuleState = RuleState::default();
+
+        assert_eq!(SurviveRule.check(&mut RuleContext { turn_count: 9, ..ctx(&data, &slot_manager, &[], &mut state) }), RuleOutcome::Continue);
+        assert_eq!(SurviveRule.check(&mut RuleContext { turn_count: 10, changed: false, ..ctx(&data, &slot_manager, &[], &mut state) }), RuleOutcome::Winner(Team::Friendly));
+    }
+
+    #[test]
+    fn survive_repeats_the_last_wave() {
+        let mut world = World::new();
+        let rule: RuleParams = RuleParams { turns: Some(10), ..Default::default() };
+        let data: CombatData = combat(CombatSystemId::SURVIVE, rule, 2);
+        let slot_manager: SlotManager = slots(&mut world, 1, 0);
+        let mut state: RuleState = RuleState::default();
+
+        assert_eq!(SurviveRule.check(&mut ctx(&data, &slot_manager, &[], &mut state)), RuleOutcome::SpawnWave(1));
+        assert_eq!(SurviveRule.check(&mut RuleContext { current_wave: 1, ..ctx(&data, &slot_manager, &[], &mut state) }), RuleOutcome::SpawnWave(1));
+        assert_eq!(SurviveRule.check(&mut ctx(&data, &slots(&mut world, 0, 0), &[], &mut state)), RuleOutcome::Winner(Team::Enemy));
+    }
+
+    #[test]
+    fn escort_is_lost_with_the_escort() {
+        let mut world = World::new();
+        let rule: RuleParams = RuleParams { escort: Some(spawn(ESCORT)), ..Default::default() };
+        let data: CombatData = combat(CombatSystemId::ESCORT, rule, 1);
+        let slot_manager: SlotManager = slots(&mut world, 2, 1);
+        let mut state: RuleState = RuleState::default();
+
+        // Enemies which share the escort's id don't count.
+        assert_eq!(EscortRule.check(&mut ctx(&data, &slot_manager, &[(ESCORT, Team::Enemy)], &mut state)), RuleOutcome::Continue);
+        assert_eq!(EscortRule.check(&mut ctx(&data, &slot_manager, &[(ESCORT, Team::Friendly)], &mut state)), RuleOutcome::Winner(Team::Enemy));
+    }
+
+    #[test]
+    fn boss_is_won_by_defeating_the_boss_of_the_last_wave() {
+        let mut world = World::new();
+        let rule: RuleParams = RuleParams { turns: Some(20), ..Default::default() };
+        let waves: Vec<Wave> = vec![Wave::new_simple(vec![spawn(GRUNT)]), Wave::boss(spawn(BOSS))];
+        let data: CombatData = CombatData::basic("test", "", waves).with_rule(CombatSystemId::BOSS, rule);
+        let slot_manager: SlotManager = slots(&mut world, 1, 1);
+        let mut state: RuleState = RuleState::default();
+
+        assert_eq!(BossRule.check(&mut ctx(&data, &slot_manager, &[(BOSS, Team::Enemy)], &mut state)), RuleOutcome::Continue);
+        assert_eq!(BossRule.check(&mut RuleContext { current_wave: 1, ..ctx(&data, &slot_manager, &[(BOSS, Team::Enemy)], &mut state) }), RuleOutcome::Winner(Team::Friendly));
+        assert_eq!(BossRule.check(&mut RuleContext { current_wave: 1, turn_count: 20, ..ctx(&data, &slot_manager, &[], &mut state) }), RuleOutcome::Winner(Team::Enemy));
+    }
+
+    #[test]
+    fn king_of_the_hill_is_won_by_holding_the_hill() {
+        let mut world = World::new();
+        let rule: RuleParams = RuleParams { turns: Some(2), ..Default::default() };
+        let data: CombatData = combat(CombatSystemId::KING_OF_THE_HILL, rule, 1);
+        let (friendly_ahead, enemy_ahead, tied) = (slots(&mut world, 3, 1), slots(&mut world, 1, 3), slots(&mut world, 2, 2));
+        let mut state: RuleState = RuleState::default();
+
+        let end_turn = |slot_manager: &SlotManager, state: &mut RuleState| {
+            KingOfTheHillRule.check(&mut RuleContext { turn_ended: true, ..ctx(&data, slot_manager, &[], state) })
+        };
+        assert_eq!(end_turn(&friendly_ahead, &mut state), RuleOutcome::Continue);
+        assert_eq!(end_turn(&enemy_ahead, &mut state), RuleOutcome::Continue);
+        assert_eq!(state.hill, Some((Team::Enemy, 1)));
+        assert_eq!(end_turn(&tied, &mut state), RuleOutcome::Continue);
+        assert_eq!(state.hill, None);
+        assert_eq!(end_turn(&friendly_ahead, &mut state), RuleOutcome::Continue);
+        assert_eq!(end_turn(&friendly_ahead, &mut state), RuleOutcome::Winner(Team::Friendly));
+    }
+}
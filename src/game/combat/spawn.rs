@@ -25,12 +25,12 @@ use crate::{
         Character,
         CharacterId,
         CharacterPrefabData,
-        master::MasterDrone,
     },
 };
 use crate::core::activity::{Activity, ActivityAggregator, ActivityState};
 use crate::game::character::{CharacterStore, UnassignedCharacter, CharacterData};
 use crate::game::combat::ability::{Ability, AbilityList, UnassignedAbility};
+use crate::game::combat::difficulty::DifficultyProfile;
 use crate::game::combat::process::Principal;
 use crate::game::combat::{Team, Rank};
 use crate::game::ui::crosshair::UiCrosshair;
@@ -318,10 +318,11 @@ impl<'s> System<'s> for SpawnInvokeSystem {
         WriteStorage<'s, Ability>,
         WriteStorage<'s, UnassignedAbility>,
         Read<'s, AbilityList>,
+        Read<'s, DifficultyProfile>,
         Write<'s, EventChannel<CharacterSpawnedEvent>>,
     );
 
-    fn run(&mut self, (entities, time, mut principals, mut parents, mut characters, mut unassigned_characters, mut character_prefabs, mut teams, mut transforms, mut slot_managers, mut spawn_processes, mut spawn_actions, character_store, mut abilities, mut unassigned_abilities, ability_list, mut spawn_events): Self::SystemData) {
+    fn run(&mut self, (entities, time, mut principals, mut parents, mut characters, mut unassigned_characters, mut character_prefabs, mut teams, mut transforms, mut slot_managers, mut spawn_processes, mut spawn_actions, character_store, mut abilities, mut unassigned_abilities, ability_list, difficulty, mut spawn_events): Self::SystemData) {
         let mut to_remove: Vec<(Entity, SpawnAction)> = Vec::new();
         for (entity, action) in (&entities, &spawn_actions).join() {
             to_remove.push((entity, *action));
@@ -338,6 +339,11 @@ impl<'s> System<'s> for SpawnInvokeSystem {
             spawn_actions.remove(entity);
 
             let new_character: Entity = entity;
+            // Every spawn (waves, bosses and summons) is scaled by the difficulty here.
+            let mut character_data: Option<CharacterData> = action.character_data.or_else(|| character_store.data(&action.character_id));
+            if let Some(data) = character_data.as_mut() {
+                difficulty.scale(action.team, data);
+            }
 
             match Character::spawn(
                 &parents,
@@ -348,10 +354,15 @@ impl<'s> System<'s> for SpawnInvokeSystem {
                 &mut character_prefabs,
                 &mut spawn_processes,
                 &character_store,
-                new_character, action.character_id, action.character_data, action.rank, action.team, action.slot_idx,
+                new_character, action.character_id, character_data, action.rank, action.team, action.slot_idx,
                 principal,
             ) {
                 Ok(_) => {
+                    // Characters without an instantiated prefab (e.g. when running headless) still need a transform to move into their slot.
+                    if !transforms.contains(new_character) {
+                        transforms.insert(new_character, Transform::default());
+                    }
+
                     //populate character's abilities.
                     Character::populate_abilities(
                         &entities,
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use amethyst::{
+    core::Parent,
+    ecs::{
+        prelude::*,
+        storage::ComponentEvent,
+    },
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+};
+
+use crate::core::get_root;
+use crate::game::character::{Character, CharacterDefeatedEvent, CharacterId};
+use crate::game::combat::Team;
+use crate::game::combat::ability::{Ability, AbilityPerform, HealEvent, MissEvent};
+use crate::game::combat::pipeline::DmgResolvedEvent;
+
+/// What a single character (or a group of characters) has done over the course of a combat.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CharacterStats {
+    pub dmg_dealt: f32,
+    pub dmg_taken: f32,
+    pub hits: usize,
+    pub misses: usize,
+    pub kills: usize,
+    pub healing: f32,
+    pub charge_used: f32,
+}
+
+impl CharacterStats {
+    /// The proportion of attacks which hit, if the character has attacked at all.
+    pub fn accuracy(&self) -> Option<f32> {
+        let attacks: usize = self.hits + self.misses;
+        if attacks == 0 {
+            None
+        } else {
+            Some(self.hits as f32 / attacks as f32)
+        }
+    }
+
+    pub fn add(&mut self, other: &CharacterStats) {
+        self.dmg_dealt += other.dmg_dealt;
+        self.dmg_taken += other.dmg_taken;
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.kills += other.kills;
+        self.healing += other.healing;
+        self.charge_used += other.charge_used;
+    }
+}
+
+/// The stats of a character in the combat in progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatantStats {
+    pub id: CharacterId,
+    pub name: &'static str,
+    /// The team the character was last seen on (hacked characters switch teams).
+    pub team: Team,
+    pub stats: CharacterStats,
+}
+
+/// The stats of every character which took part in the combat in progress.
+#[derive(Debug, Clone, Default)]
+pub struct CombatStats {
+    pub characters: BTreeMap<Entity, CombatantStats>,
+}
+
+impl CombatStats {
+    /// The stats of the character, which are created the first time the character is seen.
+    fn entry(
+        &mut self,
+        parents: &ReadStorage<Parent>,
+        teams: &ReadStorage<Team>,
+        characters: &ReadStorage<Character>,
+        character_ent: Entity,
+    ) -> Option<&mut CharacterStats> {
+        let character: &Character = characters.get(character_ent)?;
+        let (team, _) = Team::get_team(parents, teams, character_ent)?;
+        let combatant = self.characters.entry(character_ent).or_insert_with(|| CombatantStats {
+            id: character.id(),
+            name: character.name(),
+            team,
+            stats: CharacterStats::default(),
+        });
+        combatant.team = team;
+        Some(&mut combatant.stats)
+    }
+
+    /// The stats of each character which was last seen on the team.
+    pub fn team(&self, team: Team) -> impl Iterator<Item=&CombatantStats> {
+        self.characters.values().filter(move |combatant| combatant.team == team)
+    }
+
+    pub fn team_total(&self, team: Team) -> CharacterStats {
+        let mut total: CharacterStats = CharacterStats::default();
+        for combatant in self.team(team) {
+            total.add(&combatant.stats);
+        }
+        total
+    }
+}
+
+/// The stats of the player's drones over every combat, which are kept in the save.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub victories: usize,
+    pub defeats: usize,
+    pub total: CharacterStats,
+    pub characters: BTreeMap<CharacterId, CharacterStats>,
+}
+
+impl LifetimeStats {
+    /// Adds the stats of the friendly characters in a finished combat.
+    pub fn record(&mut self, stats: &CombatStats, winner: Option<Team>) {
+        match winner {
+            Some(Team::Friendly) => self.victories += 1,
+            Some(Team::Enemy) => self.defeats += 1,
+            None => {},
+        }
+        for combatant in stats.team(Team::Friendly) {
+            self.total.add(&combatant.stats);
+            self.characters.entry(combatant.id).or_default().add(&combatant.stats);
+        }
+    }
+}
+
+/// Collects the `CombatStats` of the combat in progress.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(CombatStatsSystemDesc))]
+pub struct CombatStatsSystem {
+    #[system_desc(flagged_storage_reader(AbilityPerform))]
+    perform_reader: ReaderId<ComponentEvent>,
+
+    #[system_desc(event_channel_reader)]
+    dmg_resolved_reader: ReaderId<DmgResolvedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    miss_reader: ReaderId<MissEvent>,
+
+    #[system_desc(event_channel_reader)]
+    heal_reader: ReaderId<HealEvent>,
+
+    #[system_desc(event_channel_reader)]
+    defeated_reader: ReaderId<CharacterDefeatedEvent>,
+}
+
+impl<'s> System<'s> for CombatStatsSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, Character>,
+        ReadStorage<'s, Ability>,
+        ReadStorage<'s, AbilityPerform>,
+        Read<'s, EventChannel<DmgResolvedEvent>>,
+        Read<'s, EventChannel<MissEvent>>,
+        Read<'s, EventChannel<HealEvent>>,
+        Read<'s, EventChannel<CharacterDefeatedEvent>>,
+        Write<'s, CombatStats>,
+    );
+
+    fn run(&mut self, (entities, parents, teams, characters, abilities, performs, dmg_resolved_events, miss_events, heal_events, defeated_events, mut stats): Self::SystemData) {
+        for event in performs.channel().read(&mut self.perform_reader) {
+            if let ComponentEvent::Inserted(id) = event {
+                let ability_ent: Entity = entities.entity(*id);
+                if let (Some(ability), Some(perform)) = (abilities.get(ability_ent), performs.get(ability_ent)) {
+                    // Charging abilities have a negative cost.
+                    let charge: f32 = perform.charge(&ability.data.charge);
+                    if charge > 0.0 {
+                        if let Some((_, character_ent)) = get_root::<Character, _, _>(&parents, &characters, ability_ent) {
+                            if let Some(source) = stats.entry(&parents, &teams, &characters, character_ent) {
+                                source.charge_used += charge;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for event in dmg_resolved_events.read(&mut self.dmg_resolved_reader) {
+            let dmg: f32 = event.breakdown.total;
+            if let Some(source_ent) = event.package.source {
+                if let Some(source) = stats.entry(&parents, &teams, &characters, source_ent) {
+                    source.dmg_dealt += dmg;
+                    source.hits += 1;
+                }
+            }
+            if let Some(target) = stats.entry(&parents, &teams, &characters, event.package.target) {
+                target.dmg_taken += dmg;
+            }
+        }
+
+        for event in miss_events.read(&mut self.miss_reader) {
+            if let Some(source_ent) = event.source {
+                if let Some(source) = stats.entry(&parents, &teams, &characters, source_ent) {
+                    source.misses += 1;
+                }
+            }
+        }
+
+        for event in heal_events.read(&mut self.heal_reader) {
+            if let Some(source_ent) = event.source {
+                if let Some(source) = stats.entry(&parents, &teams, &characters, source_ent) {
+                    source.healing += event.heal_value;
+                }
+            }
+        }
+
+        for event in defeated_events.read(&mut self.defeated_reader) {
+            if let Some(killer_ent) = event.killer {
+                if let Some(killer) = stats.entry(&parents, &teams, &characters, killer_ent) {
+                    killer.kills += 1;
+                }
+            }
+        }
+    }
+}
@@ -1,30 +1,32 @@
-use std::marker::PhantomData;
-
-impl Component for StatusEffect {
-    type Storage = DenseVecStorage<Self>;
-}
-
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 
 use amethyst::{
+    assets::{
+        Format,
+        RonFormat,
+    },
     core::{
-        math::Vector2,
-        Named,
         Parent,
-        ParentHierarchy,
         SystemDesc,
-        Time,
-        Transform,
     },
     ecs::prelude::*,
+    log::error,
     shrev::{
         EventChannel,
         ReaderId,
     },
-    ui::UiTransform,
+    utils::application_root_dir,
 };
 
+use crate::game::character::{Character, LastDamaged};
+use crate::game::combat::{TickTurn, Team};
+
+/// The file (relative to the application root) which defines the effects of each status.
+pub const STATUS_FILE: &str = "assets/statuses.ron";
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum StatusType {
     Scramble,
     Overclocked,
@@ -32,6 +34,16 @@ pub enum StatusType {
     Defend,
     Empower,
     Focus,
+    /// Inflicted by kinetic attacks. Reduces accuracy.
+    Shaken,
+    /// Inflicted by plasma attacks. Deals damage at the end of each turn.
+    Burning,
+    /// Inflicted by radiation attacks. Increases the damage received.
+    Exposed,
+    /// Increases speed, so the character acts more often in the initiative turn mode.
+    Haste,
+    /// Reduces speed, so the character acts less often in the initiative turn mode.
+    Slow,
 }
 
 impl StatusType {
@@ -43,25 +55,262 @@ impl StatusType {
             StatusType::Defend,
             StatusType::Empower,
             StatusType::Focus,
+            StatusType::Shaken,
+            StatusType::Burning,
+            StatusType::Exposed,
+            StatusType::Haste,
+            StatusType::Slow,
         ]
     }
 }
 
-pub struct StatusData {
-    name: String,
-    desc: String,
-    ty: StatusType,
+/// Sent whenever a status is inflicted on a character.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StatusInflictedEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub ty: StatusType,
+    /// The remaining turns of the status, after its stacking rules have been applied.
+    pub turns: usize,
+}
+
+/// Determines when the turns of a status are counted down (and when its per tick effects are applied).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StatusTick {
+    /// At the end of the afflicted character's turn.
+    OwnTurn,
+    /// At the end of the opposing team's turn.
+    /// In the initiative turn mode, this is the end of each turn taken by a character of the opposing team.
+    OpposingTurn,
+    /// The status is only removed by other means (e.g. overclocked turns are used up).
+    Never,
+}
+
+/// Determines what happens when a status is inflicted on a character which already has it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StatusStacking {
+    /// The turns are added to the remaining turns.
+    Extend,
+    /// The remaining turns are set to whichever is longer.
+    Refresh,
+    /// Adds a stack (up to the max stacks) and refreshes the turns.
+    Stack,
+    /// The new status is ignored.
+    Ignore,
+}
+
+/// Multipliers which are applied to the stats of the afflicted character.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusModifiers {
+    pub dmg_output: f32,
+    pub dmg_receive: f32,
+    pub accuracy: f32,
+    pub evade: f32,
+    /// Only used by the initiative turn mode.
+    pub speed: f32,
+}
+
+impl Default for StatusModifiers {
+    fn default() -> Self {
+        Self {
+            dmg_output: 1.0,
+            dmg_receive: 1.0,
+            accuracy: 1.0,
+            evade: 1.0,
+            speed: 1.0,
+        }
+    }
+}
+
+impl StatusModifiers {
+    /// Scales how far each multiplier is from 1 by the strength, and applies it once for each stack.
+    pub fn scaled(&self, strength: f32, stacks: usize) -> Self {
+        let scale = |value: f32| (1.0 + (value - 1.0) * strength).powi(stacks as i32);
+        Self {
+            dmg_output: scale(self.dmg_output),
+            dmg_receive: scale(self.dmg_receive),
+            accuracy: scale(self.accuracy),
+            evade: scale(self.evade),
+            speed: scale(self.speed),
+        }
+    }
+
+    pub fn combine(&self, other: &StatusModifiers) -> Self {
+        Self {
+            dmg_output: self.dmg_output * other.dmg_output,
+            dmg_receive: self.dmg_receive * other.dmg_receive,
+            accuracy: self.accuracy * other.accuracy,
+            evade: self.evade * other.evade,
+            speed: self.speed * other.speed,
+        }
+    }
+}
+
+/// A status definition, as it is stored in `assets/statuses.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusDef {
+    pub ty: StatusType,
+    pub name: String,
+    pub desc: String,
+    pub tick: StatusTick,
+    #[serde(default = "StatusDef::default_stacking")]
+    pub stacking: StatusStacking,
+    /// Only used by `StatusStacking::Stack`.
+    #[serde(default = "StatusDef::default_max_stacks")]
+    pub max_stacks: usize,
+    /// The proportion of max health which is restored each tick. Negative values deal damage.
+    #[serde(default)]
+    pub health_per_tick: f32,
+    #[serde(default)]
+    pub modifiers: StatusModifiers,
+    /// The afflicted character has no turns while the status is active.
+    #[serde(default)]
+    pub stun: bool,
+    /// These statuses can not be inflicted on the afflicted character while the status is active.
+    #[serde(default)]
+    pub immunities: Vec<StatusType>,
+}
+
+impl StatusDef {
+    fn default_stacking() -> StatusStacking {
+        StatusStacking::Extend
+    }
+
+    fn default_max_stacks() -> usize {
+        1
+    }
+}
+
+/// Contains the definition of every status.
+#[derive(Debug, Clone, Default)]
+pub struct StatusList {
+    defs: HashMap<StatusType, StatusDef>,
+}
+
+impl StatusList {
+    /// Overwrites an existing definition of the same status.
+    pub fn register(&mut self, def: StatusDef) {
+        self.defs.insert(def.ty, def);
+    }
+
+    pub fn get(&self, ty: StatusType) -> Option<&StatusDef> {
+        self.defs.get(&ty)
+    }
+}
+
+/// The definitions which are shipped with the game. These are used for any status which fails to load,
+/// since the core statuses (e.g. Defend and Overclocked) would otherwise do nothing.
+const BUILT_IN_STATUSES: &[u8] = include_bytes!("../../../assets/statuses.ron");
+
+/// Loads the status definitions from `assets/statuses.ron`.
+pub fn status_list() -> StatusList {
+    match application_root_dir() {
+        Ok(app_root) => load_statuses(app_root.join(STATUS_FILE)),
+        Err(err) => {
+            error!("[status_list] Failed to find the application root: {:?}", err);
+            built_in_statuses()
+        },
+    }
+}
+
+fn parse_statuses(bytes: Vec<u8>) -> Result<Vec<StatusDef>, amethyst::Error> {
+    Format::<Vec<StatusDef>>::import_simple(&RonFormat, bytes)
+}
+
+fn built_in_statuses() -> StatusList {
+    let mut list: StatusList = StatusList::default();
+    for def in parse_statuses(BUILT_IN_STATUSES.to_vec()).expect("Invalid built-in status definitions!") {
+        list.register(def);
+    }
+    list
+}
+
+/// Falls back to the built-in definition of every status which the file does not define, or all of them if it fails to load.
+pub fn load_statuses(path: impl AsRef<Path>) -> StatusList {
+    let mut list: StatusList = StatusList::default();
+    match fs::read(path.as_ref()) {
+        Ok(bytes) => {
+            match parse_statuses(bytes) {
+                Ok(defs) => {
+                    for def in defs {
+                        list.register(def);
+                    }
+                },
+                Err(err) => error!("[load_statuses] Failed to parse {:?}, using the built-in statuses: {:?}", path.as_ref(), err),
+            }
+        },
+        Err(err) => error!("[load_statuses] Failed to read {:?}, using the built-in statuses: {:?}", path.as_ref(), err),
+    }
+    let built_in: StatusList = built_in_statuses();
+    for ty in StatusType::all() {
+        if list.get(ty).is_none() {
+            if let Some(def) = built_in.get(ty) {
+                list.register(def.clone());
+            }
+        }
+    }
+    list
 }
 
+/// A status which is currently affecting a character.
+#[derive(Debug, Clone, PartialEq)]
 pub struct StatusEffect {
-    pub data: StatusData,
-    pub turns: i32,
+    pub def: StatusDef,
+    pub turns: usize,
+    pub stacks: usize,
     pub strength: f32,
+    /// The character which inflicted the status, if any.
+    pub source: Option<Entity>,
 }
 
-use crate::game::character::{Character, LastDamaged, WeaponSlot};
-use crate::game::combat::{TickTurn, Team};
+impl StatusEffect {
+    pub fn new(def: StatusDef, turns: usize, strength: f32, source: Option<Entity>) -> Self {
+        Self {
+            def,
+            turns,
+            stacks: 1,
+            strength,
+            source,
+        }
+    }
 
+    pub fn modifiers(&self) -> StatusModifiers {
+        self.def.modifiers.scaled(self.strength, self.stacks)
+    }
+
+    /// The proportion of max health which is restored (or lost, if negative) this tick.
+    pub fn health_per_tick(&self) -> f32 {
+        self.def.health_per_tick * self.strength * self.stacks as f32
+    }
+
+    /// Applies the stacking rules of the status when it is inflicted again.
+    /// Returns the remaining turns if the new status had any effect.
+    pub fn stack(&mut self, turns: usize, strength: f32, source: Option<Entity>) -> Option<usize> {
+        match self.def.stacking {
+            StatusStacking::Extend => {
+                self.turns += turns;
+            },
+            StatusStacking::Refresh => {
+                self.turns = self.turns.max(turns);
+            },
+            StatusStacking::Stack => {
+                if self.stacks < self.def.max_stacks {
+                    self.stacks += 1;
+                }
+                self.turns = self.turns.max(turns);
+            },
+            StatusStacking::Ignore => return None,
+        }
+        self.strength = self.strength.max(strength);
+        if source.is_some() {
+            self.source = source;
+        }
+        Some(self.turns)
+    }
+}
+
+/// Evaluates the statuses of every character when the turn is ticked.
 #[derive(Debug, new, SystemDesc)]
 #[system_desc(name(StatusSystemDesc))]
 pub struct StatusSystem {
@@ -75,24 +324,116 @@ impl<'s> System<'s> for StatusSystem {
         ReadStorage<'s, Parent>,
         ReadStorage<'s, Team>,
         WriteStorage<'s, Character>,
+        WriteStorage<'s, LastDamaged>,
         Read<'s, EventChannel<TickTurn>>,
     );
 
-    fn run(&mut self, (entities, parents, teams, mut characters, tick_turn_events): Self::SystemData) {
+    fn setup(&mut self, world: &mut World) {
+        world.insert(status_list());
+    }
+
+    fn run(&mut self, (entities, parents, teams, mut characters, mut last_damaged, tick_turn_events): Self::SystemData) {
         for event in tick_turn_events.read(&mut self.tick_turn_event_reader) {
             for (character_ent, mut character) in (&entities, &mut characters).join() {
-                if let Some((team, team_ent)) = Team::get_team(&parents, &teams, character_ent) {
-                    if team == event.next_team.other() {
-                        character.decrement_status(StatusType::Unstable);
-                        character.decrement_status(StatusType::Scramble);
-                        character.decrement_status(StatusType::Empower);
-                        character.decrement_status(StatusType::Focus);
+                if let Some((team, _)) = Team::get_team(&parents, &teams, character_ent) {
+                    let tick: StatusTick = if event.is_own_turn(character_ent, team) {
+                        StatusTick::OwnTurn
+                    } else if team != event.ended_team {
+                        StatusTick::OpposingTurn
                     } else {
-                        character.decrement_status(StatusType::Defend);
+                        // The teammates of the character which acted in the initiative turn mode.
+                        continue;
+                    };
+                    for (delta_health, source) in character.tick_statuses(tick) {
+                        if delta_health < 0.0 {
+                            last_damaged.insert(character_ent, LastDamaged {
+                                entity: source,
+                                dmg: -delta_health,
+                            });
+                        }
                     }
                 }
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(stacking: StatusStacking, max_stacks: usize) -> StatusDef {
+        StatusDef {
+            ty: StatusType::Burning,
+            name: "Burning".to_string(),
+            desc: String::new(),
+            tick: StatusTick::OwnTurn,
+            stacking,
+            max_stacks,
+            health_per_tick: -0.1,
+            modifiers: StatusModifiers {
+                dmg_receive: 1.5,
+                ..Default::default()
+            },
+            stun: false,
+            immunities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn extend_adds_the_turns() {
+        let mut effect = StatusEffect::new(def(StatusStacking::Extend, 1), 2, 1.0, None);
+        assert_eq!(effect.stack(3, 1.0, None), Some(5));
+        assert_eq!(effect.stacks, 1);
+    }
+
+    #[test]
+    fn refresh_keeps_the_longer_turns() {
+        let mut effect = StatusEffect::new(def(StatusStacking::Refresh, 1), 3, 1.0, None);
+        assert_eq!(effect.stack(2, 1.0, None), Some(3));
+        assert_eq!(effect.stack(4, 1.0, None), Some(4));
+    }
+
+    #[test]
+    fn stack_is_limited_to_the_max_stacks() {
+        let mut effect = StatusEffect::new(def(StatusStacking::Stack, 2), 2, 1.0, None);
+        assert_eq!(effect.stack(3, 1.0, None), Some(3));
+        assert_eq!(effect.stack(1, 1.0, None), Some(3));
+        assert_eq!(effect.stacks, 2);
+        assert!((effect.health_per_tick() - -0.2).abs() < 1e-6);
+        assert!((effect.modifiers().dmg_receive - 2.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ignore_has_no_effect() {
+        let mut effect = StatusEffect::new(def(StatusStacking::Ignore, 1), 2, 1.0, None);
+        assert_eq!(effect.stack(5, 2.0, None), None);
+        assert_eq!(effect.turns, 2);
+        assert_eq!(effect.strength, 1.0);
+    }
+
+    #[test]
+    fn shipped_statuses_define_every_status() {
+        let app_root = application_root_dir().expect("Failed to find the application root!");
+        let bytes: Vec<u8> = fs::read(app_root.join(STATUS_FILE)).expect("Failed to read the statuses!");
+        let defs: Vec<StatusDef> = parse_statuses(bytes).expect("Failed to parse the statuses!");
+        for ty in StatusType::all() {
+            assert!(defs.iter().any(|def| def.ty == ty), "{:?} is not defined", ty);
+        }
+    }
 
+    #[test]
+    fn missing_file_falls_back_to_the_built_in_statuses() {
+        let list: StatusList = load_statuses("missing_statuses.ron");
+        for ty in StatusType::all() {
+            assert!(list.get(ty).is_some(), "{:?} is not defined", ty);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn stacking_keeps_the_stronger_strength() {
+        let mut effect = StatusEffect::new(def(StatusStacking::Extend, 1), 1, 2.0, None);
+        effect.stack(1, 0.5, None);
+        assert_eq!(effect.strength, 2.0);
+    }
+}
@@ -20,7 +20,7 @@ use combat_render::flash::Flash;
 use crate::game::character::{Character, LastDamaged, WeaponSlot};
 use crate::game::combat::ability::{ChargeEvent, DmgPackage, DmgTimer, HealEvent, MissEvent};
 use crate::game::ui::hud::UiBase;
-use crate::game::ui::marker::ShowUiMarkerEvent;
+use crate::game::ui::marker::{MarkerUiCompletedEvent, ShowUiMarkerEvent};
 
 pub const BUFF_MARKER_ANIM_TIME: f32 = 0.7;
 
@@ -43,14 +43,16 @@ impl<'s> System<'s> for BuffSystem {
         Write<'s, EventChannel<HealEvent>>,
         Write<'s, EventChannel<ChargeEvent>>,
         Write<'s, EventChannel<ShowUiMarkerEvent>>,
+        Write<'s, EventChannel<MarkerUiCompletedEvent>>,
         Read<'s, Time>,
     );
 
-    fn run(&mut self, (entities, mut characters, ui_bases, ui_transforms, mut dmg_events, charge_events, mut show_marker_events, time): Self::SystemData) {
+    fn run(&mut self, (entities, mut characters, ui_bases, ui_transforms, mut dmg_events, charge_events, mut show_marker_events, mut completed_events, time): Self::SystemData) {
         for event in dmg_events.read(&mut self.heal_event_reader) {
             // Inflict damage on character.
             if let Some(character) = characters.get_mut(event.target) {
                 let delta_health = character.change_health(event.heal_value);
+                let mut shown: bool = false;
                 if let Some(ui_base) = ui_bases.get(event.target) {
                     if let Some(ui_transform) = ui_transforms.get(ui_base.entity()) {
                         show_marker_events.single_write(
@@ -65,8 +67,13 @@ impl<'s> System<'s> for BuffSystem {
                                 character: Some(event.target),
                             }
                         );
+                        shown = true;
                     }
                 }
+                // Nothing to wait on without a marker (e.g. when running headless).
+                if !shown {
+                    completed_events.single_write(MarkerUiCompletedEvent { owner: event.owner });
+                }
             }
         }
 
@@ -74,6 +81,7 @@ impl<'s> System<'s> for BuffSystem {
             // Inflict damage on character.
             if let Some(character) = characters.get_mut(event.target) {
                 let delta_charge = character.add_charge(event.charge_value);
+                let mut shown: bool = false;
                 if let Some(ui_base) = ui_bases.get(event.target) {
                     if let Some(ui_transform) = ui_transforms.get(ui_base.entity()) {
                         show_marker_events.single_write(
@@ -88,8 +96,13 @@ impl<'s> System<'s> for BuffSystem {
                                 character: Some(event.target),
                             }
                         );
+                        shown = true;
                     }
                 }
+                // Nothing to wait on without a marker (e.g. when running headless).
+                if !shown {
+                    completed_events.single_write(MarkerUiCompletedEvent { owner: event.owner });
+                }
             }
         }
     }
@@ -17,12 +17,33 @@ use amethyst::{
 };
 use combat_render::flash::Flash;
 
+use crate::core::CombatRng;
 use crate::game::character::{Character, LastDamaged, WeaponSlot};
 use crate::game::combat::ability::{DmgPackage, DmgTimer, MissEvent};
+use crate::game::combat::Team;
+use crate::game::combat::difficulty::DifficultyProfile;
+use crate::game::combat::element::{element_config, ElementConfig};
+use crate::game::combat::pipeline::{DmgPipeline, DmgResolvedEvent, HitOutcome};
+use crate::game::combat::status::{StatusInflictedEvent, StatusList};
 use crate::game::ui::hud::UiBase;
 use crate::game::ui::marker::ShowUiMarkerEvent;
 
 pub const DMG_MARKER_ANIM_TIME: f32 = 0.7;
+pub const EFFECTIVENESS_MARKER_OFFSET: f32 = 30.0;
+pub const SUPER_EFFECTIVE_COLOR: [f32; 4] = [1.0, 0.6, 0.0, 1.0];
+pub const NOT_EFFECTIVE_COLOR: [f32; 4] = [0.6, 0.6, 0.6, 1.0];
+pub const DMG_COLOR: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+pub const CRITICAL_COLOR: [f32; 4] = [1.0, 0.85, 0.0, 1.0];
+pub const GLANCING_COLOR: [f32; 4] = [0.75, 0.45, 0.45, 1.0];
+
+/// The text and colour of the damage marker for each outcome.
+fn dmg_marker(dmg: f32, outcome: HitOutcome) -> (String, [f32; 4]) {
+    match outcome {
+        HitOutcome::Normal => (format!("{:.1}", dmg), DMG_COLOR),
+        HitOutcome::Critical => (format!("Critical! {:.1}", dmg), CRITICAL_COLOR),
+        HitOutcome::Glancing => (format!("Glancing {:.1}", dmg), GLANCING_COLOR),
+    }
+}
 
 #[derive(Debug, new, SystemDesc)]
 #[system_desc(name(DmgSystemDesc))]
@@ -37,6 +58,8 @@ pub struct DmgSystem {
 impl<'s> System<'s> for DmgSystem {
     type SystemData = (
         Entities<'s>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
         WriteStorage<'s, Character>,
         WriteStorage<'s, DmgTimer>,
         WriteStorage<'s, UiBase>,
@@ -46,9 +69,20 @@ impl<'s> System<'s> for DmgSystem {
         Read<'s, EventChannel<MissEvent>>,
         Write<'s, EventChannel<ShowUiMarkerEvent>>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
+        Read<'s, ElementConfig>,
+        Read<'s, StatusList>,
+        Read<'s, DmgPipeline>,
+        Read<'s, DifficultyProfile>,
+        Write<'s, EventChannel<DmgResolvedEvent>>,
+        Write<'s, EventChannel<StatusInflictedEvent>>,
     );
 
-    fn run(&mut self, (entities, mut characters, mut dmg_timers, ui_bases, ui_transforms, mut last_damaged, mut dmg_events, miss_events, mut show_marker_events, time): Self::SystemData) {
+    fn setup(&mut self, world: &mut World) {
+        world.insert(element_config());
+    }
+
+    fn run(&mut self, (entities, parents, teams, mut characters, mut dmg_timers, ui_bases, ui_transforms, mut last_damaged, mut dmg_events, miss_events, mut show_marker_events, time, mut rng, element_config, status_list, pipeline, difficulty, mut dmg_resolved_events, mut status_events): Self::SystemData) {
         for (entity, mut dmg_timer) in (&entities, &mut dmg_timers).join() {
             if dmg_timer.timer <= 0.0 {
                 dmg_events.single_write(
@@ -59,37 +93,81 @@ impl<'s> System<'s> for DmgSystem {
             dmg_timer.timer -= time.delta_seconds();
         }
 
+        let mut follow_ups: Vec<DmgPackage> = Vec::new();
         for event in dmg_events.read(&mut self.dmg_package_event_reader) {
             // Inflict damage on character.
-            if let Some(character) = characters.get_mut(event.target) {
-                if let Ok(received) = Character::inflict_dmg_silent(&mut characters, event.source, event.target, event.power, event.element) {
+            if let Some(character) = characters.get(event.target) {
+                let element_multiplier: f32 = character.element_multiplier(event.element, &element_config);
+                let source_team: Option<Team> = event.source
+                    .and_then(|source_ent| Team::get_team(&parents, &teams, source_ent))
+                    .map(|(team, _)| team);
+                if let Ok(result) = Character::inflict_dmg_silent(&mut rng, &mut characters, &pipeline, &element_config, &difficulty, source_team, event) {
+                    let received: f32 = result.breakdown.total;
+                    let outcome: HitOutcome = result.breakdown.outcome;
+                    dmg_resolved_events.single_write(
+                        DmgResolvedEvent {
+                            package: *event,
+                            breakdown: result.breakdown,
+                        }
+                    );
+                    follow_ups.extend(result.follow_ups);
+                    last_damaged.insert(event.target, LastDamaged {
+                        entity: event.source,
+                        dmg: received,
+                    });
                     if let Some(ui_base) = ui_bases.get(event.target) {
                         if let Some(ui_transform) = ui_transforms.get(ui_base.entity()) {
+                            let (text, text_color): (String, [f32; 4]) = dmg_marker(received, outcome);
                             show_marker_events.single_write(
                                 ShowUiMarkerEvent {
                                     owner: None,
                                     position: Vector2::new(ui_transform.local_x, ui_transform.local_y),
-                                    text: format!("{:.1}", received),
-                                    text_color: [1.0, 0.0, 0.0, 1.0],
+                                    text,
+                                    text_color,
                                     anim_vel: Some(Vector2::new(0.0, 100.0)),
                                     anim_time: DMG_MARKER_ANIM_TIME,
                                     fade: true,
                                     character: Some(event.target),
                                 }
                             );
-                            last_damaged.insert(event.target, LastDamaged {
-                                entity: event.source,
-                                dmg: received,
-                            });
+                            let effectiveness: Option<(&str, [f32; 4])> = if element_multiplier > 1.0 {
+                                Some(("Super effective!", SUPER_EFFECTIVE_COLOR))
+                            } else if element_multiplier < 1.0 {
+                                Some(("Not very effective", NOT_EFFECTIVE_COLOR))
+                            } else {
+                                None
+                            };
+                            if let Some((text, text_color)) = effectiveness {
+                                show_marker_events.single_write(
+                                    ShowUiMarkerEvent {
+                                        owner: None,
+                                        position: Vector2::new(ui_transform.local_x, ui_transform.local_y + EFFECTIVENESS_MARKER_OFFSET),
+                                        text: text.to_string(),
+                                        text_color,
+                                        anim_vel: Some(Vector2::new(0.0, 100.0)),
+                                        anim_time: DMG_MARKER_ANIM_TIME,
+                                        fade: true,
+                                        character: Some(event.target),
+                                    }
+                                );
+                            }
                         }
                     }
                     if let Some(status) = event.status {
-                        Character::inflict_status_silent(&mut characters, event.target, status);
+                        Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, event.source, event.target, status);
+                    }
+                    // Every element has a chance to inflict its own status on top of the attack's effect.
+                    if let Some(status) = element_config.proc(event.element).and_then(|element_proc| element_proc.select(&mut rng)) {
+                        Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, event.source, event.target, status);
                     }
                 }
             }
         }
 
+        for package in follow_ups {
+            dmg_events.single_write(package);
+        }
+
         for event in miss_events.read(&mut self.miss_event_reader) {
             if let Some(ui_base) = ui_bases.get(event.target) {
                 if let Some(ui_transform) = ui_transforms.get(ui_base.entity()) {
@@ -31,6 +31,7 @@ use amethyst::{
 use combat_render::flash::Flash;
 use rand::Rng;
 
+use crate::core::CombatRng;
 use crate::game::character::{Character, WeaponSlot};
 use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireBulletEvent, MissEvent};
 
@@ -63,13 +64,14 @@ impl<'s> System<'s> for FireBulletSystem {
         Write<'s, EventChannel<MissEvent>>,
         ReadExpect<'s, ParentHierarchy>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
     );
 
     fn setup(&mut self, world: &mut World) {
         self.sound_handle = Some(world.read_resource::<Loader>().load("music/gunshot.mp3", Mp3Format, (), &world.read_resource()));
     }
 
-    fn run(&mut self, (entities, mut characters, mut flashes, mut flash_timers, mut dmg_timers, mut transforms, names, weapon_slots, audio_assets, loader, output, fire_bullet_events, mut miss_events, hierarchy, time): Self::SystemData) {
+    fn run(&mut self, (entities, mut characters, mut flashes, mut flash_timers, mut dmg_timers, mut transforms, names, weapon_slots, audio_assets, loader, output, fire_bullet_events, mut miss_events, hierarchy, time, mut rng): Self::SystemData) {
         for (entity, flash, mut flash_timer) in (&entities, &flashes, &mut flash_timers).join() {
             if let Some(mut t) = flash_timer.0 {
                 t -= time.delta_seconds();
@@ -85,7 +87,7 @@ impl<'s> System<'s> for FireBulletSystem {
         }
 
         for event in fire_bullet_events.read(&mut self.reader) {
-            if let Ok(hit) = Character::check_hit(&characters, event.target, event.source, event.accuracy) {
+            if let Ok(hit) = Character::check_hit(&mut rng, &characters, event.target, event.source, event.accuracy) {
                 if hit {
                     let mut source_pos: Option<Vector3<f32>> = None;
 
@@ -101,6 +103,13 @@ impl<'s> System<'s> for FireBulletSystem {
                         }
                     }
 
+                    // Fall back to the character itself if it has no weapon slots (e.g. its prefab is not loaded).
+                    if source_pos.is_none() {
+                        if let Some(transform) = transforms.get(event.source) {
+                            source_pos = Some(Vector4::from(transform.global_matrix().column(3)).xyz());
+                        }
+                    }
+
                     if let Some(source) = source_pos {
                         let flash_ent: Entity = entities.create();
                         flashes.insert(flash_ent, Flash::default());
@@ -126,6 +135,8 @@ impl<'s> System<'s> for FireBulletSystem {
                                 power: event.power,
                                 element: Element::Kinetic,
                                 status: event.effect,
+                                scale: 1.0,
+                                hit: event.hit,
                             },
                             timer: 0.0,
                         });
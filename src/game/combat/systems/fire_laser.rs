@@ -29,8 +29,9 @@ use amethyst::{
 use combat_render::flash::Flash;
 use rand::Rng;
 
+use crate::core::CombatRng;
 use crate::game::character::{Character, WeaponSlot};
-use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireLaserEvent, MissEvent, StatusInflictDesc};
+use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireLaserEvent, HitDesc, MissEvent, StatusInflictDesc};
 use crate::game::map::WorldPrefabData;
 use crate::game::combat::{CombatRoot, CombatState};
 
@@ -40,8 +41,10 @@ pub struct Laser {
     pub target: Entity,
     pub source: Option<Entity>,
     pub power: f32,
+    pub scale: f32,
     pub element: Element,
     pub effect: Option<StatusInflictDesc>,
+    pub hit_desc: HitDesc,
     pub hit: bool,
 }
 
@@ -73,6 +76,7 @@ impl<'s> System<'s> for FireLaserSystem {
         ReadExpect<'s, ParentHierarchy>,
         PrefabLoader<'s, WorldPrefabData>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
     );
 
     /*
@@ -89,7 +93,7 @@ impl<'s> System<'s> for FireLaserSystem {
     }
 */
 
-    fn run(&mut self, (entities, roots, mut characters, mut lasers, mut transforms, mut prefabs, names, weapon_slots, fire_laser_events, mut miss_events, mut dmg_events, hierarchy, prefab_loader, time): Self::SystemData) {
+    fn run(&mut self, (entities, roots, mut characters, mut lasers, mut transforms, mut prefabs, names, weapon_slots, fire_laser_events, mut miss_events, mut dmg_events, hierarchy, prefab_loader, time, mut rng): Self::SystemData) {
         if self.laser.is_none() {
             for root in roots.join() {
                 if root.current_state == CombatState::Init {
@@ -115,6 +119,8 @@ impl<'s> System<'s> for FireLaserSystem {
                             element: laser.element,
                             source: laser.source,
                             status: laser.effect,
+                            scale: laser.scale,
+                            hit: laser.hit_desc,
                         }
                     );
                 } else {
@@ -130,7 +136,7 @@ impl<'s> System<'s> for FireLaserSystem {
         }
 
         for event in fire_laser_events.read(&mut self.reader) {
-            if let Ok(hit) = Character::check_hit(&characters, event.target, event.source, event.accuracy) {
+            if let Ok(hit) = Character::check_hit(&mut rng, &characters, event.target, event.source, event.accuracy) {
                 let mut source_pos: Option<Vector3<f32>> = None;
                 for (weapon_slot, transform, _) in (&weapon_slots, &transforms, hierarchy.all_children(event.source)).join() {
                     // Get bullet source point.
@@ -144,6 +150,13 @@ impl<'s> System<'s> for FireLaserSystem {
                     }
                 }
 
+                // Fall back to the character itself if it has no weapon slots (e.g. its prefab is not loaded).
+                if source_pos.is_none() {
+                    if let Some(transform) = transforms.get(event.source) {
+                        source_pos = Some(Vector4::from(transform.global_matrix().column(3)).xyz());
+                    }
+                }
+
                 if let Some(source) = source_pos {
                     if let Some(target_trans) = transforms.get(event.target).cloned() {
                         let pos: Vector3<f32> = Vector4::from(target_trans.global_matrix().column(3)).xyz();
@@ -165,8 +178,10 @@ impl<'s> System<'s> for FireLaserSystem {
                             source: Some(event.source),
                             target: event.target,
                             power: event.power,
+                            scale: event.scale,
                             element: event.element,
                             effect: event.effect,
+                            hit_desc: event.hit,
                             timer: event.time,
                             hit,
                         });
@@ -33,8 +33,8 @@ use combat_render::flash::Flash;
 use rand::Rng;
 
 use crate::game::character::{Character, WeaponSlot};
-use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireTorpedoEvent, MissEvent, StatusInflictDesc};
-use crate::core::roll;
+use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireTorpedoEvent, HitDesc, MissEvent, StatusInflictDesc};
+use crate::core::{CombatRng, roll};
 use crate::game::map::WorldPrefabData;
 use crate::game::combat::{CombatRoot, CombatState};
 
@@ -57,7 +57,8 @@ pub struct Torpedo {
     speed: f32,
     power: f32,
     element: Element,
-    effect: Option<StatusInflictDesc>
+    effect: Option<StatusInflictDesc>,
+    hit_desc: HitDesc,
 }
 
 impl<'s> System<'s> for FireTorpedoSystem {
@@ -77,6 +78,7 @@ impl<'s> System<'s> for FireTorpedoSystem {
         ReadExpect<'s, ParentHierarchy>,
         PrefabLoader<'s, WorldPrefabData>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
     );
 
     /*
@@ -96,7 +98,7 @@ impl<'s> System<'s> for FireTorpedoSystem {
     }
 */
 
-    fn run(&mut self, (entities, roots, mut characters, mut flashes, mut torpedoes, mut transforms, mut model_prefabs, names, weapon_slots, fire_bullet_events, mut miss_events, mut dmg_packages, hierarchy, prefab_loader, time): Self::SystemData) {
+    fn run(&mut self, (entities, roots, mut characters, mut flashes, mut torpedoes, mut transforms, mut model_prefabs, names, weapon_slots, fire_bullet_events, mut miss_events, mut dmg_packages, hierarchy, prefab_loader, time, mut rng): Self::SystemData) {
         if self.torpedo_model.is_none() {
             for root in roots.join() {
                 if root.current_state == CombatState::Init {
@@ -128,6 +130,8 @@ impl<'s> System<'s> for FireTorpedoSystem {
                                     source: torpedo.source,
                                     target: torpedo.target,
                                     status: torpedo.effect,
+                                    scale: 1.0,
+                                    hit: torpedo.hit_desc,
                                 }
                             );
                         } else {
@@ -145,7 +149,7 @@ impl<'s> System<'s> for FireTorpedoSystem {
         }
 
         for event in fire_bullet_events.read(&mut self.reader) {
-            if let Ok(hit) = Character::check_hit(&characters, event.target, event.source, event.accuracy) {
+            if let Ok(hit) = Character::check_hit(&mut rng, &characters, event.target, event.source, event.accuracy) {
                 let mut source_pos: Option<Vector3<f32>> = None;
                 for (weapon_slot, transform, _) in (&weapon_slots, &transforms, hierarchy.all_children(event.source)).join() {
                     // Get bullet source point.
@@ -158,6 +162,13 @@ impl<'s> System<'s> for FireTorpedoSystem {
                         source_pos = Some(source);
                     }
                 }
+                // Fall back to the character itself if it has no weapon slots (e.g. its prefab is not loaded).
+                if source_pos.is_none() {
+                    if let Some(transform) = transforms.get(event.source) {
+                        source_pos = Some(Vector4::from(transform.global_matrix().column(3)).xyz());
+                    }
+                }
+
                 if let Some(source_pos) = source_pos {
                     let torpedo_ent: Entity = entities.create();
                     transforms.insert(torpedo_ent, Transform::from(source_pos));
@@ -169,6 +180,7 @@ impl<'s> System<'s> for FireTorpedoSystem {
                         hit,
                         speed: TORPEDO_SPEED,
                         effect: event.effect,
+                        hit_desc: event.hit,
                     });
                     model_prefabs.insert(torpedo_ent, self.torpedo_model.clone().unwrap());
                 }
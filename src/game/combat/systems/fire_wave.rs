@@ -29,6 +29,7 @@ use amethyst::{
 use combat_render::flash::Flash;
 use rand::Rng;
 
+use crate::core::CombatRng;
 use crate::game::character::{Character, WeaponSlot};
 use crate::game::combat::ability::{DmgPackage, DmgTimer, Element, FireWaveEvent, MissEvent, StatusInflictDesc, WaveDmg};
 use crate::game::map::WorldPrefabData;
@@ -67,6 +68,7 @@ impl<'s> System<'s> for FireWaveSystem {
         ReadExpect<'s, ParentHierarchy>,
         PrefabLoader<'s, WorldPrefabData>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
     );
 
     /*
@@ -83,7 +85,7 @@ impl<'s> System<'s> for FireWaveSystem {
     }
     */
 
-    fn run(&mut self, (entities, roots, mut characters, mut waves, mut transforms, mut prefabs, names, weapon_slots, fire_wave_events, mut miss_events, mut dmg_events, hierarchy, prefab_loader, time): Self::SystemData) {
+    fn run(&mut self, (entities, roots, mut characters, mut waves, mut transforms, mut prefabs, names, weapon_slots, fire_wave_events, mut miss_events, mut dmg_events, hierarchy, prefab_loader, time, mut rng): Self::SystemData) {
 
         if self.wave.is_none() {
             for root in roots.join() {
@@ -106,7 +108,7 @@ impl<'s> System<'s> for FireWaveSystem {
                 if let Some(dmg) = wave.dmg {
                     if let Some(source_ent) = wave.source {
                         for target_ent in wave.targets.iter() {
-                            if let Ok(hit) = Character::check_hit(&characters, *target_ent, source_ent, dmg.accuracy) {
+                            if let Ok(hit) = Character::check_hit(&mut rng, &characters, *target_ent, source_ent, dmg.accuracy) {
                                 if hit {
                                     dmg_events.single_write(
                                         DmgPackage {
@@ -115,6 +117,8 @@ impl<'s> System<'s> for FireWaveSystem {
                                             element: dmg.element,
                                             source: Some(source_ent),
                                             status: dmg.effect,
+                                            scale: 1.0,
+                                            hit: dmg.hit,
                                         }
                                     );
                                 } else {
@@ -146,6 +150,13 @@ impl<'s> System<'s> for FireWaveSystem {
                 }
             }
 
+            // Fall back to the character itself if it has no weapon slots (e.g. its prefab is not loaded).
+            if source_pos.is_none() {
+                if let Some(transform) = transforms.get(event.source) {
+                    source_pos = Some(Vector4::from(transform.global_matrix().column(3)).xyz());
+                }
+            }
+
             if let Some(source) = source_pos {
                 let mut transform: Transform = Transform::from(source);
                 let wave_ent: Entity = entities.create();
@@ -20,8 +20,9 @@ use amethyst::{
 };
 use rand::Rng;
 
-use crate::core::get_root_mut;
+use crate::core::{CombatRng, get_root_mut};
 use crate::game::character::{Character, CharacterDefeatedEvent, Defeated, LastDamaged};
+use crate::game::combat::Team;
 use crate::game::combat::spawn::{SlotManager, Slots};
 use crate::game::ui::hud::UiBase;
 
@@ -40,6 +41,7 @@ impl<'s> System<'s> for CharacterDefeatSystem {
         Entities<'s>,
         WriteStorage<'s, Parent>,
         WriteStorage<'s, Character>,
+        ReadStorage<'s, Team>,
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Defeated>,
         ReadStorage<'s, LastDamaged>,
@@ -47,9 +49,10 @@ impl<'s> System<'s> for CharacterDefeatSystem {
         WriteStorage<'s, SlotManager>,
         Write<'s, EventChannel<CharacterDefeatedEvent>>,
         Read<'s, Time>,
+        Write<'s, CombatRng>,
     );
 
-    fn run(&mut self, (entities, parents, mut characters, mut transforms, mut defeated, last_damaged, mut ui_bases, mut slot_managers, mut character_defeated_events, time): Self::SystemData) {
+    fn run(&mut self, (entities, parents, mut characters, teams, mut transforms, mut defeated, last_damaged, mut ui_bases, mut slot_managers, mut character_defeated_events, time, mut rng): Self::SystemData) {
         for (entity, character) in (&entities, &characters).join() {
             if character.relative_health() <= 0.0 {
                 let killer: Option<Entity> = {
@@ -62,6 +65,9 @@ impl<'s> System<'s> for CharacterDefeatSystem {
                 character_defeated_events.single_write(
                     CharacterDefeatedEvent {
                         character_ent: entity,
+                        character_id: character.id(),
+                        name: character.name(),
+                        team: Team::get_team(&parents, &teams, entity).map(|(team, _)| team),
                         splash_dmg: character.max_health() * SPLASH_PROPORTION,
                         killer,
                     }
@@ -71,7 +77,6 @@ impl<'s> System<'s> for CharacterDefeatSystem {
 
         for event in character_defeated_events.read(&mut self.reader) {
             characters.remove(event.character_ent);
-            let mut rng = rand::thread_rng();
             // TODO: Perhaps chance of crashing if all 3 values of normalized vector are 0.
             defeated.insert(event.character_ent, Defeated {
                 drift: Vector3::new(rng.gen_range(-0.5, 0.5), -2.0 + rng.gen_range(-0.7, 0.7), rng.gen_range(-0.5, 0.5)),
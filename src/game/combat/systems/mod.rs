@@ -1,6 +1,5 @@
 pub mod character_idle;
 pub mod standard_combat;
-pub mod earth_combat;
 pub mod delay;
 pub mod dmg;
 pub mod buff;
@@ -1,8 +1,5 @@
 use amethyst::{
-    core::{
-        Parent,
-        ParentHierarchy,
-    },
+    core::Parent,
     ecs::prelude::*,
     prelude::SystemDesc,
     shrev::EventChannel,
@@ -18,24 +15,27 @@ use crate::{
         combat::{
             CombatRoot,
             CombatState,
+            initiative::InitiativeQueue,
             spawn::{SlotManager, Slots},
             TickTurn,
+            TurnMode,
         },
     },
 };
 use crate::game::combat::{Team};
 use crate::game::combat::process::Principal;
 use crate::game::combat::systems::delay::Delay;
-use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
 use crate::game::character::CharacterDefeatedEvent;
 use crate::game::ui::banner::ShowUiBannerDisplayEvent;
 use crate::game::ui::crosshair::UiCrosshair;
-use crate::game::combat::ability::hack::HackPerformedEvent;
+use crate::game::combat::ability::flee::FleeEvent;
 use crate::core::get_root;
-use crate::game::combat::ability::{DmgPackage, Element};
+use crate::game::combat::ability::{DmgPackage, Element, HitDesc};
 
+/// The time the victory or defeat banner is shown for before the results screen.
 pub const EXIT_TIMER: f32 = 3.0;
 
+/// Runs the turns of every combat. Whether the combat has been won or lost is decided by its rule (see `CombatRuleSystem`).
 #[derive(Debug, new, SystemDesc)]
 #[system_desc(name(StandardCombatSystemDesc))]
 pub struct StandardCombatSystem {
@@ -43,7 +43,7 @@ pub struct StandardCombatSystem {
     pub defeated_event_reader: ReaderId<CharacterDefeatedEvent>,
 
     #[system_desc(event_channel_reader)]
-    pub hack_event_reader: ReaderId<HackPerformedEvent>,
+    pub flee_event_reader: ReaderId<FleeEvent>,
 }
 
 #[derive(Debug, Copy, Clone, Default, Component)]
@@ -52,6 +52,34 @@ pub struct HasInitTag;
 pub struct ExitCombat {
     pub timer: f32,
     pub(crate) winner: Option<Team>,
+    /// Set once the player has dismissed the results screen. The combat is only left after this.
+    pub dismissed: bool,
+    /// Set if the player escaped, in which case the campaign does not progress.
+    pub fled: bool,
+}
+
+impl ExitCombat {
+    pub fn new(timer: f32, winner: Option<Team>) -> Self {
+        Self {
+            timer,
+            winner,
+            dismissed: false,
+            fled: false,
+        }
+    }
+
+    pub fn fled(timer: f32) -> Self {
+        Self {
+            timer,
+            winner: None,
+            dismissed: false,
+            fled: true,
+        }
+    }
+
+    pub fn winner(&self) -> Option<Team> {
+        self.winner
+    }
 }
 
 impl<'s> System<'s> for StandardCombatSystem {
@@ -66,17 +94,20 @@ impl<'s> System<'s> for StandardCombatSystem {
         ReadStorage<'s, Team>,
         ReadStorage<'s, UiCrosshair>,
         WriteStorage<'s, Delay>,
-        ReadExpect<'s, ParentHierarchy>,
         Write<'s, Option<ExitCombat>>,
         Write<'s, EventChannel<TickTurn>>,
         Read<'s, EventChannel<CharacterDefeatedEvent>>,
-        Write<'s, EventChannel<SpawnWaveEvent>>,
         Write<'s, EventChannel<ShowUiBannerDisplayEvent>>,
         Write<'s, EventChannel<DmgPackage>>,
-        Read<'s, EventChannel<HackPerformedEvent>>,
+        Read<'s, EventChannel<FleeEvent>>,
     );
 
-    fn run(&mut self, (entities, mut characters, mut slot_managers, mut combat_roots, mut has_init_tags, mut principals, parents, teams, crosshairs, mut delays, hierarchy, mut exit, mut tick_evt, defeated_events, mut spawn_wave_events, mut show_banners, mut dmg_events, hacked_events): Self::SystemData) {
+    fn run(&mut self, (entities, mut characters, mut slot_managers, mut combat_roots, mut has_init_tags, mut principals, parents, teams, crosshairs, mut delays, mut exit, mut tick_evt, defeated_events, mut show_banners, mut dmg_events, flee_events): Self::SystemData) {
+        // The combats the player has escaped from. Every combat has to see these, so they are read before the loop.
+        let escaped_roots: Vec<Entity> = flee_events.read(&mut self.flee_event_reader)
+            .filter(|event| event.escaped)
+            .filter_map(|event| get_root::<CombatRoot, _, _>(&parents, &combat_roots, event.character_ent).map(|(_, root_ent)| root_ent))
+            .collect();
         for (entity, mut root, slot_manager) in (&entities, &mut combat_roots, &slot_managers).join() {
             // Only execute in no principals are running.
             if Principal::is_root_engaged(
@@ -88,12 +119,30 @@ impl<'s> System<'s> for StandardCombatSystem {
                 match root.current_state {
                     CombatState::Init => {
                         if has_init_tags.contains(entity) {
-                            root.current_state = CombatState::InTurn(Team::Friendly);
+                            let mut first_team: Team = Team::Friendly;
+                            if root.data.turn_mode() == TurnMode::Initiative {
+                                let participants: Vec<(Entity, f32)> = InitiativeQueue::participants(slot_manager, &characters);
+                                if let Some(actor) = root.initiative.advance(&participants) {
+                                    // The first character already has its initial turns.
+                                    hold_turns(&mut characters, &participants, actor);
+                                    if let Some((team, _)) = Team::get_team(&parents, &teams, actor) {
+                                        first_team = team;
+                                    }
+                                }
+                            }
+                            root.current_state = CombatState::InTurn(first_team);
                         } else {
                             has_init_tags.insert(entity, HasInitTag::default());
                         }
                     }
                     CombatState::InTurn(team) => {
+                        if root.data.turn_mode() == TurnMode::Initiative {
+                            // Characters which join during the turn (e.g. when they are spawned) wait for their place in the queue.
+                            if let Some(actor) = root.initiative.current() {
+                                let participants: Vec<(Entity, f32)> = InitiativeQueue::participants(slot_manager, &characters);
+                                hold_turns(&mut characters, &participants, actor);
+                            }
+                        }
                         let mut active: bool = false;
                         // Check if all turns have been used.
                         for (i, entity_opt) in slot_manager.for_team(team).occupied().iter().enumerate() {
@@ -110,18 +159,41 @@ impl<'s> System<'s> for StandardCombatSystem {
                         }
                     }
                     CombatState::DoneTurn(team) => {
-                        let new_team: Team = team.other();
+                        let mut new_team: Team = team.other();
+                        let mut last_actor: Option<Entity> = None;
+                        let mut next_actor: Option<Entity> = None;
+                        if root.data.turn_mode() == TurnMode::Initiative {
+                            let participants: Vec<(Entity, f32)> = InitiativeQueue::participants(slot_manager, &characters);
+                            last_actor = root.initiative.current();
+                            next_actor = root.initiative.advance(&participants);
+                            if let Some(actor) = next_actor {
+                                hold_turns(&mut characters, &participants, actor);
+                                if let Some((actor_team, _)) = Team::get_team(&parents, &teams, actor) {
+                                    new_team = actor_team;
+                                }
+                            }
+                        }
+
                         // Tick
                         tick_evt.single_write(TickTurn {
                             count: root.turn_count,
+                            ended_team: team,
                             next_team: new_team,
+                            actor: last_actor,
                         });
+                        root.turn_count += 1;
 
                         // Start new turn.
-                        for (i, entity_opt) in slot_manager.for_team(new_team).occupied().iter().enumerate() {
-                            if let Some(character_ent) = *entity_opt {
-                                if let Some(character) = characters.get_mut(character_ent) {
-                                    character.restore();
+                        if let Some(actor) = next_actor {
+                            if let Some(character) = characters.get_mut(actor) {
+                                character.restore();
+                            }
+                        } else {
+                            for (i, entity_opt) in slot_manager.for_team(new_team).occupied().iter().enumerate() {
+                                if let Some(character_ent) = *entity_opt {
+                                    if let Some(character) = characters.get_mut(character_ent) {
+                                        character.restore();
+                                    }
                                 }
                             }
                         }
@@ -132,8 +204,7 @@ impl<'s> System<'s> for StandardCombatSystem {
                     _ => {}
                 }
             }
-            // Spawn in next wave.
-            let mut test_state: bool = false;
+            // Defeated characters damage their team's master drone.
             for event in defeated_events.read(&mut self.defeated_event_reader) {
                 if let Some((team, team_ent)) = Team::get_team(&parents, &teams, event.character_ent) {
                     if let Some((slot_manager, _)) = get_root::<SlotManager, _, _>(&parents, &slot_managers, team_ent) {
@@ -147,73 +218,41 @@ impl<'s> System<'s> for StandardCombatSystem {
                                     power,
                                     element: Element::Kinetic,
                                     status: None,
+                                    scale: 1.0,
+                                    hit: HitDesc::exact(),
                                 }
                             )
                         }
                     }
                 }
-                test_state = true;
             }
-            for event in hacked_events.read(&mut self.hack_event_reader) {
-                test_state = true;
-            }
-            if test_state {
-                if slot_manager.enemy.is_empty() {
-                    let idx: usize = root.current_wave + 1;
-                    if let Some(wave) = root.data.waves.get(idx) {
-                        let mut team_ent: Option<Entity> = None;
-                        for (ent, team, _) in (&entities, &teams, hierarchy.all_children(entity)).join() {
-                            if *team == Team::Enemy {
-                                team_ent = Some(ent);
-                                break;
-                            }
-                        }
-                        if let Some(team_ent) = team_ent {
-                            spawn_wave_events.single_write(
-                                SpawnWaveEvent {
-                                    wave: wave.clone(),
-                                    idx,
-                                    team_ent,
-                                }
-                            );
-                        }
-                        root.current_wave = idx;
-                    } else {
-                        show_banners.single_write(
-                            ShowUiBannerDisplayEvent {
-                                text: "Victory".to_string(),
-                                color: [0.0, 1.0, 0.0, 1.0],
-                                owner: entity,
-                            }
-                        );
-                        root.current_state = CombatState::Victory(Team::Friendly);
-                        *exit = Some(ExitCombat {
-                            timer: EXIT_TIMER,
-                            winner: Some(Team::Friendly),
-                        });
-                        for (crosshair_ent, _) in (&entities, crosshairs.mask()).join() {
-                            entities.delete(crosshair_ent);
-                        }
+            if escaped_roots.contains(&entity) {
+                show_banners.single_write(
+                    ShowUiBannerDisplayEvent {
+                        text: "Escaped".to_string(),
+                        color: [1.0, 1.0, 0.0, 1.0],
+                        owner: entity,
                     }
+                );
+                root.current_state = CombatState::Fled;
+                *exit = Some(ExitCombat::fled(EXIT_TIMER));
+                for (crosshair_ent, _) in (&entities, crosshairs.mask()).join() {
+                    entities.delete(crosshair_ent);
                 }
-                if slot_manager.friendly.master().is_none() {
-                    show_banners.single_write(
-                        ShowUiBannerDisplayEvent {
-                            text: "Defeated".to_string(),
-                            color: [1.0, 0.0, 0.0, 1.0],
-                            owner: entity,
-                        }
-                    );
-                    root.current_state = CombatState::Victory(Team::Enemy);
-                    *exit = Some(ExitCombat {
-                        timer: EXIT_TIMER,
-                        winner: Some(Team::Enemy),
-                    });
-                    for (crosshair_ent, _) in (&entities, crosshairs.mask()).join() {
-                        entities.delete(crosshair_ent);
-                    }
+            }
+        }
+    }
+}
+
+/// Makes every character apart from the actor wait for its place in the initiative queue.
+fn hold_turns(characters: &mut WriteStorage<Character>, participants: &[(Entity, f32)], actor: Entity) {
+    for (character_ent, _) in participants.iter() {
+        if *character_ent != actor {
+            if let Some(character) = characters.get_mut(*character_ent) {
+                if !character.is_waiting() {
+                    character.wait();
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
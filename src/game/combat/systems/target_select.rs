@@ -167,7 +167,8 @@ impl<'s> System<'s> for SelectAbilityTargetSystem {
                 if select_target.perform_on_select {
                     if let Some(ability) = abilities.get(select_target.ability_ent) {
                         if Principal::try_root_engage(&parents, &mut principals, select_target.ability_ent, ability.data.system) == Some(true) {
-                            performs.insert(select_target.ability_ent, AbilityPerform::new(target));
+                            let charge: Option<f32> = invokes.get(select_target.ability_ent).and_then(|invoke| invoke.charge);
+                            performs.insert(select_target.ability_ent, AbilityPerform::with_charge(target, charge));
                             invokes.remove(select_target.ability_ent);
                         }
 
@@ -27,6 +27,9 @@ pub struct AiAbilitySelection {
 
     /// The target of this ability invocation.
     pub target: AbilityTarget,
+
+    /// The charge to invest in `AbilityCharge::Range` abilities. `None` invests the lowest charge.
+    pub charge: Option<f32>,
 }
 
 impl Component for AiAbilitySelectionQuery {
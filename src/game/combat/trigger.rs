@@ -0,0 +1,201 @@
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Loader,
+    },
+    audio::{
+        Mp3Format,
+        OggFormat,
+        output::Output,
+        Source,
+        SourceHandle,
+    },
+    core::{
+        Parent,
+        ParentHierarchy,
+    },
+    ecs::prelude::*,
+    log::warn,
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+};
+
+use crate::core::{CombatRng, get_root};
+use crate::game::character::{Character, CharacterDefeatedEvent, CharacterId};
+use crate::game::combat::{CharacterSpawn, CombatRoot, Team};
+use crate::game::combat::ability::StatusInflictDesc;
+use crate::game::combat::ability::hack::HackPerformedEvent;
+use crate::game::combat::process::Principal;
+use crate::game::combat::spawn::{SlotManager, SpawnAction};
+use crate::game::combat::status::{StatusInflictedEvent, StatusList};
+use crate::game::map::DialogueStore;
+use crate::game::ui::dialogue::ShowDialogueDisplayEvent;
+
+/// What causes a trigger to fire.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerCondition {
+    /// The combat reaches the turn count.
+    Turn(i32),
+    /// A character with the id drops below the proportion (from 0 to 1) of its max health.
+    HealthBelow(CharacterId, f32),
+    /// A character with the id is defeated.
+    Defeated(CharacterId),
+    /// A drone is hacked.
+    Hacked,
+}
+
+/// What happens when a trigger fires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerAction {
+    /// Spawns a reinforcement into the next free slot of the enemy team.
+    Spawn(CharacterSpawn),
+    /// Shows the dialogue with the name from the `DialogueStore`.
+    Dialogue(String),
+    /// Inflicts the status on every character with the id.
+    Status(CharacterId, StatusInflictDesc),
+    /// Plays the track, relative to the assets directory (e.g. `"music/theme.ogg"`).
+    Music(String),
+}
+
+/// A scripted event of a combat, which fires once when its condition is met.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub condition: TriggerCondition,
+    pub actions: Vec<TriggerAction>,
+}
+
+/// Fires the triggers of the combat. Fired triggers are recorded in the `CombatRoot`.
+#[derive(Debug, new, SystemDesc)]
+#[system_desc(name(TriggerSystemDesc))]
+pub struct TriggerSystem {
+    /// A track which is waiting to finish loading before it is played.
+    pending_music: Option<SourceHandle>,
+
+    #[system_desc(event_channel_reader)]
+    defeated_reader: ReaderId<CharacterDefeatedEvent>,
+
+    #[system_desc(event_channel_reader)]
+    hack_reader: ReaderId<HackPerformedEvent>,
+}
+
+impl<'s> System<'s> for TriggerSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, CombatRoot>,
+        ReadStorage<'s, SlotManager>,
+        WriteStorage<'s, Principal>,
+        WriteStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        WriteStorage<'s, Character>,
+        WriteStorage<'s, SpawnAction>,
+        ReadExpect<'s, ParentHierarchy>,
+        Write<'s, CombatRng>,
+        Read<'s, StatusList>,
+        Read<'s, DialogueStore>,
+        Read<'s, EventChannel<CharacterDefeatedEvent>>,
+        Read<'s, EventChannel<HackPerformedEvent>>,
+        Write<'s, EventChannel<StatusInflictedEvent>>,
+        Write<'s, EventChannel<ShowDialogueDisplayEvent>>,
+        Read<'s, AssetStorage<Source>>,
+        ReadExpect<'s, Loader>,
+        Option<Read<'s, Output>>,
+    );
+
+    fn run(&mut self, (entities, mut roots, slot_managers, mut principals, mut parents, teams, mut characters, mut spawn_actions, hierarchy, mut rng, status_list, dialogue_store, defeated_events, hack_events, mut status_events, mut show_dialogue_events, audio_assets, loader, output): Self::SystemData) {
+        // The defeated characters have already lost their `Character`, so the id is taken from the event.
+        let defeated: Vec<(Option<Entity>, CharacterId)> = defeated_events.read(&mut self.defeated_reader)
+            .map(|event| (get_root::<CombatRoot, _, _>(&parents, &roots, event.character_ent).map(|(_, root_ent)| root_ent), event.character_id))
+            .collect();
+        let hacked: bool = hack_events.read(&mut self.hack_reader).count() > 0;
+
+        for (root_ent, root, slot_manager) in (&entities, &mut roots, &slot_managers).join() {
+            let mut actions: Vec<TriggerAction> = Vec::new();
+            for (i, trigger) in root.data.triggers().iter().enumerate() {
+                if root.fired_triggers.contains(&i) {
+                    continue;
+                }
+                let met: bool = match &trigger.condition {
+                    TriggerCondition::Turn(turn) => root.turn_count >= *turn,
+                    TriggerCondition::HealthBelow(id, threshold) => (&characters, hierarchy.all_children(root_ent)).join()
+                        .any(|(character, _)| character.id() == *id && character.relative_health() < *threshold),
+                    TriggerCondition::Defeated(id) => defeated.contains(&(Some(root_ent), *id)),
+                    TriggerCondition::Hacked => hacked,
+                };
+                if met {
+                    root.fired_triggers.insert(i);
+                    actions.extend(trigger.actions.iter().cloned());
+                }
+            }
+
+            for action in actions {
+                match action {
+                    TriggerAction::Spawn(spawn) => {
+                        let team_ent: Option<Entity> = (&entities, &teams, hierarchy.all_children(root_ent)).join()
+                            .find(|(_, team, _)| **team == Team::Enemy)
+                            .map(|(team_ent, _, _)| team_ent);
+                        // Nothing is spawned if the enemy team is full.
+                        if let (Some(team_ent), Some(slot_idx)) = (team_ent, slot_manager.for_team(Team::Enemy).find_next(false)) {
+                            Character::invoke_spawn(
+                                &entities,
+                                &mut principals,
+                                &mut parents,
+                                &mut spawn_actions,
+                                team_ent,
+                                spawn.character_id,
+                                None,
+                                spawn.rank,
+                                Team::Enemy,
+                                slot_idx,
+                                false,
+                            );
+                        }
+                    },
+                    TriggerAction::Dialogue(name) => {
+                        if let Some(dialogue) = dialogue_store.dialogue_list.get(&name).cloned() {
+                            show_dialogue_events.single_write(
+                                ShowDialogueDisplayEvent {
+                                    dialogue,
+                                    owner: Some(root_ent),
+                                    start_idx: 0,
+                                    principal: true,
+                                }
+                            );
+                        } else {
+                            warn!("[TriggerSystem] No dialogue named `{}`.", name);
+                        }
+                    },
+                    TriggerAction::Status(id, inflict) => {
+                        let targets: Vec<Entity> = (&entities, &characters, hierarchy.all_children(root_ent)).join()
+                            .filter(|(_, character, _)| character.id() == id)
+                            .map(|(character_ent, _, _)| character_ent)
+                            .collect();
+                        for target_ent in targets {
+                            Character::inflict_status(&mut rng, &mut characters, &status_list, &mut status_events, None, target_ent, inflict);
+                        }
+                    },
+                    TriggerAction::Music(path) => {
+                        self.pending_music = if path.ends_with(".ogg") {
+                            Some(loader.load(path, OggFormat, (), &*audio_assets))
+                        } else {
+                            Some(loader.load(path, Mp3Format, (), &*audio_assets))
+                        };
+                    },
+                }
+            }
+        }
+
+        // The track is played as soon as it has loaded.
+        if let Some(handle) = self.pending_music.take() {
+            if let Some(sound) = audio_assets.get(&handle) {
+                if let Some(ref output) = output {
+                    output.play_once(sound, 1.0);
+                }
+            } else {
+                self.pending_music = Some(handle);
+            }
+        }
+    }
+}
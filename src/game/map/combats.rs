@@ -1,203 +1,96 @@
-use crate::game::map::CombatStore;
-use crate::game::combat::{CombatData, CharacterSpawn, Wave, Rank};
-use crate::game::character::*;
-pub fn combats() -> CombatStore {
-    let mut combats: CombatStore = CombatStore::default();
-    combats.combat_list.insert(
-        "pluto".to_string(),
-        CombatData::basic("pluto", "maps/sol/pluto.ron", vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Basic),
-            ]),
-        ])
-    );
-    combats.combat_list.insert(
-        "neptune".to_string(),
-        CombatData::basic("neptune", "maps/sol/neptune.ron",vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-        ])
-    );
-    combats.combat_list.insert(
-        "uranus".to_string(),
-        CombatData::basic("uranus", "maps/sol/uranus.ron",vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-        ])
-    );
-    combats.combat_list.insert(
-        "saturn".to_string(),
-        CombatData::basic("saturn", "maps/sol/saturn.ron",vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Elite),
+use std::fs;
+use std::path::Path;
+
+use amethyst::{
+    assets::{
+        Format,
+        Prefab,
+        RonFormat,
+    },
+    Error,
+    log::{error, warn},
+    utils::application_root_dir,
+};
+
+use crate::game::map::{CombatStore, MapPrefabData};
+use crate::game::combat::CombatData;
+
+/// The directory (relative to the application root) which contains the campaign combats.
+pub const COMBAT_DIR: &str = "assets/combats";
+/// The map (relative to the application root) whose points refer to the campaign combats.
+pub const MAP_FILE: &str = "assets/maps/map.ron";
 
-            ]),
-        ])
-    );
-    combats.combat_list.insert(
-        "jupiter".to_string(),
-        CombatData::basic("jupiter", "maps/sol/jupiter.ron",vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::boss(
-                CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-            ),
-        ])
-    );
-    combats.combat_list.insert(
-        "mars".to_string(),
-        CombatData::basic("mars", "maps/sol/mars.ron",vec! [
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-            ]),
-        ])
-    );
-    combats.combat_list.insert(
-        "moon".to_string(),
-        CombatData::basic("moon", "maps/sol/moon.ron",vec! [
-                Wave::boss(
-                    CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-                ),
-        Wave {
-            master: Some(CharacterSpawn::new(GuardianDrone::character_id(), Rank::Basic)),
-            characters: vec![
-                CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-            ],
+/// Loads the campaign combats from `assets/combats`.
+pub fn combats() -> CombatStore {
+    match application_root_dir() {
+        Ok(app_root) => load_combats(app_root.join(COMBAT_DIR)),
+        Err(err) => {
+            error!("[combats] Failed to find the application root: {:?}", err);
+            CombatStore::default()
         },
-        ])
-    );
-    combats.combat_list.insert(
-        "earth".to_string(),
-        CombatData::basic("earth", "maps/sol/earth.ron",vec! [
-            Wave::boss(
-                CharacterSpawn::new(EarthCharacter::character_id(), Rank::Basic),
-            ),
-        ])
-    );
+    }
+}
 
-    combats.combat_list.insert(
-        "venus".to_string(),
-        CombatData::basic("venus", "maps/sol/venus.ron",vec![
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Basic),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Elite),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ChargeSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Advanced),
-                CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Advanced),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-            ]),
-            Wave::new_simple(vec![
-                CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-                CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-            ]),
+/// Loads every combat in the specified directory, keyed by the name of the combat.
+/// Files which fail to load, or which have no waves, are skipped.
+pub fn load_combats(dir: impl AsRef<Path>) -> CombatStore {
+    let mut combats: CombatStore = CombatStore::default();
+    match fs::read_dir(dir.as_ref()) {
+        Ok(entries) => {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if path.extension().map_or(false, |ext| ext == "ron") {
+                    match fs::read(&path) {
+                        Ok(bytes) => {
+                            match Format::<CombatData>::import_simple(&RonFormat, bytes) {
+                                // Every combat starts with its first wave, so combats without waves can not be run.
+                                Ok(combat) if combat.waves().is_empty() => warn!("[load_combats] Skipped {:?}, which has no waves.", path),
+                                Ok(combat) => {
+                                    combats.combat_list.insert(combat.name().to_string(), combat);
+                                },
+                                Err(err) => warn!("[load_combats] Failed to parse {:?}: {:?}", path, err),
+                            }
+                        },
+                        Err(err) => warn!("[load_combats] Failed to read {:?}: {:?}", path, err),
+                    }
+                }
+            }
+        },
+        Err(err) => warn!("[load_combats] Failed to read combat directory {:?}: {:?}", dir.as_ref(), err),
+    }
+    combats
+}
 
-        ]),
-    );
+/// Checks that the combat of every point on the map has been loaded.
+/// This catches a misspelt combat, or a combat file which failed to load, at startup instead of when the player reaches it.
+pub fn validate_map(path: impl AsRef<Path>, combats: &CombatStore) -> Result<(), Error> {
+    let bytes: Vec<u8> = fs::read(path.as_ref())?;
+    let map: Prefab<MapPrefabData> = Format::<Prefab<MapPrefabData>>::import_simple(&RonFormat, bytes)?;
+    for point in map.entities().filter_map(|entity| entity.data()).filter_map(|data| data.map_point.as_ref()) {
+        if let Some(combat) = &point.combat {
+            combats.get(combat)
+                .map_err(|err| Error::from_string(format!("map point {} in {:?}: {}", point.index, path.as_ref(), err)))?;
+        }
+    }
+    Ok(())
+}
 
-    combats.combat_list.insert(
-        "mercury".to_string(),
-        CombatData::basic("mercury", "maps/sol/mercury.ron", vec! [
-                Wave::new_simple(vec![
-                    CharacterSpawn::new(GunnerSpacebotDrone::character_id(), Rank::Elite),
-                    CharacterSpawn::new(SupporterSpacebotDrone::character_id(), Rank::Elite),
-                    CharacterSpawn::new(SpacebotDrone::character_id(), Rank::Elite),
-                ]),
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                Wave::new_simple(vec![
-                    CharacterSpawn::new(ModelXDrone::character_id(), Rank::Legendary),
-                ]),
+    #[test]
+    fn map_only_refers_to_loaded_combats() {
+        let app_root = application_root_dir().expect("Failed to find the application root!");
+        let combats: CombatStore = load_combats(app_root.join(COMBAT_DIR));
+        if let Err(err) = validate_map(app_root.join(MAP_FILE), &combats) {
+            panic!("{}", err);
+        }
+    }
 
-                Wave::boss(
-                    CharacterSpawn::new(GuardianDrone::character_id(), Rank::Legendary),
-                ),
-        ])
-    );
-    combats
-}
\ No newline at end of file
+    #[test]
+    fn unknown_combats_are_rejected() {
+        let app_root = application_root_dir().expect("Failed to find the application root!");
+        assert!(validate_map(app_root.join(MAP_FILE), &CombatStore::default()).is_err());
+    }
+}
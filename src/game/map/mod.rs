@@ -35,6 +35,7 @@ use amethyst::{
         rendy::mesh::MeshBuilder,
         shape::FromShape,
     },
+    utils::application_root_dir,
 };
 use serde::{
     Deserialize,
@@ -46,12 +47,9 @@ use space_render::{
 };
 
 use crate::game::combat::{CombatData, Wave, CharacterSpawn, Rank, Difficulty};
-use std::collections::HashMap;
+use crate::game::combat::stats::LifetimeStats;
+use std::collections::{BTreeSet, HashMap};
 
-use crate::game::character::spacebot::{
-    GunnerSpacebotDrone,
-    ModelXDrone,
-};
 use crate::game::ui::dialogue::{Dialogue, DialogueSegment, DialogueText};
 use std::fs::File;
 
@@ -93,7 +91,9 @@ impl<'a, 'b> SystemBundle<'a, 'b> for MapBundle {
             &[],
         );
 
-        world.insert(combats::combats());
+        let combat_store: CombatStore = combats::combats();
+        combats::validate_map(application_root_dir()?.join(combats::MAP_FILE), &combat_store)?;
+        world.insert(combat_store);
 
         world.insert(load_current());
 
@@ -112,7 +112,7 @@ pub enum MapStage {
     Complete,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CurrentState {
     pub max_point: usize,
     pub max_stage: MapStage,
@@ -122,6 +122,14 @@ pub struct CurrentState {
 
     pub difficulty: Difficulty,
     pub has_cheats: bool,
+
+    /// Named flags which are set by dialogue choices, so that later dialogues can react to them.
+    #[serde(default)]
+    pub flags: BTreeSet<String>,
+
+    /// The combined stats of the player's drones over every combat.
+    #[serde(default)]
+    pub lifetime_stats: LifetimeStats,
 }
 
 impl Default for CurrentState {
@@ -135,10 +143,24 @@ impl Default for CurrentState {
 
             difficulty: Difficulty::Normal,
             has_cheats: true,
+
+            flags: BTreeSet::new(),
+            lifetime_stats: LifetimeStats::default(),
         }
     }
 }
 
+impl CurrentState {
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Returns true if the flag was not already set.
+    pub fn set_flag(&mut self, flag: impl Into<String>) -> bool {
+        self.flags.insert(flag.into())
+    }
+}
+
 #[derive(Serialize, Deserialize, PrefabData)]
 #[serde(deny_unknown_fields)]
 pub struct WorldPrefabData {
@@ -187,19 +209,20 @@ impl MapPoint {
         None
     }
 
+    /// Returns `Ok(None)` if the point has no combat, or an error if its combat was not loaded.
     pub fn combat<'s, 'a>(
         points: &ReadStorage<'s, MapPoint>,
         combat_store: &'a CombatStore,
         index: usize,
-    ) -> Option<&'a CombatData> {
+    ) -> Result<Option<&'a CombatData>, CombatStoreError> {
         for point in points.join() {
             if point.index == index {
                 if let Some(combat) = &point.combat {
-                    return combat_store.combat_list.get(combat);
+                    return combat_store.get(combat).map(Some);
                 }
             }
         }
-        None
+        Ok(None)
     }
 
     pub fn name_of<'s>(
@@ -266,6 +289,18 @@ pub struct CombatStore {
     pub combat_list: HashMap<String, CombatData>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum CombatStoreError {
+    #[error(display = "no combat named `{}` in `assets/combats`", _0)]
+    UnknownCombat(String),
+}
+
+impl CombatStore {
+    pub fn get(&self, name: &str) -> Result<&CombatData, CombatStoreError> {
+        self.combat_list.get(name).ok_or_else(|| CombatStoreError::UnknownCombat(name.to_string()))
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct DialogueStore {
     pub dialogue_list: HashMap<String, Dialogue>,
@@ -25,6 +25,7 @@ use crate::game::map::{CurrentState, MapPawn, MapRoot, MapPoint, MapStage, Dialo
 use failure::_core::mem::take;
 use crate::core::{get_root, get_root_mut};
 use crate::game::ui::dialogue::{DialogueCompletedEvent, ShowDialogueDisplayEvent};
+use crate::game::combat::difficulty::DifficultyProfile;
 use crate::game::combat::process::Principal;
 use crate::state::map_state::MapState;
 use crate::game::map::systems::movement::MoveTarget;
@@ -57,9 +58,10 @@ impl<'s> System<'s> for MapControlSystem {
         Read<'s, EventChannel<InputEvent<StringBindings>>>,
         Read<'s, EventChannel<DialogueCompletedEvent>>,
         Write<'s, EventChannel<ShowDialogueDisplayEvent>>,
+        Read<'s, DifficultyProfile>,
     );
 
-    fn run(&mut self, (entities, parents, principals, mut transforms, mut map_roots, mut map_pawns, points, move_targets, mut current_state, dialogue_store, combat_store, mut engage_combat, input_events, dialogue_completed_events, mut show_dialogue_events): Self::SystemData) {
+    fn run(&mut self, (entities, parents, principals, mut transforms, mut map_roots, mut map_pawns, points, move_targets, mut current_state, dialogue_store, combat_store, mut engage_combat, input_events, dialogue_completed_events, mut show_dialogue_events, difficulty): Self::SystemData) {
 
         let mut should_engage_combat: bool = false;
         for event in input_events.read(&mut self.input_event_reader) {
@@ -122,13 +124,19 @@ impl<'s> System<'s> for MapControlSystem {
                                 }
                             }
                             if current_state.max_stage == MapStage::Combat {
-                                if let Some(combat_data) = MapPoint::combat(&points, &combat_store, map_root.point_idx) {
-                                    if should_engage_combat {
-                                        *engage_combat = Some(EngageCombat { combat_data: combat_data.clone(), point_idx: map_root.point_idx });
-                                    }
-                                } else {
-                                    current_state.max_stage = MapStage::PostDialogue;
-                                    save_current(&current_state);
+                                match MapPoint::combat(&points, &combat_store, map_root.point_idx) {
+                                    Ok(Some(combat_data)) => {
+                                        if should_engage_combat {
+                                            *engage_combat = Some(EngageCombat { combat_data: combat_data.clone(), point_idx: map_root.point_idx });
+                                        }
+                                    },
+                                    Ok(None) => {
+                                        current_state.max_stage = MapStage::PostDialogue;
+                                        save_current(&current_state);
+                                    },
+                                    // The combats of the map are validated when the `MapBundle` is built, so this is a bug.
+                                    // The campaign must never move past a combat which was not fought.
+                                    Err(err) => panic!("[MapControlSystem] The combat at point {} can not be started: {}", map_root.point_idx, err),
                                 }
                             }
                             if current_state.max_stage == MapStage::PostDialogue {
@@ -150,8 +158,9 @@ impl<'s> System<'s> for MapControlSystem {
                             if current_state.max_stage == MapStage::Complete {
                                 current_state.max_point += 1;
                                 current_state.max_stage = MapStage::PreDialogue;
-                                current_state.master_health_mul += 0.2;
-                                current_state.master_charge_mul += 0.2;
+                                // The profile is the one of the combat which was just won.
+                                current_state.master_health_mul += 0.2 * difficulty.reward;
+                                current_state.master_charge_mul += 0.2 * difficulty.reward;
                                 save_current(&current_state);
                             }
                         }
@@ -47,6 +47,11 @@ pub const ABILITY_COUNT: i32 = 8;
 pub const ABILITY_HEIGHT: f32 = 60.0;
 pub const ABILITY_PANEL_HEIGHT: f32 = ABILITY_HEIGHT * (ABILITY_COUNT as f32);
 pub const ABILITY_PANEL_OFFSET: f32 = STATUS_WIDTH;
+pub const COOLDOWN_COLOR: [f32; 4] = [0.35, 0.35, 0.35, 1.0];
+/// The number of charge options shown for abilities with a range of charge.
+pub const CHARGE_STEP_COUNT: usize = 3;
+pub const CHARGE_STEP_WIDTH: f32 = 45.0;
+pub const CHARGE_STEP_HEIGHT: f32 = 18.0;
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Component)]
 pub struct UiAbilityTargetTag;
@@ -71,6 +76,8 @@ pub struct UiAbility {
     name: String,
     icon: Option<Handle<Texture>>,
     ability_ent: Entity,
+    /// The charge to invest, for the charge options of `AbilityCharge::Range` abilities.
+    charge: Option<f32>,
     invokable: bool,
     hover: bool,
 }
@@ -129,6 +136,8 @@ impl UiAbilitySelection {
 #[derive(Debug, Copy, Clone, new, PartialEq)]
 pub struct UiAbilitySelectEvent {
     pub(crate) ability_ent: Entity,
+    /// The charge chosen for `AbilityCharge::Range` abilities. `None` invests the lowest charge.
+    pub(crate) charge: Option<f32>,
 }
 
 impl<'s> System<'s> for AbilitySelectSystem {
@@ -253,6 +262,7 @@ impl<'s> System<'s> for AbilitySelectSystem {
                                     name: ability.data.name.to_owned(),
                                     ability_ent,
                                     icon: None,
+                                    charge: None,
                                     invokable,
                                     hover: false,
                                 };
@@ -267,6 +277,8 @@ impl<'s> System<'s> for AbilitySelectSystem {
                                         [0.0, 1.0, 1.0, 1.0]
                                     } else if invokable {
                                         [1.0; 4]
+                                    } else if ability.is_on_cooldown() {
+                                        COOLDOWN_COLOR
                                     } else if locked {
                                         [0.2, 0.2, 0.2, 1.0]
                                     } else {
@@ -329,6 +341,62 @@ impl<'s> System<'s> for AbilitySelectSystem {
                                 ui_transforms.insert(charge_text_ent, transform);
                                 parents.insert(charge_text_ent, Parent { entity: charge_ent });
 
+                                // Cooldown Text
+                                if ability.is_on_cooldown() {
+                                    let turns: i32 = ability.current_cooldown;
+                                    let cooldown_text_data: String = format!("{} turn{}", turns, if turns == 1 { "" } else { "s" });
+                                    let cooldown_text_ent: Entity = entities.create();
+                                    let mut cooldown_text = UiText::new(fonts.ability().clone(), cooldown_text_data, COOLDOWN_COLOR, 15.0);
+                                    cooldown_text.align = Anchor::MiddleRight;
+                                    ui_texts.insert(cooldown_text_ent, cooldown_text);
+                                    let id: String = String::from("ability_cooldown_text:") + &cooldown_text_ent.id().to_string();
+                                    let mut transform: UiTransform = UiTransform::new(
+                                        id,
+                                        Anchor::MiddleRight,
+                                        Anchor::MiddleRight,
+                                        -10.0, 0.0, 1.5,
+                                        ABILITY_WIDTH * 0.25, ABILITY_HEIGHT * 0.4,
+                                    );
+                                    transform.opaque = false;
+                                    ui_transforms.insert(cooldown_text_ent, transform);
+                                    parents.insert(cooldown_text_ent, Parent { entity: ui_ability_ent });
+                                }
+
+                                // Charge Options
+                                // Clicking the ability itself invests the lowest charge.
+                                if invokable && ability.data.scaling.is_some() && ability.data.charge.is_variable() {
+                                    for (i, step) in ability.data.charge.steps(CHARGE_STEP_COUNT).into_iter().enumerate() {
+                                        let step_invokable: bool = Character::can_take_turn(&characters, event.character_ent, step);
+                                        let step_ent: Entity = entities.create();
+                                        ui_abilities.insert(step_ent, UiAbility {
+                                            name: ability.data.name.to_owned(),
+                                            ability_ent,
+                                            icon: None,
+                                            charge: Some(step),
+                                            invokable: step_invokable,
+                                            hover: false,
+                                        });
+                                        let step_color: [f32; 4] = if step_invokable { [0.0, 1.0, 1.0, 1.0] } else { [0.4, 0.1, 0.1, 1.0] };
+                                        let mut step_text: UiText = UiText::new(fonts.ability().clone(), format!("{:.0}", step), step_color, 12.0);
+                                        step_text.align = Anchor::Middle;
+                                        ui_texts.insert(step_ent, step_text);
+                                        ui_images.insert(step_ent, UiImage::SolidColor([0.0, 0.0, 0.0, 1.0]));
+                                        let id: String = String::from("ability_charge_step:") + &step_ent.id().to_string();
+                                        let transform: UiTransform = UiTransform::new(
+                                            id,
+                                            Anchor::BottomLeft,
+                                            Anchor::BottomLeft,
+                                            10.0 + (i as f32) * (CHARGE_STEP_WIDTH + 5.0), 3.0, 2.0,
+                                            CHARGE_STEP_WIDTH, CHARGE_STEP_HEIGHT,
+                                        );
+                                        ui_transforms.insert(step_ent, transform);
+                                        if step_invokable {
+                                            interactable.insert(step_ent, Interactable::default());
+                                        }
+                                        parents.insert(step_ent, Parent { entity: ui_ability_ent });
+                                    }
+                                }
+
                                 // Add main ability entity.
                                 if invokable {
                                     interactable.insert(ui_ability_ent, Interactable::default());
@@ -353,7 +421,7 @@ impl<'s> System<'s> for AbilitySelectSystem {
             if ui_event.event_type == UiEventType::Click {
                 if let Some(ui_ability) = ui_abilities.get(ui_event.target) {
                     // Initiate ability target selection.
-                    ability_select_events.single_write(UiAbilitySelectEvent::new(ui_ability.ability_ent));
+                    ability_select_events.single_write(UiAbilitySelectEvent::new(ui_ability.ability_ent, ui_ability.charge));
                 }
             } else if ui_event.event_type == UiEventType::HoverStart {
                 if let Some(ui_ability) = ui_abilities.get_mut(ui_event.target) {
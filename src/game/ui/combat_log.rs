@@ -0,0 +1,146 @@
+use amethyst::{
+    core::Parent,
+    ecs::prelude::*,
+    input::{
+        InputEvent,
+        StringBindings,
+    },
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+    ui::{
+        Anchor,
+        UiImage,
+        UiText,
+        UiTransform,
+    },
+    winit::VirtualKeyCode,
+};
+
+use crate::game::combat::log::CombatLog;
+use crate::game::ui::font::GameFonts;
+
+/// The number of entries which are visible at once.
+pub const LOG_PANEL_LINES: usize = 10;
+pub const LOG_PANEL_WIDTH: f32 = 520.0;
+pub const LOG_LINE_HEIGHT: f32 = 18.0;
+pub const LOG_PANEL_COLOR: [f32; 4] = [0.005, 0.005, 0.006, 0.8];
+pub const LOG_TEXT_COLOR: [f32; 4] = [0.8, 0.8, 0.8, 1.0];
+/// Toggles the panel.
+pub const LOG_TOGGLE_KEY: VirtualKeyCode = VirtualKeyCode::Tab;
+
+/// Shows the latest entries of the `CombatLog`.
+/// The panel is toggled with `LOG_TOGGLE_KEY`, and scrolled through with page up and page down.
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct UiCombatLog {
+    pub visible: bool,
+    /// The number of entries scrolled back from the latest entry.
+    pub scroll: usize,
+    /// The entry count, scroll and visibility which the rows were built for.
+    shown: Option<(usize, usize, bool)>,
+    rows: Vec<Entity>,
+}
+
+impl Default for UiCombatLog {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            scroll: 0,
+            shown: None,
+            rows: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, SystemDesc, new)]
+#[system_desc(name(CombatLogUiSystemDesc))]
+pub struct CombatLogUiSystem {
+    #[system_desc(event_channel_reader)]
+    input_reader: ReaderId<InputEvent<StringBindings>>,
+}
+
+impl<'s> System<'s> for CombatLogUiSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Parent>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiImage>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiCombatLog>,
+        Read<'s, CombatLog>,
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
+        ReadExpect<'s, GameFonts>,
+    );
+
+    fn run(&mut self, (entities, mut parents, mut texts, mut images, mut ui_transforms, mut ui_logs, log, input_events, fonts): Self::SystemData) {
+        let max_scroll: usize = log.entries.len().saturating_sub(LOG_PANEL_LINES);
+        for event in input_events.read(&mut self.input_reader) {
+            for ui_log in (&mut ui_logs).join() {
+                match event {
+                    InputEvent::KeyPressed { key_code, .. } if *key_code == LOG_TOGGLE_KEY => {
+                        ui_log.visible = !ui_log.visible;
+                    },
+                    InputEvent::KeyPressed { key_code: VirtualKeyCode::PageUp, .. } => {
+                        ui_log.scroll = (ui_log.scroll + 1).min(max_scroll);
+                    },
+                    InputEvent::KeyPressed { key_code: VirtualKeyCode::PageDown, .. } => {
+                        ui_log.scroll = ui_log.scroll.saturating_sub(1);
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        let transform_mask = ui_transforms.mask().clone();
+        for (entity, _, _) in (&entities, &ui_logs, !transform_mask).join() {
+            let id: String = String::from("combat_log:") + &entity.id().to_string();
+            ui_transforms.insert(entity, UiTransform::new(
+                id,
+                Anchor::BottomLeft,
+                Anchor::BottomLeft,
+                10.0, 10.0, 0.0,
+                LOG_PANEL_WIDTH, LOG_LINE_HEIGHT * LOG_PANEL_LINES as f32,
+            ));
+        }
+
+        for (entity, ui_log) in (&entities, &mut ui_logs).join() {
+            // Stay on the latest entry unless the player has scrolled back.
+            let scroll: usize = ui_log.scroll.min(max_scroll);
+            let shown = Some((log.entries.len(), scroll, ui_log.visible));
+            if ui_log.shown == shown {
+                continue;
+            }
+            ui_log.shown = shown;
+
+            for row_ent in ui_log.rows.drain(..) {
+                entities.delete(row_ent);
+            }
+            if !ui_log.visible {
+                images.remove(entity);
+                continue;
+            }
+            images.insert(entity, UiImage::SolidColor(LOG_PANEL_COLOR));
+
+            let end: usize = log.entries.len() - scroll;
+            let start: usize = end.saturating_sub(LOG_PANEL_LINES);
+            for (i, entry) in log.entries[start..end].iter().enumerate() {
+                let row_ent: Entity = entities.create();
+                let mut text: UiText = UiText::new(fonts.ability().clone(), entry.describe(), LOG_TEXT_COLOR, 12.0);
+                text.align = Anchor::MiddleLeft;
+                texts.insert(row_ent, text);
+                let id: String = String::from("combat_log_row:") + &row_ent.id().to_string();
+                ui_transforms.insert(row_ent, UiTransform::new(
+                    id,
+                    Anchor::TopLeft,
+                    Anchor::TopLeft,
+                    5.0, -(i as f32) * LOG_LINE_HEIGHT, 1.0,
+                    LOG_PANEL_WIDTH - 10.0, LOG_LINE_HEIGHT,
+                ));
+                parents.insert(row_ent, Parent { entity });
+                ui_log.rows.push(row_ent);
+            }
+        }
+    }
+}
@@ -17,6 +17,7 @@ use amethyst::{
         Transform,
     },
     ecs::prelude::*,
+    log::warn,
     prelude::SystemDesc,
     renderer::{
         ActiveCamera,
@@ -58,17 +59,129 @@ use crate::game::ui::hud::UiBase;
 use crate::game::combat::process::Principal;
 use std::any::TypeId;
 use crate::game::ui::UiDisengageEvent;
+use crate::game::map::{CurrentState, save_current};
 
-#[derive(Debug, Clone, PartialEq)]
+/// A dialogue, as it is stored in `assets/dialogues/<name>.ron`.
+/// Segments are shown in order, unless a segment (or a choice) jumps to a labelled segment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Dialogue {
     pub segments: Vec<DialogueSegment>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Dialogue {
+    pub fn find_label(&self, label: &str) -> Option<usize> {
+        self.segments.iter().position(|segment| segment.label.as_ref().map_or(false, |segment_label| segment_label == label))
+    }
+
+    /// Returns the index of the first segment from `index` onwards whose condition is met, or `None` if the dialogue is over.
+    pub fn resolve(&self, index: usize, state: &CurrentState) -> Option<usize> {
+        (index..self.segments.len()).find(|idx| self.segments[*idx].condition.is_met(state))
+    }
+
+    /// Returns the index which follows the segment at `index`, ignoring any choices.
+    /// An index past the end of the dialogue completes it.
+    pub fn next(&self, index: usize) -> usize {
+        match self.segments.get(index) {
+            Some(segment) => {
+                if segment.end {
+                    self.segments.len()
+                } else if let Some(goto) = &segment.goto {
+                    self.goto(goto)
+                } else {
+                    index + 1
+                }
+            },
+            None => self.segments.len(),
+        }
+    }
+
+    /// Returns the index which follows the choice, which is made from the segment at `index`.
+    pub fn next_from_choice(&self, index: usize, choice: &DialogueChoice) -> usize {
+        match &choice.goto {
+            Some(goto) => self.goto(goto),
+            None => self.next(index),
+        }
+    }
+
+    /// Returns the choices of the segment at `index` whose conditions are met.
+    pub fn choices(&self, index: usize, state: &CurrentState) -> Vec<&DialogueChoice> {
+        match self.segments.get(index) {
+            Some(segment) => segment.choices.iter().filter(|choice| choice.condition.is_met(state)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn goto(&self, label: &str) -> usize {
+        match self.find_label(label) {
+            Some(idx) => idx,
+            None => {
+                warn!("[Dialogue] No segment labelled `{}`, ending the dialogue.", label);
+                self.segments.len()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DialogueSegment {
+    /// Allows segments and choices to jump to this segment.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// The segment is skipped if the condition is not met.
+    #[serde(default)]
+    pub condition: DialogueCondition,
+    #[serde(default)]
     pub header: Option<DialogueText>,
     pub body: DialogueText,
+    /// The path of the icon texture, relative to the assets directory.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// This is loaded from the `icon` path when the dialogue is loaded.
+    #[serde(skip)]
     pub icon_texture: Option<Handle<Texture>>,
+    /// These flags are set when the segment is shown.
+    #[serde(default)]
+    pub set_flags: Vec<String>,
+    /// If there are any choices, the player has to pick one of them to continue.
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+    /// The label of the segment which follows this one.
+    #[serde(default)]
+    pub goto: Option<String>,
+    /// Ends the dialogue after this segment.
+    #[serde(default)]
+    pub end: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// The label of the segment which follows the choice. If this is not specified, the dialogue continues as normal.
+    #[serde(default)]
+    pub goto: Option<String>,
+    /// These flags are set when the choice is made.
+    #[serde(default)]
+    pub set_flags: Vec<String>,
+    /// The choice is hidden if the condition is not met.
+    #[serde(default)]
+    pub condition: DialogueCondition,
+}
+
+/// Tests the flags of the `CurrentState`. An empty condition is always met.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DialogueCondition {
+    /// Every one of these flags must be set.
+    #[serde(default)]
+    pub all: Vec<String>,
+    /// None of these flags may be set.
+    #[serde(default)]
+    pub none: Vec<String>,
+}
+
+impl DialogueCondition {
+    pub fn is_met(&self, state: &CurrentState) -> bool {
+        self.all.iter().all(|flag| state.has_flag(flag)) && !self.none.iter().any(|flag| state.has_flag(flag))
+    }
 }
 
 impl DialogueSegment {
@@ -82,7 +195,9 @@ impl DialogueSegment {
                 color: [1.0; 4],
                 text: body.to_string(),
             },
+            icon: None,
             icon_texture: None,
+            ..Self::empty()
         }
     }
 
@@ -96,17 +211,47 @@ impl DialogueSegment {
                 color: [1.0; 4],
                 text: body.to_string(),
             },
+            icon: None,
             icon_texture: Some(icon_texture),
+            ..Self::empty()
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            label: None,
+            condition: DialogueCondition::default(),
+            header: None,
+            body: DialogueText {
+                color: [1.0; 4],
+                text: String::new(),
+            },
+            icon: None,
+            icon_texture: None,
+            set_flags: Vec::new(),
+            choices: Vec::new(),
+            goto: None,
+            end: false,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DialogueText {
     pub  text: String,
+    #[serde(default = "DialogueText::default_color")]
     pub color: [f32; 4],
 }
 
+impl DialogueText {
+    fn default_color() -> [f32; 4] {
+        [1.0; 4]
+    }
+}
+
+/// The colour of the choices which the player can pick from.
+const CHOICE_COLOR: [f32; 4] = [0.55, 0.75, 1.0, 1.0];
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ShowDialogueDisplayEvent {
     pub owner: Option<Entity>,
@@ -129,6 +274,7 @@ pub struct UiDialogueDisplay {
     pub header_ent: Entity,
     pub body_ent: Entity,
     pub icon_ent: Entity,
+    pub choices_ent: Entity,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -169,11 +315,26 @@ impl<'s> System<'s> for DialogueUiSystem {
         Write<'s, EventChannel<InputEvent<StringBindings>>>,
         Write<'s, EventChannel<DialogueCompletedEvent>>,
         Write<'s, EventChannel<UiDisengageEvent>>,
+        Write<'s, CurrentState>,
     );
 
-    fn run(&mut self, (entities, mut principals, mut parents, teams, mut texts, mut images, mut dialogue_displays, mut ui_transforms, combat_roots, fonts, show_dialogue_events, mut navigate_dialogue_events, input_events, mut dialogue_completed_events, disengage_events): Self::SystemData) {
+    fn run(&mut self, (entities, mut principals, mut parents, teams, mut texts, mut images, mut dialogue_displays, mut ui_transforms, combat_roots, fonts, show_dialogue_events, mut navigate_dialogue_events, input_events, mut dialogue_completed_events, disengage_events, mut current_state): Self::SystemData) {
         for event in show_dialogue_events.read(&mut self.show_dialogue_event_reader) {
-            if let Some(segment) = event.dialogue.segments.get(event.start_idx) {
+            let start_idx: usize = match event.dialogue.resolve(event.start_idx, &current_state) {
+                Some(start_idx) => start_idx,
+                None => {
+                    // Every remaining segment was skipped, so there is nothing to show.
+                    if event.start_idx < event.dialogue.segments.len() {
+                        dialogue_completed_events.single_write(
+                            DialogueCompletedEvent {
+                                owner: event.owner,
+                            }
+                        );
+                    }
+                    continue;
+                },
+            };
+            if let Some(segment) = event.dialogue.segments.get(start_idx) {
                 let dialogue_ent: Entity = entities.create();
                 if event.principal {
                     if Principal::try_root_engage(&parents, &mut principals, event.owner.expect("No owner specified for principal dialogue!"), TypeId::of::<Self>()) != Some(true) {
@@ -246,13 +407,33 @@ impl<'s> System<'s> for DialogueUiSystem {
                 texts.insert(body_ent, text);
                 parents.insert(body_ent, Parent { entity: dialogue_ent });
 
+                let choices_ent: Entity = entities.create();
+                let id: String = String::from("dialogue_choices:") + &choices_ent.id().to_string();
+                let mut ui_transform = UiTransform::new(
+                    id,
+                    Anchor::TopMiddle,
+                    Anchor::TopMiddle,
+                    0.0, -130.0, 1.0,
+                    600.0, 70.0,
+                );
+                ui_transforms.insert(choices_ent, ui_transform);
+                let choices_text: String = Self::choices_text(&event.dialogue.choices(start_idx, &current_state));
+                let mut text = UiText::new(fonts.ability().clone(), choices_text, CHOICE_COLOR, 17.0);
+                text.line_mode = LineMode::Wrap;
+                text.align = Anchor::TopLeft;
+                texts.insert(choices_ent, text);
+                parents.insert(choices_ent, Parent { entity: dialogue_ent });
+
+                Self::set_flags(&mut current_state, &segment.set_flags);
+
                 dialogue_displays.insert(dialogue_ent, UiDialogueDisplay {
                     dialogue: event.dialogue.clone(),
                     owner: event.owner,
-                    current_idx: event.start_idx,
+                    current_idx: start_idx,
                     header_ent,
                     body_ent,
                     icon_ent,
+                    choices_ent,
                 });
             }
         }
@@ -274,6 +455,24 @@ impl<'s> System<'s> for DialogueUiSystem {
                 InputEvent::MouseButtonPressed(MouseButton::Left) => {
                     1
                 },
+                InputEvent::KeyPressed { key_code, .. } => {
+                    if let Some(choice_idx) = Self::choice_idx(*key_code) {
+                        for (dialogue_ent, dialogue_display) in (&entities, &dialogue_displays).join() {
+                            let choices: Vec<&DialogueChoice> = dialogue_display.dialogue.choices(dialogue_display.current_idx, &current_state);
+                            if let Some(choice) = choices.get(choice_idx) {
+                                let index: usize = dialogue_display.dialogue.next_from_choice(dialogue_display.current_idx, choice);
+                                Self::set_flags(&mut current_state, &choice.set_flags);
+                                navigate_dialogue_events.single_write(
+                                    NavigateDialogueEvent {
+                                        dialogue_ent,
+                                        index,
+                                    }
+                                );
+                            }
+                        }
+                    }
+                    0
+                },
                 _ => 0,
             };
 
@@ -292,12 +491,15 @@ impl<'s> System<'s> for DialogueUiSystem {
                 },
                 1 => {
                     for (dialogue_ent, dialogue_display) in (&entities, &dialogue_displays).join() {
-                        navigate_dialogue_events.single_write(
-                            NavigateDialogueEvent {
-                                dialogue_ent,
-                                index: dialogue_display.current_idx + 1,
-                            }
-                        );
+                        // The player has to pick one of the choices to continue.
+                        if dialogue_display.dialogue.choices(dialogue_display.current_idx, &current_state).is_empty() {
+                            navigate_dialogue_events.single_write(
+                                NavigateDialogueEvent {
+                                    dialogue_ent,
+                                    index: dialogue_display.dialogue.next(dialogue_display.current_idx),
+                                }
+                            );
+                        }
                     }
                 },
                 _ => {},
@@ -306,7 +508,9 @@ impl<'s> System<'s> for DialogueUiSystem {
 
         for event in navigate_dialogue_events.read(&mut self.navigate_dialogue_event_reader) {
             if let Some(dialogue_display) = dialogue_displays.get_mut(event.dialogue_ent) {
-                if let Some(segment) = dialogue_display.dialogue.segments.get(event.index) {
+                let resolved: Option<(usize, &DialogueSegment)> = dialogue_display.dialogue.resolve(event.index, &current_state)
+                    .and_then(|index| dialogue_display.dialogue.segments.get(index).map(|segment| (index, segment)));
+                if let Some((index, segment)) = resolved {
                     if let Some(text) = texts.get_mut(dialogue_display.header_ent) {
                         let (header_text, header_color) : (String, [f32; 4]) = match &segment.header {
                             Some(header) => (header.text.clone(), header.color),
@@ -328,7 +532,13 @@ impl<'s> System<'s> for DialogueUiSystem {
                         }
                     }
 
-                    dialogue_display.current_idx = event.index;
+                    if let Some(text) = texts.get_mut(dialogue_display.choices_ent) {
+                        text.text = Self::choices_text(&dialogue_display.dialogue.choices(index, &current_state));
+                    }
+
+                    Self::set_flags(&mut current_state, &segment.set_flags);
+
+                    dialogue_display.current_idx = index;
                 } else {
                     dialogue_completed_events.single_write(
                         DialogueCompletedEvent {
@@ -372,4 +582,41 @@ impl<'s> System<'s> for DialogueUiSystem {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+impl DialogueUiSystem {
+    /// Maps the number keys to the index of the choice which they pick.
+    fn choice_idx(key_code: VirtualKeyCode) -> Option<usize> {
+        match key_code {
+            VirtualKeyCode::Key1 => Some(0),
+            VirtualKeyCode::Key2 => Some(1),
+            VirtualKeyCode::Key3 => Some(2),
+            VirtualKeyCode::Key4 => Some(3),
+            VirtualKeyCode::Key5 => Some(4),
+            VirtualKeyCode::Key6 => Some(5),
+            VirtualKeyCode::Key7 => Some(6),
+            VirtualKeyCode::Key8 => Some(7),
+            VirtualKeyCode::Key9 => Some(8),
+            _ => None,
+        }
+    }
+
+    fn choices_text(choices: &[&DialogueChoice]) -> String {
+        choices.iter()
+            .enumerate()
+            .map(|(idx, choice)| format!("[{}] {}", idx + 1, choice.text))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Sets the flags, and saves the state if any of them were not already set.
+    fn set_flags(current_state: &mut CurrentState, flags: &[String]) {
+        let mut changed: bool = false;
+        for flag in flags.iter() {
+            changed |= current_state.set_flag(flag.clone());
+        }
+        if changed {
+            save_current(current_state);
+        }
+    }
+}
@@ -28,9 +28,10 @@ use amethyst::{
     },
     window::ScreenDimensions,
 };
-use crate::core::{get_root, roll, get_root_mut};
+use crate::core::{CombatRng, get_root, roll, get_root_mut};
 use crate::game::ui::font::GameFonts;
 use crate::game::character::Character;
+use crate::game::combat::replay::ReplayPlayback;
 use crate::game::ui::UiDisengageEvent;
 
 pub const HACK_OPTION_SIZE: f32 = 150.0;
@@ -98,6 +99,8 @@ impl<'s> System<'s> for HackUiSystem {
         Read<'s, EventChannel<UiEvent>>,
         Write<'s, EventChannel<HackSelectedEvent>>,
         Write<'s, EventChannel<UiDisengageEvent>>,
+        Write<'s, CombatRng>,
+        Read<'s, ReplayPlayback>,
     );
 
     fn setup(&mut self, world: &mut World) {
@@ -119,8 +122,12 @@ impl<'s> System<'s> for HackUiSystem {
         ));
     }
 
-    fn run(&mut self, (entities, mut parents, mut transforms, characters, mut texts, mut images, mut interactables, mut hack_options, mut hack_bases, mut hack_removes, hierarchy, fonts, time, hack_events, ui_events, mut hack_selected_events, disengage_events): Self::SystemData) {
+    fn run(&mut self, (entities, mut parents, mut transforms, characters, mut texts, mut images, mut interactables, mut hack_options, mut hack_bases, mut hack_removes, hierarchy, fonts, time, hack_events, ui_events, mut hack_selected_events, disengage_events, mut rng, playback): Self::SystemData) {
         for event in hack_events.read(&mut self.hack_event_reader) {
+            // Replays select the recorded option directly.
+            if playback.is_active() {
+                continue;
+            }
             let base_ent: Entity = entities.create();
 
             let total_width: f32 = (HACK_OPTION_SIZE * event.options.len() as f32) + (HACK_OPTION_SPACING * (event.options.len() as f32 - 1.0));
@@ -250,7 +257,7 @@ impl<'s> System<'s> for HackUiSystem {
                 if let Some(hack_option) = hack_options.get(ui_event.target) {
                     // Initiate ability target selection.
                     if let Some((hack_data, base_ent)) = get_root_mut::<UiHackData, _, _>(&parents, &mut hack_bases, ui_event.target) {
-                        let succeeded = roll(hack_option.chance);
+                        let succeeded = roll(&mut rng, hack_option.chance);
                         hack_selected_events.single_write(
                             HackSelectedEvent {
                                 data: hack_data.clone(),
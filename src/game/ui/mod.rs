@@ -8,6 +8,9 @@ pub mod marker;
 pub mod select_character;
 pub mod select_all_button;
 pub mod turn_notification;
+pub mod turn_order;
+pub mod combat_log;
+pub mod results;
 pub mod map_notification;
 pub mod hack;
 pub mod banner;
@@ -0,0 +1,194 @@
+use amethyst::{
+    core::Parent,
+    ecs::prelude::*,
+    input::{
+        InputEvent,
+        StringBindings,
+    },
+    prelude::SystemDesc,
+    shrev::{
+        EventChannel,
+        ReaderId,
+    },
+    ui::{
+        Anchor,
+        Interactable,
+        UiEvent,
+        UiEventType,
+        UiImage,
+        UiText,
+        UiTransform,
+    },
+    winit::VirtualKeyCode,
+};
+
+use crate::game::combat::Team;
+use crate::game::combat::stats::{CharacterStats, CombatStats};
+use crate::game::combat::systems::standard_combat::ExitCombat;
+use crate::game::ui::font::GameFonts;
+
+pub const RESULTS_WIDTH: f32 = 760.0;
+pub const RESULTS_LINE_HEIGHT: f32 = 24.0;
+pub const RESULTS_PANEL_COLOR: [f32; 4] = [0.005, 0.005, 0.006, 0.95];
+pub const RESULTS_BUTTON_COLOR: [f32; 4] = [0.1, 0.1, 0.1, 1.0];
+pub const RESULTS_BUTTON_HOVER_COLOR: [f32; 4] = [0.2, 0.2, 0.2, 1.0];
+pub const RESULTS_HEADER_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+pub const RESULTS_FRIENDLY_COLOR: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+pub const RESULTS_ENEMY_COLOR: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+/// The results screen which is shown at the end of the combat, until the player dismisses it with the continue button, return or space.
+#[derive(Debug, Copy, Clone, PartialEq, Component)]
+pub struct UiResults;
+
+/// The button which dismisses the results screen.
+#[derive(Debug, Copy, Clone, PartialEq, Component)]
+pub struct UiResultsContinue;
+
+/// Formats a row of the stats table. The font is monospaced, so the columns line up.
+fn stats_row(name: &str, stats: &CharacterStats) -> String {
+    let accuracy: String = stats.accuracy().map_or("-".to_string(), |accuracy| format!("{:.0}%", accuracy * 100.0));
+    format!(
+        "{:<18}{:>9.0}{:>9.0}{:>9}{:>7}{:>9.0}{:>9.0}",
+        name, stats.dmg_dealt, stats.dmg_taken, accuracy, stats.kills, stats.healing, stats.charge_used,
+    )
+}
+
+fn header_row() -> String {
+    format!("{:<18}{:>9}{:>9}{:>9}{:>7}{:>9}{:>9}", "", "Dealt", "Taken", "Aim", "Kills", "Healed", "Charge")
+}
+
+#[derive(Debug, SystemDesc, new)]
+#[system_desc(name(ResultsUiSystemDesc))]
+pub struct ResultsUiSystem {
+    #[system_desc(event_channel_reader)]
+    ui_reader: ReaderId<UiEvent>,
+
+    #[system_desc(event_channel_reader)]
+    input_reader: ReaderId<InputEvent<StringBindings>>,
+}
+
+impl<'s> System<'s> for ResultsUiSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Parent>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiImage>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, Interactable>,
+        WriteStorage<'s, UiResults>,
+        WriteStorage<'s, UiResultsContinue>,
+        Write<'s, Option<ExitCombat>>,
+        Read<'s, CombatStats>,
+        Read<'s, EventChannel<UiEvent>>,
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
+        ReadExpect<'s, GameFonts>,
+    );
+
+    fn run(&mut self, (entities, mut parents, mut texts, mut images, mut ui_transforms, mut interactables, mut results, mut continues, mut exit, stats, ui_events, input_events, fonts): Self::SystemData) {
+        let mut dismiss: bool = false;
+        for ui_event in ui_events.read(&mut self.ui_reader) {
+            if continues.contains(ui_event.target) {
+                match ui_event.event_type {
+                    UiEventType::Click => dismiss = true,
+                    UiEventType::HoverStart => {
+                        if let Some(image) = images.get_mut(ui_event.target) {
+                            *image = UiImage::SolidColor(RESULTS_BUTTON_HOVER_COLOR);
+                        }
+                    },
+                    UiEventType::HoverStop => {
+                        if let Some(image) = images.get_mut(ui_event.target) {
+                            *image = UiImage::SolidColor(RESULTS_BUTTON_COLOR);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+        let shown: bool = results.join().next().is_some();
+        for event in input_events.read(&mut self.input_reader) {
+            match event {
+                InputEvent::KeyPressed { key_code: VirtualKeyCode::Return, .. } | InputEvent::KeyPressed { key_code: VirtualKeyCode::Space, .. } => {
+                    dismiss = dismiss || shown;
+                },
+                _ => {},
+            }
+        }
+
+        let exit: &mut ExitCombat = match exit.as_mut() {
+            Some(exit) => exit,
+            None => return,
+        };
+        if dismiss {
+            exit.dismissed = true;
+            for (entity, parent) in (&entities, &parents).join() {
+                if results.contains(parent.entity) {
+                    entities.delete(entity);
+                }
+            }
+            for (entity, _) in (&entities, &results).join() {
+                entities.delete(entity);
+            }
+            return;
+        }
+        if exit.timer > 0.0 || exit.dismissed || shown {
+            return;
+        }
+
+        // Collect the lines of the screen.
+        let mut lines: Vec<(String, [f32; 4])> = Vec::new();
+        lines.push((match exit.winner() {
+            Some(Team::Friendly) => "Victory".to_string(),
+            Some(Team::Enemy) => "Defeated".to_string(),
+            None if exit.fled => "Escaped".to_string(),
+            None => "Combat Over".to_string(),
+        }, [1.0; 4]));
+        lines.push((header_row(), RESULTS_HEADER_COLOR));
+        for (team, color) in [(Team::Friendly, RESULTS_FRIENDLY_COLOR), (Team::Enemy, RESULTS_ENEMY_COLOR)].iter() {
+            for combatant in stats.team(*team) {
+                lines.push((stats_row(combatant.name, &combatant.stats), *color));
+            }
+            lines.push((stats_row("Total", &stats.team_total(*team)), *color));
+        }
+
+        let panel_ent: Entity = entities.create();
+        let panel_height: f32 = (lines.len() + 2) as f32 * RESULTS_LINE_HEIGHT;
+        results.insert(panel_ent, UiResults);
+        images.insert(panel_ent, UiImage::SolidColor(RESULTS_PANEL_COLOR));
+        ui_transforms.insert(panel_ent, UiTransform::new(
+            String::from("results:") + &panel_ent.id().to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.0, 0.0, 2.0,
+            RESULTS_WIDTH, panel_height,
+        ));
+
+        for (i, (line, color)) in lines.into_iter().enumerate() {
+            let line_ent: Entity = entities.create();
+            let mut text: UiText = UiText::new(fonts.ability().clone(), line, color, if i == 0 { 25.0 } else { 15.0 });
+            text.align = if i == 0 { Anchor::Middle } else { Anchor::MiddleLeft };
+            texts.insert(line_ent, text);
+            ui_transforms.insert(line_ent, UiTransform::new(
+                String::from("results_line:") + &line_ent.id().to_string(),
+                Anchor::TopMiddle,
+                Anchor::TopMiddle,
+                0.0, -(i as f32) * RESULTS_LINE_HEIGHT, 1.0,
+                RESULTS_WIDTH - 20.0, RESULTS_LINE_HEIGHT,
+            ));
+            parents.insert(line_ent, Parent { entity: panel_ent });
+        }
+
+        let button_ent: Entity = entities.create();
+        continues.insert(button_ent, UiResultsContinue);
+        images.insert(button_ent, UiImage::SolidColor(RESULTS_BUTTON_COLOR));
+        texts.insert(button_ent, UiText::new(fonts.ability().clone(), "Continue".to_string(), [1.0; 4], 20.0));
+        interactables.insert(button_ent, Interactable::default());
+        ui_transforms.insert(button_ent, UiTransform::new(
+            String::from("results_continue:") + &button_ent.id().to_string(),
+            Anchor::BottomMiddle,
+            Anchor::BottomMiddle,
+            0.0, 5.0, 1.0,
+            200.0, RESULTS_LINE_HEIGHT * 1.5,
+        ));
+        parents.insert(button_ent, Parent { entity: panel_ent });
+    }
+}
@@ -59,6 +59,7 @@ pub const HEALTH_BAR_COLOR: [f32; 4] = [1.0, 0.1, 0.1, 0.5];
 pub const CHARGE_BAR_COLOR: [f32; 4] = [0.1, 0.1, 1.0, 0.5];
 pub const RANK_ICON_SIZE: f32 = 40.0;
 pub const STATUS_ICON_SIZE: f32 = 35.0;
+pub const STATUS_FALLBACK_COLOR: [f32; 4] = [0.6, 0.3, 0.8, 0.8];
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UiStatus {
@@ -404,8 +405,11 @@ impl<'s> System<'s> for StatusUiSystem {
                     status_icon.count = value;
                     if value > 0 {
                         if let UiImage::Texture(_) = image {}
-                        else {
-                            *image = UiImage::Texture(self.status_textures.get(&status_icon.ty).expect("No status icon for this status!").clone());
+                        else if let Some(status_texture) = self.status_textures.get(&status_icon.ty) {
+                            *image = UiImage::Texture(status_texture.clone());
+                        } else {
+                            // Statuses without an icon are shown as a coloured square.
+                            *image = UiImage::SolidColor(STATUS_FALLBACK_COLOR);
                         }
                         text.text = "x".to_string() + &value.to_string();
                     } else {
@@ -0,0 +1,220 @@
+use amethyst::{
+    core::Parent,
+    ecs::{
+        prelude::*,
+        storage::GenericReadStorage,
+    },
+    prelude::SystemDesc,
+    ui::{
+        Anchor,
+        UiText,
+        UiTransform,
+    },
+};
+
+use crate::game::character::Character;
+use crate::game::combat::{CombatRoot, CombatState, Team, TurnMode};
+use crate::game::combat::initiative::InitiativeQueue;
+use crate::game::combat::spawn::SlotManager;
+use crate::game::combat::status::StatusType;
+use crate::game::ui::font::GameFonts;
+
+/// The maximum number of upcoming actors which are listed.
+pub const TURN_ORDER_LENGTH: usize = 8;
+pub const TURN_ORDER_WIDTH: f32 = 260.0;
+pub const TURN_ORDER_ROW_HEIGHT: f32 = 22.0;
+pub const FRIENDLY_TURN_COLOR: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+pub const ENEMY_TURN_COLOR: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+/// The alpha of the actors which are not acting in the current turn.
+pub const WAITING_ALPHA: f32 = 0.5;
+
+/// Lists the characters which are going to act next, along with the turns they have.
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct UiTurnOrder {
+    pub root_ent: Entity,
+    /// The actors which are currently displayed.
+    pub items: Vec<TurnOrderItem>,
+    pub rows: Vec<Entity>,
+}
+
+impl UiTurnOrder {
+    pub fn new(root_ent: Entity) -> Self {
+        Self {
+            root_ent,
+            items: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// A single upcoming turn of a character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnOrderItem {
+    pub character_ent: Entity,
+    pub name: &'static str,
+    pub team: Team,
+    /// The turns which the character has (or will have once its turn starts).
+    pub turns: i32,
+    /// The extra actions granted by the overclocked status.
+    pub overclocked: usize,
+    pub stunned: bool,
+    /// Whether the character is acting right now.
+    pub active: bool,
+}
+
+impl TurnOrderItem {
+    fn new(character_ent: Entity, character: &Character, team: Team, active: bool) -> Self {
+        Self {
+            character_ent,
+            name: character.name(),
+            team,
+            turns: if active { character.turns() } else { character.base_turns() },
+            overclocked: character.status(StatusType::Overclocked),
+            stunned: character.is_stunned(),
+            active,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text: String = self.name.to_string();
+        if self.stunned {
+            text += " - stunned";
+        } else {
+            text += &format!(" - {} turn{}", self.turns, if self.turns == 1 { "" } else { "s" });
+        }
+        if self.overclocked > 0 {
+            text += &format!(" (+{} overclocked)", self.overclocked);
+        }
+        text
+    }
+
+    pub fn color(&self) -> [f32; 4] {
+        let mut color: [f32; 4] = match self.team {
+            Team::Friendly => FRIENDLY_TURN_COLOR,
+            Team::Enemy => ENEMY_TURN_COLOR,
+        };
+        if !self.active {
+            color[3] = WAITING_ALPHA;
+        }
+        color
+    }
+}
+
+/// Collects the upcoming actors of the combat.
+/// When the teams alternate, this is every character which still has a turn in the current phase, followed by the characters of the following phases.
+pub fn turn_order(
+    root: &CombatRoot,
+    slot_manager: &SlotManager,
+    parents: &impl GenericReadStorage<Component=Parent>,
+    teams: &impl GenericReadStorage<Component=Team>,
+    characters: &impl GenericReadStorage<Component=Character>,
+) -> Vec<TurnOrderItem> {
+    let mut items: Vec<TurnOrderItem> = Vec::new();
+    let phase_team: Team = match root.current_state {
+        CombatState::Init => Team::Friendly,
+        CombatState::InTurn(team) => team,
+        CombatState::DoneTurn(team) => team.other(),
+        CombatState::Victory(_) | CombatState::Fled => return items,
+    };
+    match root.data.turn_mode() {
+        TurnMode::Alternating => {
+            let in_turn: bool = root.current_state == CombatState::InTurn(phase_team);
+            for (i, team) in [phase_team, phase_team.other(), phase_team].iter().enumerate() {
+                for entity_opt in slot_manager.for_team(*team).occupied().iter() {
+                    if let Some(character_ent) = *entity_opt {
+                        if let Some(character) = characters.get(character_ent) {
+                            if i == 0 && in_turn {
+                                // Characters which have already used their turns in this phase are skipped.
+                                if character.has_turn() {
+                                    items.push(TurnOrderItem::new(character_ent, character, *team, true));
+                                }
+                            } else {
+                                items.push(TurnOrderItem::new(character_ent, character, *team, false));
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        TurnMode::Initiative => {
+            let participants: Vec<(Entity, f32)> = InitiativeQueue::participants(slot_manager, characters);
+            let current: Option<Entity> = root.initiative.current();
+            for (i, character_ent) in root.initiative.upcoming(&participants, TURN_ORDER_LENGTH).into_iter().enumerate() {
+                if let Some(character) = characters.get(character_ent) {
+                    if let Some((team, _)) = Team::get_team(parents, teams, character_ent) {
+                        let active: bool = i == 0 && current == Some(character_ent) && character.has_turn();
+                        items.push(TurnOrderItem::new(character_ent, character, team, active));
+                    }
+                }
+            }
+        },
+    }
+    items.truncate(TURN_ORDER_LENGTH);
+    items
+}
+
+/// Keeps the turn order up to date as characters spawn, die, switch teams and use their turns.
+#[derive(Debug, SystemDesc, new)]
+#[system_desc(name(TurnOrderUiSystemDesc))]
+pub struct TurnOrderUiSystem;
+
+impl<'s> System<'s> for TurnOrderUiSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Parent>,
+        ReadStorage<'s, Team>,
+        ReadStorage<'s, Character>,
+        ReadStorage<'s, CombatRoot>,
+        ReadStorage<'s, SlotManager>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiTurnOrder>,
+        WriteStorage<'s, UiTransform>,
+        ReadExpect<'s, GameFonts>,
+    );
+
+    fn run(&mut self, (entities, mut parents, teams, characters, combat_roots, slot_managers, mut texts, mut turn_orders, mut ui_transforms, fonts): Self::SystemData) {
+        let transform_mask = ui_transforms.mask().clone();
+        for (entity, _, _) in (&entities, &turn_orders, !transform_mask).join() {
+            let id: String = String::from("turn_order:") + &entity.id().to_string();
+            let ui_transform = UiTransform::new(
+                id,
+                Anchor::TopRight,
+                Anchor::TopRight,
+                -10.0, -10.0, 0.0,
+                TURN_ORDER_WIDTH, TURN_ORDER_ROW_HEIGHT * TURN_ORDER_LENGTH as f32,
+            );
+            ui_transforms.insert(entity, ui_transform);
+        }
+
+        for (entity, mut ui_turn_order) in (&entities, &mut turn_orders).join() {
+            let items: Vec<TurnOrderItem> = match (combat_roots.get(ui_turn_order.root_ent), slot_managers.get(ui_turn_order.root_ent)) {
+                (Some(root), Some(slot_manager)) => turn_order(root, slot_manager, &parents, &teams, &characters),
+                _ => Vec::new(),
+            };
+            if items == ui_turn_order.items {
+                continue;
+            }
+
+            for row_ent in ui_turn_order.rows.drain(..) {
+                entities.delete(row_ent);
+            }
+            for (i, item) in items.iter().enumerate() {
+                let row_ent: Entity = entities.create();
+                let mut text: UiText = UiText::new(fonts.ability().clone(), item.text(), item.color(), 15.0);
+                text.align = Anchor::MiddleRight;
+                texts.insert(row_ent, text);
+                let id: String = String::from("turn_order_row:") + &row_ent.id().to_string();
+                ui_transforms.insert(row_ent, UiTransform::new(
+                    id,
+                    Anchor::TopMiddle,
+                    Anchor::TopMiddle,
+                    0.0, -(i as f32) * TURN_ORDER_ROW_HEIGHT, 0.0,
+                    TURN_ORDER_WIDTH, TURN_ORDER_ROW_HEIGHT,
+                ));
+                parents.insert(row_ent, Parent { entity });
+                ui_turn_order.rows.push(row_ent);
+            }
+            ui_turn_order.items = items;
+        }
+    }
+}
@@ -13,7 +13,8 @@ extern crate specs_derive;
 
 use amethyst::prelude::*;
 
-use crate::game::combat::CombatData;
+use crate::game::combat::{CombatData, CombatState, Team};
+use crate::game::combat::replay::CombatReplay;
 use amethyst::{
     LoggerConfig,
     StdoutLog,
@@ -44,9 +45,42 @@ fn main() -> Result<(), amethyst::Error> {
             module_levels: vec![],
         }
     );
+    // Simulate a single combat without a window, e.g. `gravity --headless pluto [seed]`.
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() >= 3 && args[1] == "--headless" {
+        let seed: u64 = args.get(3).and_then(|seed| seed.parse().ok()).unwrap_or_else(rand::random);
+        return run_headless(&args[2], seed);
+    }
+    // Play back a recorded combat, e.g. `gravity --replay replay.json`.
+    if args.len() >= 3 && args[1] == "--replay" {
+        return run_replay(&args[2]);
+    }
     //let mut game = core::build_application(state::combat_state::CombatState::with_combat(CombatData::basic("Test Combat", Vec::new())))?;
     let mut game = core::build_application(state::menu_state::MainMenuState::default())?;
     //let mut game = core::build_application(MapState::new(None))?;
     game.run();
     Ok(())
 }
+
+fn run_headless(combat_name: &str, seed: u64) -> Result<(), amethyst::Error> {
+    let combat: CombatData = game::map::combats::combats().get(combat_name).cloned()
+        .map_err(|err| amethyst::Error::from_string(err.to_string()))?;
+    let mut simulation = core::headless::HeadlessCombat::new(combat, seed)?;
+    match simulation.run(core::headless::HEADLESS_DELTA, core::headless::HEADLESS_MAX_TURNS) {
+        Ok(CombatState::Victory(Team::Friendly)) => println!("[{}] Victory", combat_name),
+        Ok(CombatState::Victory(Team::Enemy)) => println!("[{}] Defeat", combat_name),
+        Ok(state) => println!("[{}] Ended in {:?}", combat_name, state),
+        Err(err) => return Err(amethyst::Error::from_string(format!("[{}] {}", combat_name, err))),
+    }
+    Ok(())
+}
+
+fn run_replay(path: &str) -> Result<(), amethyst::Error> {
+    let replay: CombatReplay = CombatReplay::load(path)
+        .map_err(|err| amethyst::Error::from_string(format!("Could not read replay `{}`: {}", path, err)))?;
+    let combat: CombatData = game::map::combats::combats().get(&replay.combat).cloned()
+        .map_err(|err| amethyst::Error::from_string(err.to_string()))?;
+    let mut game = core::build_application(state::combat_state::CombatState::with_combat(combat, None).with_replay(replay))?;
+    game.run();
+    Ok(())
+}
@@ -34,25 +34,16 @@ use nalgebra::{Quaternion, UnitQuaternion};
 use crate::{
     core::action::{Action, Invoke},
     game::{
-        character::master::MasterDrone,
         combat::spawn::{SlotManager, SpawnProcess, SpawnSource},
     },
     state::AggregateData,
 };
 use crate::core::activity::ActivityState;
 use crate::game::character::{Character, CharacterId, CharacterPrefabData, CharacterRole, CharacterSpawnError, CharacterStore, UnassignedCharacter, CharacterData};
-use crate::game::character::spacebot::SpacebotDrone;
-use crate::game::character::spangles::SpanglesDrone;
-use crate::game::character::defender::DefenderDrone;
-use crate::game::combat::{CombatData, CombatRoot, Team, Wave, Rank};
+use crate::game::combat::{CombatData, CombatInstance, CombatRoot, Team, Wave, Rank};
 use crate::game::combat::ability::{AbilityData, AbilityList, AbilityTargetArea, AbilityTargetInfo, AbilityTargetType, AbilityUsability};
-use crate::game::combat::ability::annihilate::AnnihilateAbility;
-use crate::game::combat::ability::barrage::BarrageAbility;
 use crate::game::combat::ability::charge::ChargeAbility;
-use crate::game::combat::ability::focused_charge::FocusedChargeAbility;
-use crate::game::combat::ability::snipe::SnipeAbility;
 use crate::game::combat::ability::spawn::SpawnAbility;
-use crate::game::combat::ability::twin_shot::TwinShotAbility;
 use crate::game::combat::process::Principal;
 use crate::game::combat::spawn::SpawnAction;
 use crate::game::control::camera::combat::CombatCameraTag;
@@ -60,6 +51,8 @@ use crate::game::map::{WorldPrefabData, CombatStore, CurrentState, MapStage, sav
 use crate::game::ui::{
     UiDisengageEvent,
     turn_notification::UiTurnNotification,
+    turn_order::UiTurnOrder,
+    combat_log::UiCombatLog,
 };
 use crate::game::combat::ability::hack::HackAbility;
 use crate::game::combat::systems::enemy_wave::SpawnWaveEvent;
@@ -68,11 +61,16 @@ use crate::game::combat::systems::standard_combat::{ExitCombat, EXIT_TIMER};
 use crate::state::map_state::MapState;
 use std::fs::File;
 use crate::game::ui::dialogue::ShowDialogueDisplayEvent;
+use crate::game::combat::replay::{CombatReplay, ReplayPlayback, ReplayRecorder, REPLAY_PATH};
+use crate::game::combat::log::{CombatLog, COMBAT_LOG_PATH};
+use crate::game::combat::stats::CombatStats;
 
 #[derive(Debug, Clone)]
 pub struct CombatState {
     combat: CombatData,
     point_idx: Option<usize>,
+    seed: Option<u64>,
+    replay: Option<CombatReplay>,
     combat_root: Option<Entity>,
     friendly_root: Option<Entity>,
     enemy_root: Option<Entity>,
@@ -83,11 +81,26 @@ impl CombatState {
         Self {
             combat,
             point_idx,
+            seed: None,
+            replay: None,
             combat_root: None,
             friendly_root: None,
             enemy_root: None,
         }
     }
+
+    /// Uses a fixed seed for the combat's randomness, so that a previous fight can be reproduced.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Plays back a recorded combat. The recorded abilities are performed instead of waiting for the player or AI.
+    pub fn with_replay(mut self, replay: CombatReplay) -> Self {
+        self.seed = Some(replay.seed);
+        self.replay = Some(replay);
+        self
+    }
 }
 
 impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
@@ -113,38 +126,23 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
         world.create_entity().with(map_handle).build();
 
         // Set up our combat instance.
-        world.exec(|(entities, mut parents, mut transforms, mut roots, mut principals, mut slot_managers, mut teams): (Entities, WriteStorage<Parent>, WriteStorage<Transform>, WriteStorage<CombatRoot>, WriteStorage<Principal>, WriteStorage<SlotManager>, WriteStorage<Team>)| {
-            let root_ent = entities.create();
-            let root: CombatRoot = CombatRoot::new(combat_data.clone());
-            let slot_manager: SlotManager = SlotManager::new();
-            let principal: Principal = Principal::new();
-
-            roots.insert(root_ent, root);
-            slot_managers.insert(root_ent, slot_manager);
-            principals.insert(root_ent, principal);
-            transforms.insert(root_ent, Transform::default());
-
-            self.combat_root = Some(root_ent);
-
-            // Set up teams.
-            let friendly_ent: Entity = entities.create();
-            teams.insert(friendly_ent, Team::Friendly);
-            parents.insert(friendly_ent, Parent { entity: root_ent });
-            // Set transform.
-            let mut friendly_transform: Transform = Transform::from(Vector3::new(0.0, 0.0, 30.0));
-            transforms.insert(friendly_ent, friendly_transform);
-
-            let enemy_ent: Entity = entities.create();
-            teams.insert(enemy_ent, Team::Enemy);
-            parents.insert(enemy_ent, Parent { entity: root_ent });
-            // Set transform.
-            let mut enemy_transform: Transform = Transform::from(Vector3::new(0.0, 0.0, -30.0));
-            enemy_transform.append_rotation_y_axis(std::f32::consts::PI);
-            transforms.insert(enemy_ent, enemy_transform);
+        if let Some(replay) = &self.replay {
+            *world.write_resource::<CurrentState>() = replay.state.clone();
+        }
+        let seed: u64 = self.seed.unwrap_or_else(rand::random);
+        let instance: CombatInstance = CombatInstance::create(world, combat_data.clone(), seed);
+        self.combat_root = Some(instance.root_ent);
+        self.friendly_root = Some(instance.friendly_ent);
+        self.enemy_root = Some(instance.enemy_ent);
 
-            self.friendly_root = Some(friendly_ent);
-            self.enemy_root = Some(enemy_ent);
-        });
+        // Either play back the specified replay or record a new one.
+        if let Some(replay) = self.replay.clone() {
+            world.insert(ReplayPlayback::new(replay));
+        } else {
+            world.insert(ReplayPlayback::default());
+            world.write_resource::<ReplayRecorder>().path = Some(REPLAY_PATH.to_string());
+        }
+        world.write_resource::<CombatLog>().path = Some(COMBAT_LOG_PATH.to_string());
 
         // Add ui.
         let turn_notification_ent = world.entities().create();
@@ -153,6 +151,10 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
             root_ent: self.combat_root.unwrap(),
             precursor_text: "Turn: ".to_string(),
         });
+        let turn_order_ent = world.entities().create();
+        world.write_storage::<UiTurnOrder>().insert(turn_order_ent, UiTurnOrder::new(self.combat_root.unwrap()));
+        let combat_log_ent = world.entities().create();
+        world.write_storage::<UiCombatLog>().insert(combat_log_ent, UiCombatLog::default());
 
         // Particle TEST
         //world.write_storage::<>
@@ -190,40 +192,8 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
             auto_fovs.insert(camera_ent, auto_fov);
         });
 
-        // Spawn our master drone.
-//        Character::spawn_to_world(
-//            world,
-//            self.friendly_root.unwrap(),
-//            MasterDrone::character_id(),
-//            Team::Friendly,
-//            0,
-//        );
-
-        let current_state: CurrentState = *world.read_resource::<CurrentState>();
-
-        let mut master_data: CharacterData = MasterDrone::data();
-        master_data.max_health *= current_state.master_health_mul;
-        master_data.max_charge *= current_state.master_charge_mul;
-        master_data.artificial_charge *= current_state.master_charge_mul;
-        // Spawn friendlies.
-        Character::spawn_to_world(
-            world,
-            self.friendly_root.unwrap(),
-            MasterDrone::character_id(),
-            Some(master_data),
-            Rank::Basic,
-            Team::Friendly,
-            0,
-            true,
-        );
-
-        world.write_resource::<EventChannel<SpawnWaveEvent>>().single_write(
-            SpawnWaveEvent {
-                team_ent: self.enemy_root.unwrap(),
-                idx: 0,
-                wave: combat_data.waves().get(0).expect("The combat has no waves!").clone(),
-            }
-        );
+        // Spawn our master drone and the first wave.
+        instance.begin(world, &combat_data);
 
         // Rebuild view.
         *world.write_resource::<RebuildRendering>() = RebuildRendering(true);
@@ -233,20 +203,25 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
 
         let delta: f32 = data.world.read_resource::<Time>().delta_seconds();
         if let Some(mut exit_opt) = data.world.try_fetch_mut::<Option<ExitCombat>>() {
-            let mut exit_idx: Option<(usize, Option<Team>)> = None;
+            let mut exit_idx: Option<(usize, Option<Team>, bool)> = None;
             if let Some(exit) = exit_opt.as_mut() {
                 exit.timer -= delta;
-                if exit.timer <= 0.0 {
-                    if let Some(point_idx) = self.point_idx {
-                        exit_idx = Some((point_idx, exit.winner));
+                // The results screen is shown once the timer runs out, and the combat is left when it is dismissed.
+                if exit.timer <= 0.0 && exit.dismissed {
+                    match self.point_idx {
+                        Some(point_idx) => exit_idx = Some((point_idx, exit.winner, exit.fled)),
+                        // A combat which is not part of the campaign, such as a replay, ends the game without touching the save.
+                        None => return Trans::Quit,
                     }
                 }
             }
-            if let Some((exit_idx, winner)) = exit_idx {
+            if let Some((exit_idx, winner, fled)) = exit_idx {
                 *exit_opt = None;
                 // Progress game.
                 if let Some(mut current_state) = data.world.try_fetch_mut::<CurrentState>() {
-                    if current_state.max_point == exit_idx && winner == Some(Team::Friendly) {
+                    let progressed: bool = current_state.max_point == exit_idx && winner == Some(Team::Friendly);
+                    // Fleeing does not progress the campaign, but the master keeps its damage.
+                    if progressed || fled {
                         if let Some(slot_manager) = data.world.read_storage::<SlotManager>().get(self.combat_root.unwrap()) {
                             if let Some(master) = slot_manager.friendly.master() {
                                 if let Some(character) = data.world.read_storage::<Character>().get(master) {
@@ -254,10 +229,13 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
                                 }
                             }
                         }
+                    }
+                    if progressed {
                         if current_state.max_stage != MapStage::Complete {
                             current_state.max_stage = MapStage::PostDialogue;
                         }
                     }
+                    current_state.lifetime_stats.record(&data.world.read_resource::<CombatStats>(), winner);
 
                     save_current(&current_state);
                     return Trans::Switch(
@@ -282,6 +260,8 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
         match &event {
             StateEvent::Window(event) => {
                 if is_close_requested(&event) {
+                    // Keep the replay of unfinished combats too, so that soft-locks can be reproduced.
+                    data.world.write_resource::<ReplayRecorder>().write();
                     Trans::Quit
                 } else {
                     Trans::None
@@ -302,7 +282,7 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
                         has_cheats = state.has_cheats;
                     }
                     if has_cheats {
-                        data.world.insert(Some(ExitCombat { timer: 0.0, winner: Some(Team::Friendly) }));
+                        data.world.insert(Some(ExitCombat::new(0.0, Some(Team::Friendly))));
                     }
                 }
                 if let InputEvent::KeyPressed { key_code: VirtualKeyCode::L, .. } = input {
@@ -311,7 +291,7 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for CombatState {
                         has_cheats = state.has_cheats;
                     }
                     if has_cheats {
-                        data.world.insert(Some(ExitCombat { timer: 0.0, winner: Some(Team::Enemy) }));
+                        data.world.insert(Some(ExitCombat::new(0.0, Some(Team::Enemy))));
                     }
                 }
                 if let InputEvent::KeyPressed { key_code: VirtualKeyCode::H, .. } = input {
@@ -34,14 +34,13 @@ use nalgebra::{Quaternion, UnitQuaternion};
 use crate::{
     core::action::{Action, Invoke},
     game::{
-        character::master::MasterDrone,
         combat::spawn::{SlotManager, SpawnProcess, SpawnSource},
     },
     state::AggregateData,
 };
 use crate::game::combat::CombatData;
 use crate::game::map::{MapPrefabData, CurrentState, MapStage, MapPawn, MapPoint, MapRoot, EngageCombat};
-use crate::game::character::{CharacterPrefabData, CharacterStore};
+use crate::game::character::{CharacterId, CharacterPrefabData, CharacterStore};
 use std::thread::sleep;
 use crate::state::combat_state::CombatState;
 use crate::game::combat::process::Principal;
@@ -118,7 +117,7 @@ impl<'a, 'b> State<AggregateData<'a, 'b>, StateEvent> for MapState {
         // Add the loaded entities to the scene.
         let mut map_ent: Entity = data.world.create_entity().with(map_handle).build();
 
-        let master_handle: Handle<Prefab<CharacterPrefabData>> = data.world.read_resource::<CharacterStore>().prefab(&MasterDrone::character_id()).expect("No master drone prefab!").clone();
+        let master_handle: Handle<Prefab<CharacterPrefabData>> = data.world.read_resource::<CharacterStore>().prefab(&CharacterId::MASTER).expect("No master drone prefab!").clone();
 
         let pawn_ent: Entity = data.world.create_entity().with(MapPawn::default()).with(master_handle).build();
 
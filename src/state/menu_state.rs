@@ -72,7 +72,6 @@ use crate::state::AggregateData;
 use crate::state::combat_state::CombatState;
 use crate::state::map_state::MapState;
 
-use game::character::spacebot::SpacebotDrone;
 use crate::game::map::{CurrentState, save_current};
 
 pub const CAMERA_ROTATION_SPEED: f32 = 0.02;